@@ -1,11 +1,14 @@
 use crate::compiler::Compiler;
-use crate::model::ProjectState;
+use crate::history::History;
+use crate::model::{ExportMode, ProjectState};
 use crate::theme::{self, ThemeMode};
 use crate::ui::{
-    assets, canvas, code_preview, hierarchy, inspector, palette, variables, EditorContext,
+    assets, canvas, code_preview, hierarchy, history, inspector, palette, play_log, variables, EditorContext,
 };
-use crate::validator::{CodeValidator, ValidationStatus};
-use crate::widgets::{ButtonWidget, LabelWidget, VerticalLayout};
+use crate::preview::PreviewState;
+use crate::runner::PlaySession;
+use crate::validator::{BackgroundValidation, ValidationStatus};
+use crate::widgets::{ButtonWidget, ContextMenuAction, LabelWidget, VerticalLayout};
 use eframe::App;
 use egui::RichText;
 
@@ -17,14 +20,22 @@ pub struct AetherApp {
     project_state: ProjectState,
 
     // Undo/Redo history
-    undo_stack: Vec<ProjectState>,
-    redo_stack: Vec<ProjectState>,
+    history: History,
 
     // Clipboard for copy/paste
-    clipboard: Option<String>, // JSON representation of copied widget
+    clipboard: Option<String>, // JSON of the copied widgets, also put on the OS clipboard
 
     // Code validation status
     validation_status: ValidationStatus,
+    // Running `cargo check`, if any
+    validation_job: Option<BackgroundValidation>,
+
+    // Generated app launched with Play, if any
+    play_session: Option<PlaySession>,
+    // Restart the played app when the project changes
+    play_auto_rebuild: bool,
+    // Time of the last check for project changes while playing
+    play_last_change_check: f64,
 
     // Theme mode (Light or Dark)
     theme_mode: ThemeMode,
@@ -37,6 +48,9 @@ pub struct AetherApp {
 
     // Canvas pan offset
     canvas_pan: egui::Vec2,
+
+    // Interactive canvas preview, while the Preview toggle is on
+    preview: Option<PreviewState>,
 }
 
 // UI State implementation
@@ -50,7 +64,9 @@ pub struct UiState {
     pub right_bottom_tab: RightBottomTab, // New split for Inspector/Variables
 
     pub show_code_preview: bool,
+    pub show_history: bool,
     pub show_project_settings: bool,
+    pub show_play_log: bool,
 }
 
 impl Default for UiState {
@@ -62,7 +78,9 @@ impl Default for UiState {
             left_tab: LeftTab::Palette,
             right_bottom_tab: RightBottomTab::Inspector,
             show_code_preview: false,
+            show_history: false,
             show_project_settings: false,
+            show_play_log: false,
         }
     }
 }
@@ -96,105 +114,108 @@ impl AetherApp {
             ..Default::default()
         }));
 
+        let project_state = ProjectState::new(Box::new(root));
         Self {
             ui_state: UiState::default(),
-            project_state: ProjectState::new(Box::new(root)),
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
+            history: History::new(&project_state),
+            project_state,
             clipboard: None,
             validation_status: ValidationStatus::NotRun,
+            validation_job: None,
+            play_session: None,
+            play_auto_rebuild: false,
+            play_last_change_check: 0.0,
             theme_mode: ThemeMode::Dark,
             theme_initialized: false,
             canvas_zoom: 1.0,
             canvas_pan: egui::Vec2::ZERO,
+            preview: None,
         }
     }
 
-    /// Push the current state onto the undo stack before making a change
-    fn push_undo(&mut self) {
-        self.undo_stack.push(self.project_state.clone());
-        // Clear redo stack when making a new change
-        self.redo_stack.clear();
-
-        // Limit undo stack size to prevent excessive memory usage
-        const MAX_UNDO_STEPS: usize = 50;
-        if self.undo_stack.len() > MAX_UNDO_STEPS {
-            self.undo_stack.remove(0);
+    /// Start `cargo check` in the background, cancelling any check already running
+    fn start_validation(&mut self, ctx: &egui::Context) {
+        if let Some(job) = self.validation_job.take() {
+            job.cancel();
         }
+        let repaint = ctx.clone();
+        self.validation_status = ValidationStatus::Checking("Preparing...".to_string());
+        self.validation_job = Some(BackgroundValidation::start(
+            &self.project_state,
+            Some(BackgroundValidation::DEFAULT_TIMEOUT),
+            move || repaint.request_repaint(),
+        ));
+    }
+
+    /// Build and launch the generated app, replacing any session already running
+    fn start_play(&mut self, ctx: &egui::Context) {
+        // Dropping the old session stops it
+        self.play_session = None;
+        let repaint = ctx.clone();
+        self.play_session = Some(PlaySession::start(&self.project_state, move || repaint.request_repaint()));
+        self.ui_state.show_play_log = true;
     }
 
     /// Undo the last action
     fn undo(&mut self) {
-        if let Some(previous_state) = self.undo_stack.pop() {
-            // Push current state to redo stack
-            self.redo_stack.push(self.project_state.clone());
-            // Restore previous state
-            self.project_state = previous_state;
-        }
+        self.history.undo(&mut self.project_state);
     }
 
     /// Redo the last undone action
     fn redo(&mut self) {
-        if let Some(next_state) = self.redo_stack.pop() {
-            // Push current state to undo stack
-            self.undo_stack.push(self.project_state.clone());
-            // Restore next state
-            self.project_state = next_state;
-        }
+        self.history.redo(&mut self.project_state);
     }
 
-    fn can_undo(&self) -> bool {
-        !self.undo_stack.is_empty()
-    }
-
-    fn can_redo(&self) -> bool {
-        !self.redo_stack.is_empty()
-    }
-
-    /// Copy selected widget to clipboard
-    fn copy_widget(&mut self) {
-        if let Some(id) = self.project_state.selection.iter().next().cloned() {
-            if let Some(node) = self.project_state.find_node_mut(id) {
-                // Serialize the widget to JSON
-                if let Ok(json) = serde_json::to_string(node) {
-                    self.clipboard = Some(json);
+    /// Carry out a context menu action on a widget, selecting what it leaves behind
+    fn apply_context_action(&mut self, widget_id: uuid::Uuid, action: ContextMenuAction) {
+        let project = &mut self.project_state;
+        let selected = match action {
+            ContextMenuAction::None => return,
+            ContextMenuAction::Delete => {
+                if project.delete_widget(widget_id) {
+                    project.selection.remove(&widget_id);
                 }
+                return;
+            }
+            ContextMenuAction::MoveUp => {
+                project.move_widget_up(widget_id);
+                return;
+            }
+            ContextMenuAction::MoveDown => {
+                project.move_widget_down(widget_id);
+                return;
             }
+            ContextMenuAction::Duplicate => project.duplicate_widget(widget_id).map(|id| vec![id]),
+            ContextMenuAction::WrapIn(container) => project.wrap_widget(widget_id, container).map(|id| vec![id]),
+            ContextMenuAction::Unwrap => project.unwrap_container(widget_id),
+            ContextMenuAction::ConvertTo(container) => {
+                project.convert_container(widget_id, container).then(|| vec![widget_id])
+            }
+        };
+        if let Some(ids) = selected {
+            project.selection = ids.into_iter().collect();
         }
     }
 
-    /// Paste widget from clipboard (with new UUID)
-    fn paste_widget(&mut self) {
-        if let Some(json) = &self.clipboard {
-            // Deserialize the widget
-            if let Ok(mut widget) = serde_json::from_str::<Box<dyn crate::model::WidgetNode>>(json)
-            {
-                self.push_undo();
-
-                // Regenerate UUID to make it unique
-                widget = regenerate_widget_ids(widget);
-
-                // Try to add to root if it's a layout
-                if let Some(children) = self.project_state.root_node.children_mut() {
-                    children.push(widget);
-                }
-            }
+    /// Copy the selected widgets, here and to the OS clipboard so other editors can paste them
+    fn copy_widgets(&mut self, ctx: &egui::Context) {
+        let selection: Vec<_> = self.project_state.selection.iter().copied().collect();
+        if let Some(json) = self.project_state.copy_widgets(&selection) {
+            ctx.copy_text(json.clone());
+            self.clipboard = Some(json);
         }
     }
-}
 
-/// Recursively regenerate all UUIDs in a widget tree
-fn regenerate_widget_ids(
-    widget: Box<dyn crate::model::WidgetNode>,
-) -> Box<dyn crate::model::WidgetNode> {
-    // Clone the widget and serialize/deserialize to get a fresh copy
-    // Then we can modify it
-    let cloned = widget.clone_box();
-
-    // This is a simple approach: serialize and deserialize, which will generate new UUIDs
-    // if we modify the JSON. For now, just return the cloned widget.
-    // A more sophisticated approach would traverse and regenerate IDs explicitly.
-    cloned
+    /// Paste copied widgets with fresh ids into or after the selection.
+    /// `text` is pasted from the OS clipboard, falling back to the last copy made here.
+    fn paste_widgets(&mut self, text: Option<String>) {
+        if let Some(text) = text.filter(|text| crate::model::parse_clipboard(text).is_some()) {
+            self.clipboard = Some(text);
+        }
+        if let Some(json) = &self.clipboard {
+            self.project_state.paste_widgets(json);
+        }
+    }
 }
 
 impl App for AetherApp {
@@ -206,32 +227,86 @@ impl App for AetherApp {
             self.theme_initialized = true;
         }
 
-        // Process pending reorder operation with undo support
-        if let Some((source_id, target_id)) = self.project_state.pending_reorder.take() {
-            self.push_undo();
+        // Pick up output from the played app, rebuilding it if the project changed
+        if let Some(session) = &mut self.play_session {
+            session.poll();
+            let now = ctx.input(|i| i.time);
+            if self.play_auto_rebuild && now - self.play_last_change_check > 1.0 {
+                self.play_last_change_check = now;
+                if session.is_stale(&self.project_state) {
+                    self.start_play(ctx);
+                }
+            }
+        }
 
-            // Find the parent and apply reorder
-            if let Some(parent) = self.project_state.find_parent_mut(source_id) {
-                if let Some(children) = parent.children() {
-                    if let Some(target_idx) = children.iter().position(|c| c.id() == target_id) {
-                        self.project_state.reorder_widget(source_id, target_idx);
-                    }
+        // Pick up progress from a running validation
+        if let Some(job) = &self.validation_job {
+            if job.poll(&mut self.validation_status) {
+                self.validation_job = None;
+            }
+        }
+
+        // Edits only happen in response to input, so frames without any (hovering, or
+        // repaints for play output and validation progress) skip the undo comparison
+        let may_edit = ctx.input(|i| {
+            i.pointer.any_down()
+                || !i.raw.dropped_files.is_empty()
+                || i.events.iter().any(|e| !matches!(e, egui::Event::PointerMoved(_) | egui::Event::MouseMoved(_)))
+        });
+        if may_edit {
+            self.history.mark_changed();
+        }
+
+        // Process pending reorder operation
+        if let Some((source_id, target_id)) = self.project_state.pending_reorder.take() {
+            self.history.mark_changed();
+            // Reorder only between siblings in the same slot
+            let source = self.project_state.child_position(source_id);
+            let target = self.project_state.child_position(target_id);
+            if let (Some(source), Some(target)) = (source, target) {
+                if (source.parent, source.slot) == (target.parent, target.slot) {
+                    self.project_state.reorder_widget(source_id, target.index);
                 }
             }
         }
 
-        // Handle keyboard shortcuts
-        ctx.input(|i| {
-            // Copy: Ctrl+C (Cmd+C on Mac)
-            if i.modifiers.command && i.key_pressed(egui::Key::C) && !i.modifiers.shift {
-                self.copy_widget();
+        // Apply a bulk property edit from the Inspector
+        if let Some(edit) = self.project_state.pending_property_edit.take() {
+            self.history.mark_changed();
+            self.project_state.set_property(&edit.widgets, &edit.property, &edit.value);
+        }
+
+        // Apply an action picked from a widget's context menu on the canvas
+        let context_action = ctx.memory_mut(|mem| mem.data.remove_temp(ContextMenuAction::id()));
+        if let Some((widget_id, action)) = context_action {
+            self.history.mark_changed();
+            self.history.checkpoint();
+            self.apply_context_action(widget_id, action);
+        }
+
+        // Copy and paste shortcuts arrive as events, the paste carrying the OS clipboard text.
+        // They belong to the text field instead while one is focused.
+        if !ctx.wants_keyboard_input() {
+            let (copy, paste) = ctx.input(|i| {
+                let copy = i.events.iter().any(|e| matches!(e, egui::Event::Copy));
+                let paste = i.events.iter().find_map(|e| match e {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                });
+                (copy, paste)
+            });
+            if copy {
+                self.copy_widgets(ctx);
             }
-            // Paste: Ctrl+V (Cmd+V on Mac)
-            else if i.modifiers.command && i.key_pressed(egui::Key::V) {
-                self.paste_widget();
+            if paste.is_some() {
+                self.paste_widgets(paste);
             }
+        }
+
+        // Handle keyboard shortcuts
+        ctx.input(|i| {
             // Undo: Ctrl+Z (Cmd+Z on Mac)
-            else if i.modifiers.command && i.key_pressed(egui::Key::Z) && !i.modifiers.shift {
+            if i.modifiers.command && i.key_pressed(egui::Key::Z) && !i.modifiers.shift {
                 self.undo();
             }
             // Redo: Ctrl+Shift+Z or Ctrl+Y (Cmd+Shift+Z or Cmd+Y on Mac)
@@ -243,7 +318,6 @@ impl App for AetherApp {
             // Delete widget
             else if i.key_pressed(egui::Key::Delete) {
                 if let Some(id) = self.project_state.selection.iter().next().cloned() {
-                    self.push_undo();
                     if self.project_state.delete_widget(id) {
                         self.project_state.selection.clear();
                     }
@@ -277,7 +351,6 @@ impl App for AetherApp {
                     ui.separator();
 
                     if ui.button("Save Project").clicked() {
-                        self.push_undo();
                         if let Some(path) = crate::io::save_file("project.json") {
                             if let Ok(file) = std::fs::File::create(path) {
                                 let _ = serde_json::to_writer_pretty(file, &self.project_state);
@@ -286,7 +359,6 @@ impl App for AetherApp {
                         ui.close();
                     }
                     if ui.button("Load Project").clicked() {
-                        self.push_undo();
                         if let Some(path) = crate::io::pick_file("Aether Project") {
                             if let Ok(file) = std::fs::File::open(path) {
                                 let reader = std::io::BufReader::new(file);
@@ -300,6 +372,19 @@ impl App for AetherApp {
                         ui.close();
                     }
 
+                    if ui.button("Import Rust Code...").clicked() {
+                        if let Some(path) = crate::io::pick_rust_file() {
+                            match crate::io::read_file(&path) {
+                                Ok(source) => match crate::importer::Importer::import_source(&source) {
+                                    Ok(state) => self.project_state = state,
+                                    Err(e) => eprintln!("Failed to import Rust code: {}", e),
+                                },
+                                Err(e) => eprintln!("Failed to read {}: {}", path.display(), e),
+                            }
+                        }
+                        ui.close();
+                    }
+
                     ui.separator();
 
                     if ui.button("Project Settings...").clicked() {
@@ -310,52 +395,42 @@ impl App for AetherApp {
                     ui.menu_button("Export...", |ui| {
                         if ui.button("Export to Folder").clicked() {
                             if let Some(folder) = crate::io::pick_folder() {
-                                // Re-using export logic
-                                let src_dir = folder.join("src");
-                                let _ = std::fs::create_dir_all(&src_dir);
-                                
-                                let cargo_toml_path = folder.join("Cargo.toml");
-                                let cargo_toml = Compiler::generate_cargo_toml(&self.project_state.project_name);
-                                let _ = std::fs::write(&cargo_toml_path, cargo_toml);
-
-                                let main_rs_path = src_dir.join("main.rs");
-                                let main_rs = Compiler::generate_main_rs();
-                                let _ = std::fs::write(&main_rs_path, main_rs);
-
-                                let app_rs_path = src_dir.join("app.rs");
-                                let app_rs = Compiler::generate_app_rs(&self.project_state);
-                                let _ = std::fs::write(&app_rs_path, app_rs);
-                                
-                                // Copy assets...
-                                if !self.project_state.assets.assets.is_empty() {
-                                    let assets_dir = folder.join("assets");
-                                    let _ = std::fs::create_dir_all(&assets_dir);
-                                    for asset in self.project_state.assets.assets.values() {
-                                        if let Some(name) = asset.path.file_name() {
-                                            let _ = std::fs::copy(&asset.path, assets_dir.join(name));
-                                        }
-                                    }
+                                // handlers.rs is only created on the first export
+                                if let Err(e) = Compiler::export_project(&self.project_state, &folder) {
+                                    eprintln!("Failed to export project: {}", e);
+                                }
+
+                                if let Err(e) = Compiler::copy_assets(&self.project_state, &folder) {
+                                    eprintln!("Failed to copy assets: {}", e);
                                 }
                             }
                             ui.close();
                         }
                         if ui.button("Print App Code").clicked() {
-                            println!("{}", Compiler::generate_app_rs(&self.project_state));
+                            println!("{}", Compiler::generate_primary_rs(&self.project_state));
                             ui.close();
                         }
                     });
                 });
 
                 ui.menu_button("Edit", |ui| {
+                    let undo_text = match self.history.undo_entries().last() {
+                        Some(entry) => format!("Undo {}", entry.label),
+                        None => "Undo".to_string(),
+                    };
+                    let redo_text = match self.history.redo_entries().last() {
+                        Some(entry) => format!("Redo {}", entry.label),
+                        None => "Redo".to_string(),
+                    };
                     if ui
-                        .add_enabled(self.can_undo(), egui::Button::new("Undo"))
+                        .add_enabled(self.history.can_undo(), egui::Button::new(undo_text))
                         .clicked()
                     {
                         self.undo();
                         ui.close();
                     }
                     if ui
-                        .add_enabled(self.can_redo(), egui::Button::new("Redo"))
+                        .add_enabled(self.history.can_redo(), egui::Button::new(redo_text))
                         .clicked()
                     {
                         self.redo();
@@ -369,14 +444,14 @@ impl App for AetherApp {
                         .add_enabled(has_selection, egui::Button::new("Copy"))
                         .clicked()
                     {
-                        self.copy_widget();
+                        self.copy_widgets(ctx);
                         ui.close();
                     }
                     if ui
                         .add_enabled(self.clipboard.is_some(), egui::Button::new("Paste"))
                         .clicked()
                     {
-                        self.paste_widget();
+                        self.paste_widgets(None);
                         ui.close();
                     }
                 });
@@ -395,6 +470,12 @@ impl App for AetherApp {
                     if ui.checkbox(&mut self.ui_state.show_code_preview, "Code Preview Window").clicked() {
                          ui.close();
                     }
+                    if ui.checkbox(&mut self.ui_state.show_history, "History Window").clicked() {
+                        ui.close();
+                    }
+                    if ui.checkbox(&mut self.ui_state.show_play_log, "Play Log Window").clicked() {
+                        ui.close();
+                    }
                     ui.separator();
                     // Theme toggle inside View menu
                     let theme_name = match self.theme_mode {
@@ -409,29 +490,27 @@ impl App for AetherApp {
 
                 ui.menu_button("Tools", |ui| {
                     if ui.button("Validate Code").clicked() {
-                        let project_state_clone = self.project_state.clone();
-                        match CodeValidator::validate(&project_state_clone) {
-                            Ok(_) => {
-                                self.validation_status = ValidationStatus::Success;
-                            }
-                            Err(err) => {
-                                self.validation_status = ValidationStatus::Failed(err);
-                            }
-                        }
+                        self.start_validation(ctx);
+                        ui.close();
+                    }
+                    if ui.button("▶ Play").clicked() {
+                        self.start_play(ctx);
                         ui.close();
                     }
                 });
             });
         });
 
-        // Create the editor context AFTER top panel (avoids borrow conflict with push_undo)
+        // Create the editor context AFTER top panel (avoids borrow conflict with undo/redo)
         let mut editor_ctx = EditorContext {
             project_state: &mut self.project_state,
+            history: &mut self.history,
             validation_status: &mut self.validation_status,
             theme_mode: &mut self.theme_mode,
             canvas_zoom: &mut self.canvas_zoom,
             canvas_pan: &mut self.canvas_pan,
             clipboard: &mut self.clipboard,
+            preview: &mut self.preview,
         };
 
         // --- BOTTOM STATUS BAR ---
@@ -444,6 +523,11 @@ impl App for AetherApp {
                         let is_checking = editor_ctx.validation_status.is_checking();
                         if is_checking {
                             ui.add(egui::Spinner::new().size(12.0));
+                            if let Some(job) = &self.validation_job {
+                                if ui.small_button("Cancel").clicked() {
+                                    job.cancel();
+                                }
+                            }
                         }
                         
                         let status_text = editor_ctx.validation_status.display_text();
@@ -455,7 +539,35 @@ impl App for AetherApp {
                             theme::muted_color(ui.ctx())
                         };
                         
-                        ui.label(RichText::new(status_text).size(11.0).color(status_color));
+                        if let ValidationStatus::Failed(diagnostics) = &*editor_ctx.validation_status {
+                            // List the errors; clicking one selects the widget that produced it
+                            let mut reveal = None;
+                            ui.menu_button(RichText::new(status_text).size(11.0).color(status_color), |ui| {
+                                for diagnostic in diagnostics {
+                                    let text = match &diagnostic.location {
+                                        Some((file, line)) => format!("{}:{}  {}", file, line, diagnostic.message),
+                                        None => diagnostic.message.clone(),
+                                    };
+                                    let mut response = ui.button(RichText::new(text).size(11.0));
+                                    if let Some(rendered) = &diagnostic.rendered {
+                                        response = response.on_hover_text(RichText::new(rendered).monospace());
+                                    }
+                                    if response.clicked() && diagnostic.widget.is_some() {
+                                        reveal = diagnostic.widget;
+                                        ui.close();
+                                    }
+                                }
+                            });
+                            if let Some(id) = reveal {
+                                if editor_ctx.project_state.reveal_widget(id) {
+                                    self.ui_state.right_panel_expanded = true;
+                                    self.ui_state.right_bottom_tab = RightBottomTab::Inspector;
+                                }
+                            }
+                        } else {
+                            // Progress lines can be long; keep the status bar to one line
+                            ui.add(egui::Label::new(RichText::new(status_text).size(11.0).color(status_color)).truncate());
+                        }
                     });
                 });
         }
@@ -539,6 +651,33 @@ impl App for AetherApp {
                 });
         }
 
+        if self.ui_state.show_history {
+            egui::Window::new("History")
+                .open(&mut self.ui_state.show_history)
+                .default_size([280.0, 400.0])
+                .show(ctx, |ui| {
+                    history::render_history(ui, &mut editor_ctx);
+                });
+        }
+
+        let mut rebuild_play = false;
+        if self.ui_state.show_play_log {
+            if let Some(session) = &self.play_session {
+                let mut command = None;
+                egui::Window::new("Play")
+                    .open(&mut self.ui_state.show_play_log)
+                    .default_size([600.0, 300.0])
+                    .show(ctx, |ui| {
+                        command = play_log::render_play_log(ui, session, &mut self.play_auto_rebuild);
+                    });
+                match command {
+                    Some(play_log::PlayCommand::Stop) => session.stop(),
+                    Some(play_log::PlayCommand::Rebuild) => rebuild_play = true,
+                    None => {}
+                }
+            }
+        }
+
         if self.ui_state.show_project_settings {
             egui::Window::new("Project Settings")
                 .open(&mut self.ui_state.show_project_settings)
@@ -550,8 +689,47 @@ impl App for AetherApp {
                         ui.label("Project Name:");
                         ui.text_edit_singleline(&mut editor_ctx.project_state.project_name);
                     });
-                    // Future settings can go here
+
+                    ui.add_space(8.0);
+                    ui.label("Export");
+                    let export = &mut editor_ctx.project_state.export;
+                    ui.horizontal(|ui| {
+                        ui.label("Mode:");
+                        egui::ComboBox::from_id_salt("export_mode")
+                            .selected_text(export.mode.to_string())
+                            .show_ui(ui, |ui| {
+                                for mode in [ExportMode::App, ExportMode::ComponentUi, ExportMode::ComponentWidget] {
+                                    ui.selectable_value(&mut export.mode, mode, mode.to_string());
+                                }
+                            });
+                    });
+                    if export.is_component() {
+                        ui.horizontal(|ui| {
+                            ui.label("Struct Name:");
+                            ui.text_edit_singleline(&mut export.struct_name);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Module Path:");
+                            ui.text_edit_singleline(&mut export.module_path);
+                        });
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Exports src/{}.rs without main.rs or Cargo.toml",
+                                export.module_segments().join("/")
+                            ))
+                            .small()
+                            .weak(),
+                        );
+                    }
                 });
         }
+
+        // Whatever the panels changed this frame becomes an undo step, or extends the last one
+        let now = ctx.input(|i| i.time);
+        editor_ctx.history.record_if_changed(editor_ctx.project_state, now);
+
+        if rebuild_play {
+            self.start_play(ctx);
+        }
     }
 }
\ No newline at end of file
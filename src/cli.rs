@@ -0,0 +1,139 @@
+//! Headless command-line interface, so CI can regenerate and verify UI code
+//! from a committed project file without opening the editor.
+
+use crate::compiler::Compiler;
+use crate::model::ProjectState;
+use crate::validator::CodeValidator;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+pub const USAGE: &str = "\
+Usage:
+  aether_rad                                    Launch the editor
+  aether_rad export <project.json> --out <dir>  Write the generated crate and assets to <dir>
+  aether_rad check <project.json>               Validate the project and cargo check the generated code
+  aether_rad print <project.json>               Print the generated app code to stdout";
+
+/// Exit code for a command that ran but failed
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for malformed command lines
+pub const EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Export { project: PathBuf, out: PathBuf },
+    Check { project: PathBuf },
+    Print { project: PathBuf },
+    Help,
+}
+
+impl CliCommand {
+    /// Parse the arguments after the program name.
+    /// Returns `Ok(None)` when there are none, in which case the editor should start.
+    pub fn parse(args: &[String]) -> Result<Option<Self>, String> {
+        let Some(command) = args.first() else {
+            return Ok(None);
+        };
+        let rest = &args[1..];
+
+        match command.as_str() {
+            "export" => {
+                let mut project = None;
+                let mut out = None;
+                let mut iter = rest.iter();
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--out" | "-o" => {
+                            let dir = iter.next().ok_or("--out needs a directory")?;
+                            out = Some(PathBuf::from(dir));
+                        }
+                        _ if project.is_none() && !arg.starts_with('-') => {
+                            project = Some(PathBuf::from(arg));
+                        }
+                        _ => return Err(format!("Unexpected argument '{}'", arg)),
+                    }
+                }
+                Ok(Some(CliCommand::Export {
+                    project: project.ok_or("export needs a project file")?,
+                    out: out.ok_or("export needs --out <dir>")?,
+                }))
+            }
+            "check" => Ok(Some(CliCommand::Check {
+                project: single_project_arg("check", rest)?,
+            })),
+            "print" => Ok(Some(CliCommand::Print {
+                project: single_project_arg("print", rest)?,
+            })),
+            "help" | "--help" | "-h" => Ok(Some(CliCommand::Help)),
+            _ => Err(format!("Unknown command '{}'", command)),
+        }
+    }
+
+    /// Run the command, returning the process exit code.
+    pub fn execute(&self) -> i32 {
+        let result = match self {
+            CliCommand::Export { project, out } => load_project(project).and_then(|state| {
+                Compiler::export_project(&state, out)
+                    .and_then(|_| Compiler::copy_assets(&state, out))
+                    .map(|_| eprintln!("Exported to {}", out.display()))
+                    .map_err(|e| format!("Failed to export project: {}", e))
+            }),
+            CliCommand::Check { project } => load_project(project).and_then(|state| {
+                let mut progress = |line: &str| eprintln!("{}", line);
+                CodeValidator::validate(&state, &mut progress, &AtomicBool::new(false), None)
+                    .map(|message| eprintln!("{}", message))
+                    .map_err(|diagnostics| {
+                        let lines: Vec<String> = diagnostics
+                            .iter()
+                            .map(|d| match d.widget {
+                                Some(id) => format!("{}\n  = widget: {}", d, id),
+                                None => d.to_string(),
+                            })
+                            .collect();
+                        lines.join("\n")
+                    })
+            }),
+            CliCommand::Print { project } => load_project(project)
+                .map(|state| println!("{}", Compiler::generate_primary_rs(&state))),
+            CliCommand::Help => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+        };
+
+        match result {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                EXIT_FAILURE
+            }
+        }
+    }
+}
+
+/// Run the subcommand named by the arguments.
+/// Returns `None` when there is none and the editor should be launched instead.
+pub fn run(args: &[String]) -> Option<i32> {
+    match CliCommand::parse(args) {
+        Ok(command) => command.map(|command| command.execute()),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            Some(EXIT_USAGE)
+        }
+    }
+}
+
+/// Load a saved project file
+pub fn load_project(path: &Path) -> Result<ProjectState, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn single_project_arg(command: &str, rest: &[String]) -> Result<PathBuf, String> {
+    match rest {
+        [project] => Ok(PathBuf::from(project)),
+        [] => Err(format!("{} needs a project file", command)),
+        _ => Err(format!("{} takes exactly one project file", command)),
+    }
+}
@@ -1,11 +1,396 @@
-use crate::model::{ProjectState, VariableType};
+use crate::model::{
+    Action, CommonProperties, CrateDependency, CustomType, CustomTypeKind, ExportMode, ProjectState, Variable, VariableType, WidgetEvent,
+    WidgetNode,
+};
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Marker statements wrapped around each widget's code while generating, then
+/// stripped again to build the `SourceMap`. They never reach exported files.
+const WIDGET_BEGIN_MARKER: &str = "aether_widget_begin";
+const WIDGET_END_MARKER: &str = "aether_widget_end";
+
+/// Versions of optional crates emitted into generated manifests.
+/// These must stay compatible with the egui version in `generate_cargo_toml`.
+pub const EGUI_EXTRAS_VERSION: &str = "0.33.3";
+pub const EGUI_PLOT_VERSION: &str = "0.34.0";
+pub const IMAGE_VERSION: &str = "0.25";
+
+/// A private field on the generated `MyApp` struct that belongs to a widget
+/// (e.g. a window's open flag) rather than to a project variable.
+#[derive(Debug, Clone)]
+pub struct StateField {
+    pub name: Ident,
+    pub ty: TokenStream,
+    pub init: TokenStream,
+    /// Public fields can be used from hand-written handlers
+    pub public: bool,
+}
+
+/// A method on the generated `AppEvents` trait. It is called after the
+/// event's action runs and is meant to be overridden in `handlers.rs`.
+#[derive(Debug, Clone)]
+pub struct EventHook {
+    pub name: Ident,
+    pub doc: String,
+}
+
+/// A file written into an exported project.
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    /// Path relative to the project folder
+    pub path: String,
+    pub contents: String,
+    /// User-owned files are only created once and never overwritten on re-export
+    pub user_owned: bool,
+}
+
+/// Shared state threaded through `WidgetNode::codegen`.
+pub struct CodegenContext<'a> {
+    pub variables: &'a HashMap<String, Variable>,
+    fields: Vec<StateField>,
+    taken_names: HashSet<String>,
+    /// Requested field name -> identifier given out, so a field referenced before it is registered keeps its name
+    field_names: HashMap<String, Ident>,
+    hooks: Vec<EventHook>,
+    /// Field holding the current screen, when the project has more than one
+    screen_field: Option<Ident>,
+}
+
+impl<'a> CodegenContext<'a> {
+    pub fn new(variables: &'a HashMap<String, Variable>) -> Self {
+        Self {
+            variables,
+            fields: Vec::new(),
+            taken_names: variables.keys().cloned().collect(),
+            field_names: HashMap::new(),
+            hooks: Vec::new(),
+            screen_field: None,
+        }
+    }
+
+    /// Register a private state field and return the identifier to reference it by.
+    /// If `name` collides with a project variable or another registered field,
+    /// a numeric suffix is appended so the generated struct stays valid.
+    pub fn register_field(&mut self, name: &str, ty: TokenStream, init: TokenStream) -> Ident {
+        self.add_field(name, ty, init, false)
+    }
+
+    /// Like `register_field`, but the field is `pub` so handlers can use it too.
+    pub fn register_public_field(&mut self, name: &str, ty: TokenStream, init: TokenStream) -> Ident {
+        self.add_field(name, ty, init, true)
+    }
+
+    fn add_field(&mut self, name: &str, ty: TokenStream, init: TokenStream, public: bool) -> Ident {
+        let ident = match self.field_names.get(name) {
+            Some(ident) if !self.fields.iter().any(|field| field.name == *ident) => ident.clone(),
+            _ => self.unique_ident(name),
+        };
+        self.field_names.entry(name.to_string()).or_insert_with(|| ident.clone());
+        self.fields.push(StateField {
+            name: ident.clone(),
+            ty,
+            init,
+            public,
+        });
+        ident
+    }
+
+    /// Identifier of the state field a widget registers (or will register) under `name`,
+    /// for actions that change another widget's state, e.g. closing a window
+    pub fn state_field(&mut self, name: &str) -> Ident {
+        if let Some(ident) = self.field_names.get(name) {
+            return ident.clone();
+        }
+        let ident = self.unique_ident(name);
+        self.field_names.insert(name.to_string(), ident.clone());
+        ident
+    }
+
+    fn unique_ident(&mut self, name: &str) -> Ident {
+        let mut unique = name.to_string();
+        let mut suffix = 2;
+        while self.taken_names.contains(&unique) {
+            unique = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+        self.taken_names.insert(unique.clone());
+        quote::format_ident!("{}", unique)
+    }
+
+    /// Fields registered so far, in registration order
+    pub fn fields(&self) -> &[StateField] {
+        &self.fields
+    }
+
+    /// Code for a widget event: the configured actions in order, followed by a call to
+    /// the event's hook method on the `AppEvents` trait.
+    pub fn event_code(&mut self, widget: &str, id: Uuid, event: WidgetEvent, actions: &[Action]) -> TokenStream {
+        let hook = self.register_event_hook(widget, id, event);
+        let action_code: Vec<TokenStream> = actions.iter().map(|action| action.to_code(self)).collect();
+        quote! {
+            #(#action_code)*
+            self.#hook();
+        }
+    }
+
+    /// Register an `AppEvents` hook method and return its identifier.
+    /// Hook names are derived from the widget id so they survive re-export.
+    pub fn register_event_hook(&mut self, widget: &str, id: Uuid, event: WidgetEvent) -> Ident {
+        let name = format!(
+            "on_{}_{}_{}",
+            widget.to_lowercase().replace(' ', "_"),
+            id.simple(),
+            event.hook_suffix()
+        );
+        let ident = quote::format_ident!("{}", name);
+        if !self.hooks.iter().any(|hook| hook.name == ident) {
+            self.hooks.push(EventHook {
+                name: ident.clone(),
+                doc: format!("{} {} ({})", widget, event, id),
+            });
+        }
+        ident
+    }
+
+    /// Generate a child widget's code, tagged so the `SourceMap` can trace
+    /// compiler diagnostics back to it. Containers use this for their children.
+    pub fn widget_code(&mut self, node: &dyn WidgetNode) -> TokenStream {
+        let code = node.codegen(self);
+        let code = self.common_code(node.common(), code);
+        let id = node.id().to_string();
+        let begin = quote::format_ident!("{}", WIDGET_BEGIN_MARKER);
+        let end = quote::format_ident!("{}", WIDGET_END_MARKER);
+        quote! {
+            #begin!(#id);
+            #code
+            #end!(#id);
+        }
+    }
+
+    /// Wrap a widget's code to apply its tooltip, enabled state and visibility.
+    /// Widgets with the default settings are left as they are.
+    fn common_code(&self, common: &CommonProperties, code: TokenStream) -> TokenStream {
+        let mut code = code;
+        if !common.enabled.is(true) {
+            let enabled = common.enabled.to_code();
+            code = quote! {
+                ui.add_enabled_ui(#enabled, |ui| {
+                    #code
+                });
+            };
+        }
+        if !common.tooltip.is_empty() {
+            let tooltip = self.text_expr(&common.tooltip);
+            code = quote! {
+                ui.scope(|ui| {
+                    #code
+                })
+                .response
+                .on_hover_text(#tooltip);
+            };
+        }
+        if !common.visible.is(true) {
+            let visible = common.visible.to_code();
+            code = quote! {
+                if #visible {
+                    #code
+                }
+            };
+        }
+        code
+    }
+
+    /// Code that switches to the named screen. Single-screen projects have nowhere to go.
+    pub fn navigate_code(&self, screen: &str) -> TokenStream {
+        match &self.screen_field {
+            Some(field) => {
+                let variant = Compiler::screen_variant(screen);
+                quote! { self.#field = Screen::#variant; }
+            }
+            None => quote! {},
+        }
+    }
+
+    /// Event hooks registered so far, in registration order
+    pub fn hooks(&self) -> &[EventHook] {
+        &self.hooks
+    }
+
+    /// Expression for widget text with `{variable}` placeholders (see `interpolation`).
+    /// Plain text stays a string literal; otherwise the text becomes a `format!` of the fields.
+    /// Placeholders that don't check out are written literally, and `validate` reports them.
+    pub fn text_expr(&self, text: &str) -> TokenStream {
+        use crate::interpolation::{self, FormatKind, FormatSpec, Segment};
+
+        let mut format_string = String::new();
+        let mut args = Vec::new();
+        for segment in interpolation::parse(text) {
+            let Segment::Placeholder { var, spec } = &segment else {
+                format_string.push_str(&interpolation::escape(&segment.source()));
+                continue;
+            };
+            let v_type = self.variable_type(var);
+            if interpolation::check_placeholder(var, spec, v_type).is_err() {
+                format_string.push_str(&interpolation::escape(&segment.source()));
+                continue;
+            }
+            // Types without a `Display` impl are written with `Debug`, as in `display_expr`
+            let mut spec = spec.clone();
+            let displays = v_type.is_some_and(|t| t.is_displayable());
+            if !displays && FormatSpec::parse(&spec).is_ok_and(|s| s.kind == FormatKind::Display) {
+                spec.push('?');
+            }
+            if spec.is_empty() {
+                format_string.push_str("{}");
+            } else {
+                format_string.push_str(&format!("{{:{}}}", spec));
+            }
+            let ident = quote::format_ident!("{}", var);
+            args.push(quote! { self.#ident });
+        }
+
+        if args.is_empty() {
+            let text = interpolation::literal_text(text);
+            quote! { #text }
+        } else {
+            quote! { format!(#format_string, #(#args),*) }
+        }
+    }
+
+    /// Like `text_expr`, but always an owned `String`
+    pub fn owned_text_expr(&self, text: &str) -> TokenStream {
+        let expr = self.text_expr(text);
+        if syn::parse2::<syn::LitStr>(expr.clone()).is_ok() {
+            quote! { #expr.to_string() }
+        } else {
+            expr
+        }
+    }
+
+    /// Type of a project variable, if it exists
+    pub fn variable_type(&self, name: &str) -> Option<&VariableType> {
+        self.variables.get(name).map(|v| &v.v_type)
+    }
+
+    /// Expression that displays a bound variable as widget text.
+    /// Strings are borrowed; numbers, booleans and enums are converted with `to_string()`,
+    /// and lists, structs and colors are shown with their `Debug` representation.
+    pub fn display_expr(&self, var_name: &str) -> TokenStream {
+        let ident = quote::format_ident!("{}", var_name);
+        match self.variable_type(var_name) {
+            Some(VariableType::String) | None => quote! { &self.#ident },
+            Some(t) if t.is_displayable() => quote! { self.#ident.to_string() },
+            Some(_) => quote! { format!("{:?}", self.#ident) },
+        }
+    }
+}
+
+/// Lines of a generated file emitted by one widget (1-based, inclusive)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub widget: Uuid,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Links lines of a generated file back to the widgets that produced them
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Path of the mapped file, relative to the exported crate
+    pub file: String,
+    pub spans: Vec<SourceSpan>,
+}
+
+impl SourceMap {
+    /// Strip the widget markers from formatted code, recording where each widget's code ends up
+    fn extract(marked: &str, file: &str) -> (String, SourceMap) {
+        let mut out: Vec<String> = Vec::new();
+        let mut open: Vec<(Uuid, usize)> = Vec::new();
+        let mut spans = Vec::new();
+
+        let mut lines = marked.lines();
+        while let Some(line) = lines.next() {
+            let mut rest = line.to_string();
+            // Deeply nested markers may be wrapped over several lines
+            while find_marker(&rest).is_some_and(|(start, _)| !rest[start..].contains(';')) {
+                match lines.next() {
+                    Some(next) => rest.push_str(next.trim()),
+                    None => break,
+                }
+            }
+            let mut events = Vec::new();
+            // Formatted code has one marker per line, but unformatted fallback output may have many
+            while let Some((start, is_begin)) = find_marker(&rest) {
+                let end = rest[start..].find(';').map_or(rest.len(), |i| start + i + 1);
+                let marker = &rest[start..end];
+                if let Some(id) = marker.split('"').nth(1).and_then(|id| Uuid::parse_str(id).ok()) {
+                    events.push((is_begin, id));
+                }
+                rest.replace_range(start..end, "");
+            }
+
+            let keep = events.is_empty() || !rest.trim().is_empty();
+            let line_number = out.len() + 1;
+            for (is_begin, id) in events {
+                if is_begin {
+                    open.push((id, line_number));
+                } else if let Some(index) = open.iter().rposition(|(open_id, _)| *open_id == id) {
+                    let (widget, start_line) = open.remove(index);
+                    let end_line = if keep { line_number } else { out.len() };
+                    spans.push(SourceSpan {
+                        widget,
+                        start_line,
+                        end_line: end_line.max(start_line),
+                    });
+                }
+            }
+            if keep {
+                out.push(rest);
+            }
+        }
+
+        let mut code = out.join("\n");
+        if marked.ends_with('\n') {
+            code.push('\n');
+        }
+        (
+            code,
+            SourceMap {
+                file: file.to_string(),
+                spans,
+            },
+        )
+    }
+
+    /// The innermost widget whose code covers `line`
+    pub fn widget_at(&self, line: usize) -> Option<Uuid> {
+        self.spans
+            .iter()
+            .filter(|span| span.start_line <= line && line <= span.end_line)
+            .min_by_key(|span| span.end_line - span.start_line)
+            .map(|span| span.widget)
+    }
+}
+
+/// Position of the first widget marker in `line`, and whether it opens a widget
+fn find_marker(line: &str) -> Option<(usize, bool)> {
+    let begin = line.find(WIDGET_BEGIN_MARKER).map(|i| (i, true));
+    let end = line.find(WIDGET_END_MARKER).map(|i| (i, false));
+    match (begin, end) {
+        (Some(b), Some(e)) => Some(if b.0 < e.0 { b } else { e }),
+        (b, e) => b.or(e),
+    }
+}
 
 pub struct Compiler;
 
 impl Compiler {
-    pub fn generate_cargo_toml(name: &str) -> String {
-        format!(
+    pub fn generate_cargo_toml(name: &str, dependencies: &[CrateDependency]) -> String {
+        let mut toml = format!(
             r#"[package]
 name = "{}"
 version = "0.1.0"
@@ -16,14 +401,102 @@ egui = "0.33.3"
 eframe = "0.33.3"
 "#,
             name
-        )
+        );
+
+        for dep in dependencies {
+            if dep.features.is_empty() {
+                toml.push_str(&format!("{} = \"{}\"\n", dep.name, dep.version));
+            } else {
+                let features: Vec<String> = dep.features.iter().map(|f| format!("\"{}\"", f)).collect();
+                toml.push_str(&format!(
+                    "{} = {{ version = \"{}\", features = [{}] }}\n",
+                    dep.name,
+                    dep.version,
+                    features.join(", ")
+                ));
+            }
+        }
+
+        toml
+    }
+
+    /// Variant of the generated `Screen` enum for a screen name, e.g. "main menu" -> `MainMenu`
+    pub fn screen_variant(name: &str) -> Ident {
+        let mut variant: String = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                let first = chars.next().map(|c| c.to_ascii_uppercase()).unwrap_or_default();
+                std::iter::once(first).chain(chars).collect::<String>()
+            })
+            .collect();
+        if !variant.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            variant.insert_str(0, "Screen");
+        }
+        quote::format_ident!("{}", variant)
+    }
+
+    /// Unsuffixed literal of a numeric type, so it takes the type of the value it's used with.
+    /// Integers are rounded and clamped to the type's range.
+    pub fn numeric_literal(v_type: &VariableType, value: f64) -> TokenStream {
+        let literal = match v_type.integer_range() {
+            Some((min, max)) => proc_macro2::Literal::i128_unsuffixed((value.round() as i128).clamp(min, max)),
+            None => proc_macro2::Literal::f64_unsuffixed(if value.is_finite() { value } else { 0.0 }),
+        };
+        quote! { #literal }
+    }
+
+    /// Rust type of a variable in generated code
+    pub fn rust_type(v_type: &VariableType) -> TokenStream {
+        match v_type {
+            VariableType::String => quote! { String },
+            VariableType::Integer => quote! { i32 },
+            VariableType::U8 => quote! { u8 },
+            VariableType::U32 => quote! { u32 },
+            VariableType::I64 => quote! { i64 },
+            VariableType::Usize => quote! { usize },
+            VariableType::Float => quote! { f64 },
+            VariableType::F32 => quote! { f32 },
+            VariableType::Boolean => quote! { bool },
+            VariableType::Color => quote! { [f32; 4] },
+            VariableType::List(inner) => {
+                let inner = Self::rust_type(inner);
+                quote! { Vec<#inner> }
+            }
+            VariableType::Enum(name) | VariableType::Struct(name) => {
+                let ident = type_ident(name);
+                quote! { #ident }
+            }
+        }
+    }
+
+    /// Collect the crates required by every widget in the project.
+    /// Declarations of the same crate are merged: the first version wins and
+    /// features are unioned. Output is sorted by crate name for deterministic manifests.
+    pub fn collect_dependencies(state: &ProjectState) -> Vec<CrateDependency> {
+        let mut merged: BTreeMap<String, CrateDependency> = BTreeMap::new();
+        for screen in &state.screens {
+            collect_dependencies_recursive(screen.root.as_ref(), &mut merged);
+        }
+
+        merged
+            .into_values()
+            .filter(|dep| dep.name != "egui" && dep.name != "eframe")
+            .map(|mut dep| {
+                dep.features.sort();
+                dep.features.dedup();
+                dep
+            })
+            .collect()
     }
 
     pub fn generate_main_rs() -> String {
         let code = r#"#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod app;
-use app::MyApp;
+mod app_generated;
+mod handlers;
+use app_generated::MyApp;
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
@@ -44,55 +517,70 @@ fn main() -> eframe::Result {
         Self::format_rust_code(code)
     }
 
-    pub fn generate_app_rs(state: &ProjectState) -> String {
-        // 1. Generate Struct Fields
-        let mut fields = Vec::new();
-        let mut inits = Vec::new();
-
-        // Sort keys for deterministic output
-        let mut keys: Vec<String> = state.variables.keys().cloned().collect();
-        keys.sort();
-
-        for key in keys {
-            if let Some(var) = state.variables.get(&key) {
-                let name = quote::format_ident!("{}", var.name);
-                let val_str = &var.value;
-                let (ty, init_val) = match var.v_type {
-                    VariableType::String => (quote! { String }, quote! { String::from(#val_str) }),
-                    VariableType::Integer => (quote! { i32 }, {
-                        let val: i32 = val_str.parse().unwrap_or(0);
-                        quote! { #val }
-                    }),
-                    VariableType::Float => (quote! { f64 }, {
-                        let val: f64 = val_str.parse().unwrap_or(0.0);
-                        quote! { #val }
-                    }),
-                    VariableType::Boolean => (quote! { bool }, {
-                        let val: bool = val_str.parse().unwrap_or(false);
-                        quote! { #val }
-                    }),
-                };
+    /// `main.rs` that shows a component exported to the `component` module in a window,
+    /// so it can be checked and run without a host crate.
+    pub fn generate_component_host_main_rs(state: &ProjectState) -> String {
+        let name = state.export.struct_ident();
+        let draw = match state.export.mode {
+            ExportMode::ComponentWidget => quote! { ui.add(&mut self.0); },
+            _ => quote! { self.0.ui(ui); },
+        };
+        let code = quote! {
+            mod component;
 
-                // Correction for String initialization
-                let init_expr = if var.v_type == VariableType::String {
-                    let s = &var.value;
-                    quote! { #s.to_string() }
-                } else {
-                    init_val
-                };
+            struct Host(component::#name);
 
-                fields.push(quote! { pub #name: #ty });
-                inits.push(quote! { #name: #init_expr });
+            impl eframe::App for Host {
+                fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        #draw
+                    });
+                }
             }
-        }
 
-        // 2. Generate UI Code
-        let ui_body = state.root_node.codegen();
+            fn main() -> eframe::Result {
+                eframe::run_native(
+                    stringify!(#name),
+                    eframe::NativeOptions::default(),
+                    Box::new(|_cc| Ok(Box::new(Host(component::#name::default())))),
+                )
+            }
+        };
+        Self::format_rust_code(&code.to_string())
+    }
+
+    /// Generate `src/app_generated.rs`: the app state, layout and the `AppEvents` trait.
+    /// This file is overwritten on every export.
+    pub fn generate_app_generated_rs(state: &ProjectState) -> String {
+        Self::generate_app_generated_rs_mapped(state).0
+    }
+
+    /// `generate_app_generated_rs`, plus a map from its lines to widgets
+    pub fn generate_app_generated_rs_mapped(state: &ProjectState) -> (String, SourceMap) {
+        let GeneratedState {
+            fields,
+            inits,
+            ui_body,
+            hooks,
+            screen_enum,
+            type_defs,
+        } = generate_state(state);
 
         let app_code = quote! {
             use eframe::App;
             use egui::Context;
 
+            /// Event hooks called after each widget event's action runs.
+            /// Override them in `handlers.rs`; the defaults do nothing.
+            #[allow(dead_code)]
+            pub trait AppEvents {
+                #(#hooks)*
+            }
+
+            #type_defs
+
+            #screen_enum
+
             pub struct MyApp {
                 #(#fields),*
             }
@@ -115,7 +603,208 @@ fn main() -> eframe::Result {
         };
 
         // Format with prettyplease
-        Self::format_rust_code(&app_code.to_string())
+        let marked = Self::format_rust_code(&app_code.to_string());
+        SourceMap::extract(&marked, &Self::primary_rs_path(state))
+    }
+
+    /// Generate an embeddable component: a state struct drawn either through
+    /// `fn ui(&mut self, ui)` or an `egui::Widget` impl, depending on the export mode.
+    /// Hand-written hooks live in the `handlers` submodule.
+    pub fn generate_component_rs(state: &ProjectState) -> String {
+        Self::generate_component_rs_mapped(state).0
+    }
+
+    /// `generate_component_rs`, plus a map from its lines to widgets
+    pub fn generate_component_rs_mapped(state: &ProjectState) -> (String, SourceMap) {
+        let GeneratedState {
+            fields,
+            inits,
+            ui_body,
+            hooks,
+            screen_enum,
+            type_defs,
+        } = generate_state(state);
+
+        let name = state.export.struct_ident();
+        let events = quote::format_ident!("{}Events", name);
+
+        let draw_impl = if state.export.mode == ExportMode::ComponentWidget {
+            quote! {
+                impl egui::Widget for &mut #name {
+                    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+                        ui.scope(|ui| {
+                            #ui_body
+                        })
+                        .response
+                    }
+                }
+            }
+        } else {
+            quote! {
+                impl #name {
+                    pub fn ui(&mut self, ui: &mut egui::Ui) {
+                        #ui_body
+                    }
+                }
+            }
+        };
+
+        let component_code = quote! {
+            mod handlers;
+
+            /// Event hooks called after each widget event's action runs.
+            /// Override them in `handlers.rs`; the defaults do nothing.
+            #[allow(dead_code)]
+            pub trait #events {
+                #(#hooks)*
+            }
+
+            #type_defs
+
+            #screen_enum
+
+            pub struct #name {
+                #(#fields),*
+            }
+
+            impl Default for #name {
+                fn default() -> Self {
+                    Self {
+                        #(#inits),*
+                    }
+                }
+            }
+
+            #draw_impl
+        };
+
+        let marked = Self::format_rust_code(&component_code.to_string());
+        SourceMap::extract(&marked, &Self::primary_rs_path(state))
+    }
+
+    /// Generate the user-owned handlers file for hand-written logic.
+    /// It is only written when it does not exist yet, so edits survive re-export.
+    pub fn generate_handlers_rs(state: &ProjectState) -> String {
+        let (header, import) = if state.export.is_component() {
+            let name = state.export.struct_ident();
+            (
+                format!("//! Hand-written event handlers for `{}`.", name),
+                format!("use super::{{{}, {}Events}};\n\nimpl {}Events for {} {{}}", name, name, name, name),
+            )
+        } else {
+            (
+                "//! Hand-written event handlers.".to_string(),
+                "use crate::app_generated::{AppEvents, MyApp};\n\nimpl AppEvents for MyApp {}".to_string(),
+            )
+        };
+
+        format!(
+            r#"{}
+//!
+//! Aether creates this file once and never overwrites it. Override the hook
+//! methods declared on the events trait to react to widget events. Methods
+//! added to the struct here can be called from custom actions.
+
+{}
+"#,
+            header, import
+        )
+    }
+
+    /// All files that make up an exported project
+    pub fn generate_project_files(state: &ProjectState) -> Vec<GeneratedFile> {
+        if state.export.is_component() {
+            // Components are dropped into an existing crate: no manifest or main.rs
+            let module = state.export.module_segments().join("/");
+            return vec![
+                GeneratedFile {
+                    path: Self::primary_rs_path(state),
+                    contents: Self::generate_component_rs(state),
+                    user_owned: false,
+                },
+                GeneratedFile {
+                    path: format!("src/{}/handlers.rs", module),
+                    contents: Self::generate_handlers_rs(state),
+                    user_owned: true,
+                },
+            ];
+        }
+
+        vec![
+            GeneratedFile {
+                path: "Cargo.toml".to_string(),
+                contents: Self::generate_cargo_toml(&state.project_name, &Self::collect_dependencies(state)),
+                user_owned: false,
+            },
+            GeneratedFile {
+                path: "src/main.rs".to_string(),
+                contents: Self::generate_main_rs(),
+                user_owned: false,
+            },
+            GeneratedFile {
+                path: Self::primary_rs_path(state),
+                contents: Self::generate_app_generated_rs(state),
+                user_owned: false,
+            },
+            GeneratedFile {
+                path: "src/handlers.rs".to_string(),
+                contents: Self::generate_handlers_rs(state),
+                user_owned: true,
+            },
+        ]
+    }
+
+    /// Write the project into `folder`, keeping any existing user-owned files.
+    pub fn export_project(state: &ProjectState, folder: &Path) -> std::io::Result<()> {
+        for file in Self::generate_project_files(state) {
+            let path = folder.join(&file.path);
+            if file.user_owned && path.exists() {
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, file.contents)?;
+        }
+        Ok(())
+    }
+
+    /// Copy the project's image assets into `folder/assets`.
+    pub fn copy_assets(state: &ProjectState, folder: &Path) -> std::io::Result<()> {
+        if state.assets.assets.is_empty() {
+            return Ok(());
+        }
+        let assets_dir = folder.join("assets");
+        std::fs::create_dir_all(&assets_dir)?;
+        for asset in state.assets.assets.values() {
+            if let Some(name) = asset.path.file_name() {
+                std::fs::copy(&asset.path, assets_dir.join(name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The main generated source: `app_generated.rs` for apps, the component module otherwise.
+    pub fn generate_primary_rs(state: &ProjectState) -> String {
+        Self::generate_primary_rs_mapped(state).0
+    }
+
+    /// `generate_primary_rs`, plus a map from its lines to widgets
+    pub fn generate_primary_rs_mapped(state: &ProjectState) -> (String, SourceMap) {
+        if state.export.is_component() {
+            Self::generate_component_rs_mapped(state)
+        } else {
+            Self::generate_app_generated_rs_mapped(state)
+        }
+    }
+
+    /// Path of the main generated source within the exported crate
+    pub fn primary_rs_path(state: &ProjectState) -> String {
+        if state.export.is_component() {
+            format!("src/{}.rs", state.export.module_segments().join("/"))
+        } else {
+            "src/app_generated.rs".to_string()
+        }
     }
 
     /// Format Rust code using prettyplease for readable output
@@ -131,3 +820,231 @@ fn main() -> eframe::Result {
         }
     }
 }
+
+/// Struct fields, initializers, UI body and event hooks shared by every export mode
+struct GeneratedState {
+    fields: Vec<TokenStream>,
+    inits: Vec<TokenStream>,
+    ui_body: TokenStream,
+    hooks: Vec<TokenStream>,
+    /// `Screen` enum definition, empty for single-screen projects
+    screen_enum: TokenStream,
+    /// Project-defined enums and structs
+    type_defs: TokenStream,
+}
+
+fn generate_state(state: &ProjectState) -> GeneratedState {
+    // 1. Generate Struct Fields
+    let mut fields = Vec::new();
+    let mut inits = Vec::new();
+
+    // Sort keys for deterministic output
+    let mut keys: Vec<String> = state.variables.keys().cloned().collect();
+    keys.sort();
+
+    for key in keys {
+        if let Some(var) = state.variables.get(&key) {
+            let name = quote::format_ident!("{}", var.name);
+            let ty = Compiler::rust_type(&var.v_type);
+            // Values that don't parse fall back to the type's default
+            let value = var
+                .v_type
+                .parse_value(&var.value, &state.types)
+                .unwrap_or_else(|_| var.v_type.default_json(&state.types));
+            let init_expr = value_expr(&var.v_type, &value, &state.types);
+
+            fields.push(quote! { pub #name: #ty });
+            inits.push(quote! { #name: #init_expr });
+        }
+    }
+
+    // 2. Generate UI Code (widgets may register extra state fields here)
+    let mut ctx = CodegenContext::new(&state.variables);
+    let mut screen_enum = quote! {};
+    let ui_body = if let [screen] = state.screens.as_slice() {
+        ctx.widget_code(screen.root.as_ref())
+    } else {
+        // Multiple screens: a `Screen` enum field picks which tree to draw
+        let variants: Vec<Ident> = state.screens.iter().map(|s| Compiler::screen_variant(&s.name)).collect();
+        let first = &variants[0];
+        let field = ctx.register_public_field("current_screen", quote! { Screen }, quote! { Screen::#first });
+        ctx.screen_field = Some(field.clone());
+
+        let arms: Vec<TokenStream> = state
+            .screens
+            .iter()
+            .zip(&variants)
+            .map(|(screen, variant)| {
+                let body = ctx.widget_code(screen.root.as_ref());
+                quote! { Screen::#variant => { #body } }
+            })
+            .collect();
+
+        screen_enum = quote! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub enum Screen {
+                #(#variants),*
+            }
+        };
+        quote! {
+            match self.#field {
+                #(#arms)*
+            }
+        }
+    };
+
+    for field in ctx.fields() {
+        let name = &field.name;
+        let ty = &field.ty;
+        let init = &field.init;
+        if field.public {
+            fields.push(quote! { pub #name: #ty });
+        } else {
+            fields.push(quote! { #name: #ty });
+        }
+        inits.push(quote! { #name: #init });
+    }
+
+    let hooks = ctx
+        .hooks()
+        .iter()
+        .map(|hook| {
+            let name = &hook.name;
+            let doc = &hook.doc;
+            quote! {
+                #[doc = #doc]
+                fn #name(&mut self) {}
+            }
+        })
+        .collect();
+
+    GeneratedState {
+        fields,
+        inits,
+        ui_body,
+        hooks,
+        screen_enum,
+        type_defs: type_definitions(&state.types),
+    }
+}
+
+/// Identifier for a project-defined type, variant or field name.
+/// Invalid characters become `_` so a half-typed name can't break generation;
+/// the validator reports such names.
+fn type_ident(name: &str) -> Ident {
+    let mut cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if !cleaned.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        cleaned.insert(0, '_');
+    }
+    quote::format_ident!("{}", cleaned)
+}
+
+/// `#[derive]`d definitions of the project's enums and structs.
+/// Enums default to their first variant and display as the variant name.
+fn type_definitions(types: &[CustomType]) -> TokenStream {
+    let defs = types.iter().map(|t| {
+        let name = type_ident(&t.name);
+        match &t.kind {
+            CustomTypeKind::Enum { variants } => {
+                let idents: Vec<Ident> = variants.iter().map(|v| type_ident(v)).collect();
+                let defaults = (0..idents.len()).map(|i| if i == 0 { quote! { #[default] } } else { quote! {} });
+                quote! {
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+                    pub enum #name {
+                        #(#defaults #idents),*
+                    }
+
+                    impl std::fmt::Display for #name {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.write_str(match self {
+                                #(Self::#idents => #variants),*
+                            })
+                        }
+                    }
+                }
+            }
+            CustomTypeKind::Struct { fields } => {
+                let names = fields.iter().map(|f| type_ident(&f.name));
+                let tys = fields.iter().map(|f| Compiler::rust_type(&f.v_type));
+                quote! {
+                    #[derive(Debug, Clone, PartialEq, Default)]
+                    pub struct #name {
+                        #(pub #names: #tys),*
+                    }
+                }
+            }
+        }
+    });
+    quote! { #(#defs)* }
+}
+
+/// Expression constructing a structured value (see `VariableType::default_json`).
+/// Anything that doesn't match the type becomes `Default::default()`.
+fn value_expr(v_type: &VariableType, value: &serde_json::Value, types: &[CustomType]) -> TokenStream {
+    use serde_json::Value;
+    match (v_type, value) {
+        (VariableType::String, Value::String(s)) => quote! { #s.to_string() },
+        // Unsuffixed, so the literal takes the field's numeric type
+        (integer, Value::Number(n)) if integer.is_integer() => {
+            let val = n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from)).unwrap_or_default();
+            let literal = proc_macro2::Literal::i128_unsuffixed(val);
+            quote! { #literal }
+        }
+        (float, Value::Number(n)) if float.is_float() => {
+            let literal = proc_macro2::Literal::f64_unsuffixed(n.as_f64().unwrap_or_default());
+            quote! { #literal }
+        }
+        (VariableType::Boolean, Value::Bool(b)) => quote! { #b },
+        (VariableType::Color, Value::String(s)) => match crate::model::parse_color(s) {
+            Some(rgba) => {
+                let channels = rgba.iter().map(|c| *c as f32 / 255.0);
+                quote! { [#(#channels),*] }
+            }
+            None => quote! { Default::default() },
+        },
+        (VariableType::List(inner), Value::Array(items)) => {
+            let items = items.iter().map(|item| value_expr(inner, item, types));
+            quote! { vec![#(#items),*] }
+        }
+        (VariableType::Enum(name), Value::String(variant)) => {
+            let name = type_ident(name);
+            let variant = type_ident(variant);
+            quote! { #name::#variant }
+        }
+        (VariableType::Struct(name), Value::Object(map)) => {
+            let Some(CustomTypeKind::Struct { fields }) = crate::model::find_type(types, name).map(|t| &t.kind) else {
+                return quote! { Default::default() };
+            };
+            let name = type_ident(name);
+            let inits = fields.iter().map(|field| {
+                let ident = type_ident(&field.name);
+                let value = match map.get(&field.name) {
+                    Some(value) => value_expr(&field.v_type, value, types),
+                    None => quote! { Default::default() },
+                };
+                quote! { #ident: #value }
+            });
+            quote! { #name { #(#inits),* } }
+        }
+        _ => quote! { Default::default() },
+    }
+}
+
+fn collect_dependencies_recursive(node: &dyn WidgetNode, merged: &mut BTreeMap<String, CrateDependency>) {
+    for dep in node.dependencies() {
+        match merged.get_mut(&dep.name) {
+            Some(existing) => existing.features.extend(dep.features),
+            None => {
+                merged.insert(dep.name.clone(), dep);
+            }
+        }
+    }
+
+    for child in node.child_widgets() {
+        collect_dependencies_recursive(child, merged);
+    }
+}
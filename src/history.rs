@@ -0,0 +1,531 @@
+//! Undo history. Rather than a snapshot of the whole project, each step keeps a
+//! structural diff of the project's serialized form: the values that changed, and
+//! array splices for inserted, removed and moved widgets. Any edit made through the
+//! editor panels is picked up by comparing the project against the last recorded state.
+
+use crate::model::ProjectState;
+use serde_json::Value;
+
+/// A step in the path to a value in the serialized project
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// One change to the serialized project
+#[derive(Debug, Clone, PartialEq)]
+pub enum Patch {
+    /// The value at `path` changed. `None` means the key is absent.
+    Set {
+        path: Vec<PathSegment>,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
+    /// In the array at `path`, `removed` elements starting at `index` were replaced by `inserted`
+    Splice {
+        path: Vec<PathSegment>,
+        index: usize,
+        removed: Vec<Value>,
+        inserted: Vec<Value>,
+    },
+}
+
+impl Patch {
+    fn apply(&self, root: &mut Value) {
+        match self {
+            Patch::Set { path, new, .. } => set_value(root, path, new),
+            Patch::Splice { path, index, removed, inserted } => splice(root, path, *index, removed.len(), inserted),
+        }
+    }
+
+    fn revert(&self, root: &mut Value) {
+        match self {
+            Patch::Set { path, old, .. } => set_value(root, path, old),
+            Patch::Splice { path, index, removed, inserted } => splice(root, path, *index, inserted.len(), removed),
+        }
+    }
+}
+
+/// One undo step
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// What changed, e.g. "Set Button.text" or "Delete Label"
+    pub label: String,
+    pub patches: Vec<Patch>,
+    /// Input time of the last change in the step, in seconds
+    time: f64,
+}
+
+impl HistoryEntry {
+    /// Fold a follow-up edit of the same values into this step
+    fn merge(&mut self, patches: &[Patch], time: f64) -> bool {
+        let same_values = self.patches.len() == patches.len()
+            && self.patches.iter().zip(patches).all(|pair| match pair {
+                (Patch::Set { path: a, .. }, Patch::Set { path: b, .. }) => a == b,
+                _ => false,
+            });
+        if !same_values || time - self.time > History::MERGE_WINDOW {
+            return false;
+        }
+        for (mine, theirs) in self.patches.iter_mut().zip(patches) {
+            if let (Patch::Set { new, .. }, Patch::Set { new: latest, .. }) = (mine, theirs) {
+                new.clone_from(latest);
+            }
+        }
+        // Dragging a value away and back again leaves nothing to undo
+        self.patches.retain(|patch| !matches!(patch, Patch::Set { old, new, .. } if old == new));
+        self.time = time;
+        true
+    }
+}
+
+pub struct History {
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+    /// The project as of the last recorded change
+    baseline: Value,
+    /// Whether the next change may be merged into the last step
+    can_merge: bool,
+    /// Whether the project may have changed since the last `record`
+    dirty: bool,
+}
+
+impl History {
+    /// Older steps are dropped past this point
+    pub const MAX_STEPS: usize = 200;
+    /// Edits of the same values less than this many seconds apart are one step
+    pub const MERGE_WINDOW: f64 = 1.0;
+
+    pub fn new(project: &ProjectState) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            baseline: snapshot(project),
+            can_merge: false,
+            dirty: false,
+        }
+    }
+
+    /// Note that the project may have changed, for the next `record_if_changed`
+    pub fn mark_changed(&mut self) {
+        self.dirty = true;
+    }
+
+    /// `record`, if `mark_changed` was called since the last recording.
+    /// Frames in which nothing can have been edited skip serializing the project.
+    pub fn record_if_changed(&mut self, project: &ProjectState, time: f64) -> bool {
+        self.dirty && self.record(project, time)
+    }
+
+    /// Record everything that changed since the last call as an undo step, or fold it
+    /// into the last step if it edits the same values shortly after. Returns whether anything changed.
+    pub fn record(&mut self, project: &ProjectState, time: f64) -> bool {
+        self.dirty = false;
+        let current = snapshot(project);
+        if current == self.baseline {
+            return false;
+        }
+        let mut patches = Vec::new();
+        diff(&mut Vec::new(), &self.baseline, &current, &mut patches);
+        let previous = std::mem::replace(&mut self.baseline, current);
+        self.redo_stack.clear();
+
+        let merged = self.can_merge && self.undo_stack.last_mut().is_some_and(|last| last.merge(&patches, time));
+        if merged {
+            if self.undo_stack.last().is_some_and(|last| last.patches.is_empty()) {
+                self.undo_stack.pop();
+            }
+        } else {
+            let label = describe(&patches, &previous, &self.baseline);
+            self.undo_stack.push(HistoryEntry { label, patches, time });
+            if self.undo_stack.len() > Self::MAX_STEPS {
+                let excess = self.undo_stack.len() - Self::MAX_STEPS;
+                self.undo_stack.drain(..excess);
+            }
+        }
+        self.can_merge = true;
+        true
+    }
+
+    /// Make the next change a step of its own, even if it would otherwise be merged
+    pub fn checkpoint(&mut self) {
+        self.can_merge = false;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Steps that can be undone, oldest first
+    pub fn undo_entries(&self) -> &[HistoryEntry] {
+        &self.undo_stack
+    }
+
+    /// Steps that can be redone, the next one last
+    pub fn redo_entries(&self) -> &[HistoryEntry] {
+        &self.redo_stack
+    }
+
+    /// Every step, oldest first. The first `position` of them are applied.
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.undo_stack.iter().chain(self.redo_stack.iter().rev())
+    }
+
+    /// Number of steps applied to the project
+    pub fn position(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Undo the last step. Returns false if there is nothing to undo.
+    pub fn undo(&mut self, project: &mut ProjectState) -> bool {
+        self.record_pending(project);
+        let position = self.position();
+        position > 0 && self.go_to(project, position - 1)
+    }
+
+    /// Redo the last undone step. Returns false if there is nothing to redo.
+    pub fn redo(&mut self, project: &mut ProjectState) -> bool {
+        self.record_pending(project);
+        self.go_to(project, self.position() + 1)
+    }
+
+    /// Undo or redo until `position` steps are applied (0 is the oldest state kept).
+    /// Returns false if there is no such point.
+    pub fn go_to(&mut self, project: &mut ProjectState, position: usize) -> bool {
+        self.record_pending(project);
+        if position > self.undo_stack.len() + self.redo_stack.len() {
+            return false;
+        }
+        let applied = self.position();
+        if position == applied {
+            return true;
+        }
+
+        // Patch a copy, so a state that fails to load leaves the history and the project as they were
+        let mut target = self.baseline.clone();
+        if position < applied {
+            for entry in self.undo_stack[position..].iter().rev() {
+                entry.patches.iter().rev().for_each(|patch| patch.revert(&mut target));
+            }
+        } else {
+            for entry in self.redo_stack.iter().rev().take(position - applied) {
+                entry.patches.iter().for_each(|patch| patch.apply(&mut target));
+            }
+        }
+        let Ok(restored) = serde_json::from_value::<ProjectState>(target.clone()) else {
+            eprintln!("Failed to restore project from undo history");
+            return false;
+        };
+
+        if position < applied {
+            let undone = self.undo_stack.split_off(position);
+            self.redo_stack.extend(undone.into_iter().rev());
+        } else {
+            let redone = self.redo_stack.split_off(self.redo_stack.len() - (position - applied));
+            self.undo_stack.extend(redone.into_iter().rev());
+        }
+        self.baseline = target;
+        self.restore(project, restored);
+        true
+    }
+
+    /// Changes made since the last `record` become a step of their own
+    fn record_pending(&mut self, project: &ProjectState) {
+        self.checkpoint();
+        let time = self.undo_stack.last().map_or(0.0, |last| last.time);
+        self.record(project, time);
+        self.checkpoint();
+    }
+
+    /// Replace the project with a restored state, keeping the selection and the screen being edited
+    fn restore(&mut self, project: &mut ProjectState, mut restored: ProjectState) {
+        restored.active_screen = project.active_screen.min(restored.screens.len().saturating_sub(1));
+        restored.selection = std::mem::take(&mut project.selection);
+        *project = restored;
+        self.can_merge = false;
+    }
+}
+
+/// The project as compared between steps. The selection and the screen being
+/// edited are where the user is looking, not part of the design.
+fn snapshot(project: &ProjectState) -> Value {
+    let mut value = serde_json::to_value(project).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        map.remove("selection");
+        map.remove("active_screen");
+    }
+    value
+}
+
+/// A readable summary of a step, like "Set Button.text", "Delete Label" or
+/// "Reparent Slider into Horizontal Layout". `before` and `after` are the project around it.
+fn describe(patches: &[Patch], before: &Value, after: &Value) -> String {
+    // Widgets taken out of and put into children lists; a widget in both was moved
+    let mut removed = Vec::new();
+    let mut inserted = Vec::new();
+    for patch in patches {
+        if let Patch::Splice { path, removed: old, inserted: new, .. } = patch {
+            if !matches!(path.last(), Some(PathSegment::Key(key)) if key == "children") {
+                continue;
+            }
+            let parent = enclosing_widget(after, path).map(|(widget, _)| widget);
+            removed.extend(old.iter().filter_map(as_widget));
+            inserted.extend(new.iter().filter_map(as_widget).map(|widget| (widget, parent)));
+        }
+    }
+    let moved: Vec<_> = inserted
+        .iter()
+        .filter(|(widget, _)| removed.iter().any(|other| other.get("id") == widget.get("id")))
+        .collect();
+    if let [(widget, parent)] = moved.as_slice() {
+        if removed.len() == 1 && inserted.len() == 1 {
+            let old_parent = patches.iter().find_map(|patch| match patch {
+                Patch::Splice { path, removed, .. } if removed.iter().filter_map(as_widget).any(|w| w.get("id") == widget.get("id")) => {
+                    enclosing_widget(before, path).map(|(parent, _)| parent.get("id"))
+                }
+                _ => None,
+            });
+            return match parent {
+                Some(parent) if old_parent != Some(parent.get("id")) => {
+                    format!("Reparent {} into {}", widget_name(widget), widget_name(parent))
+                }
+                _ => format!("Move {}", widget_name(widget)),
+            };
+        }
+    }
+    match (removed.as_slice(), inserted.as_slice()) {
+        ([], []) => {}
+        ([widget], []) => return format!("Delete {}", widget_name(widget)),
+        ([], [(widget, _)]) => return format!("Add {}", widget_name(widget)),
+        (old, []) => return format!("Delete {} widgets", old.len()),
+        ([], new) => return format!("Add {} widgets", new.len()),
+        _ => return "Rearrange widgets".to_string(),
+    }
+
+    // Property changes, named by widget and property
+    let mut changes: Vec<(String, String)> = Vec::new();
+    for patch in patches {
+        let (path, old, new) = match patch {
+            Patch::Set { path, old, new } => (path, old.as_ref(), new.as_ref()),
+            Patch::Splice { path, .. } => (path, None, None),
+        };
+        let change = match path.first() {
+            Some(PathSegment::Key(key)) if key == "screens" => screen_change(path, old, new, before, after),
+            Some(PathSegment::Key(key)) if key == "variables" => {
+                let name = match path.get(1) {
+                    Some(PathSegment::Key(name)) => name.clone(),
+                    _ => String::new(),
+                };
+                match (path.len(), old, new) {
+                    (2, None, Some(_)) => ("Add variable".to_string(), name),
+                    (2, Some(_), None) => ("Delete variable".to_string(), name),
+                    _ => ("Edit variable".to_string(), name),
+                }
+            }
+            Some(PathSegment::Key(key)) if key == "types" => ("Edit types".to_string(), String::new()),
+            Some(PathSegment::Key(key)) if key == "assets" => ("Edit assets".to_string(), String::new()),
+            Some(PathSegment::Key(key)) if key == "export" => ("Edit export settings".to_string(), String::new()),
+            Some(PathSegment::Key(key)) if key == "project_name" => ("Rename project".to_string(), String::new()),
+            _ => ("Edit project".to_string(), String::new()),
+        };
+        if !changes.contains(&change) {
+            changes.push(change);
+        }
+    }
+    match changes.as_slice() {
+        [] => "Edit project".to_string(),
+        [(action, subject)] if subject.is_empty() => action.clone(),
+        [(action, subject)] => format!("{} {}", action, subject),
+        _ => {
+            // The same property on several widgets, e.g. a bulk edit
+            let property = |action: &str| action.strip_prefix("Set ")?.split_once('.').map(|(_, key)| key.to_string());
+            let first = property(&changes[0].0);
+            if first.is_some() && changes.iter().all(|(action, _)| property(action) == first) {
+                format!("Set {} on {} widgets", first.unwrap_or_default(), changes.len())
+            } else {
+                format!("{} changes", changes.len())
+            }
+        }
+    }
+}
+
+/// What a change inside `screens` does, as an (action, subject) pair
+fn screen_change(path: &[PathSegment], old: Option<&Value>, new: Option<&Value>, before: &Value, after: &Value) -> (String, String) {
+    if path.len() == 1 {
+        return ("Edit screens".to_string(), String::new());
+    }
+    let Some((widget, rest)) = enclosing_widget(if new.is_some() { after } else { before }, path) else {
+        return match path.get(2) {
+            Some(PathSegment::Key(key)) if key == "name" => ("Rename screen".to_string(), String::new()),
+            _ => ("Edit screens".to_string(), String::new()),
+        };
+    };
+    let name = widget_name(widget);
+    let key = |index: usize| match rest.get(index) {
+        Some(PathSegment::Key(key)) => key.clone(),
+        _ => String::new(),
+    };
+    match key(0).as_str() {
+        // The widget itself was swapped for another
+        "" => match (old.and_then(as_widget), new.and_then(as_widget)) {
+            (Some(old), Some(new)) if old.get("id") == new.get("id") => {
+                (format!("Convert {} to {}", widget_name(old), widget_name(new)), String::new())
+            }
+            (Some(old), Some(new)) => (format!("Replace {} with {}", widget_name(old), widget_name(new)), String::new()),
+            _ => (format!("Edit {}", name), String::new()),
+        },
+        "common" | "bindings" if rest.len() > 1 => (format!("Set {}.{}", name, key(1)), String::new()),
+        "events" => (format!("Edit {} events", name), String::new()),
+        "children" | "tabs" | "series" | "columns" | "options" => (format!("Edit {} {}", name, key(0)), String::new()),
+        property => (format!("Set {}.{}", name, property), String::new()),
+    }
+}
+
+/// The innermost widget on `path`, with the rest of the path inside it
+fn enclosing_widget<'a, 'p>(root: &'a Value, path: &'p [PathSegment]) -> Option<(&'a Value, &'p [PathSegment])> {
+    let mut value = root;
+    let mut found = None;
+    for (i, segment) in path.iter().enumerate() {
+        if as_widget(value).is_some() {
+            found = Some((value, &path[i..]));
+        }
+        value = match segment {
+            PathSegment::Key(key) => value.get(key.as_str()),
+            PathSegment::Index(index) => value.get(*index),
+        }?;
+    }
+    if as_widget(value).is_some() {
+        found = Some((value, &path[path.len()..]));
+    }
+    found
+}
+
+/// The value if it is a serialized widget, or the widget of a freeform child
+fn as_widget(value: &Value) -> Option<&Value> {
+    let widget = value.get("widget").unwrap_or(value);
+    (widget.get("type").is_some() && widget.get("id").is_some()).then_some(widget)
+}
+
+/// Display name of a serialized widget, e.g. "Text Edit"
+fn widget_name(widget: &Value) -> String {
+    serde_json::from_value::<Box<dyn crate::model::WidgetNode>>(widget.clone())
+        .map(|node| node.name().to_string())
+        .unwrap_or_else(|_| "widget".to_string())
+}
+
+/// Patches that turn `old` into `new`, which is at `path`
+fn diff(path: &mut Vec<PathSegment>, old: &Value, new: &Value, patches: &mut Vec<Patch>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        // A widget converted to another type keeps its id but is replaced as a whole
+        (Value::Object(old_map), Value::Object(new_map))
+            if identity(old) == identity(new) && old.get("type") == new.get("type") =>
+        {
+            let keys: std::collections::BTreeSet<&String> = old_map.keys().chain(new_map.keys()).collect();
+            for key in keys {
+                path.push(PathSegment::Key(key.clone()));
+                match (old_map.get(key), new_map.get(key)) {
+                    (Some(a), Some(b)) => diff(path, a, b, patches),
+                    (a, b) => patches.push(Patch::Set {
+                        path: path.clone(),
+                        old: a.cloned(),
+                        new: b.cloned(),
+                    }),
+                }
+                path.pop();
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => diff_arrays(path, old_items, new_items, patches),
+        _ => patches.push(Patch::Set {
+            path: path.clone(),
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+    }
+}
+
+/// Elements that keep their identity are diffed in place; the run between the
+/// unchanged start and end of the array becomes one splice
+fn diff_arrays(path: &mut Vec<PathSegment>, old: &[Value], new: &[Value], patches: &mut Vec<Patch>) {
+    if old.len() == new.len() && old.iter().zip(new).all(|(a, b)| identity(a) == identity(b)) {
+        for (index, (a, b)) in old.iter().zip(new).enumerate() {
+            path.push(PathSegment::Index(index));
+            diff(path, a, b, patches);
+            path.pop();
+        }
+        return;
+    }
+
+    let same = |a: &Value, b: &Value| match (identity(a), identity(b)) {
+        (Some(x), Some(y)) => x == y,
+        (None, None) => a == b,
+        _ => false,
+    };
+    let prefix = old.iter().zip(new).take_while(|(a, b)| same(a, b)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| same(a, b))
+        .count();
+
+    // Patches inside kept elements use their index before the splice
+    for index in (0..prefix).chain(old.len() - suffix..old.len()) {
+        let new_index = if index < prefix { index } else { index + new.len() - old.len() };
+        path.push(PathSegment::Index(index));
+        diff(path, &old[index], &new[new_index], patches);
+        path.pop();
+    }
+    patches.push(Patch::Splice {
+        path: path.clone(),
+        index: prefix,
+        removed: old[prefix..old.len() - suffix].to_vec(),
+        inserted: new[prefix..new.len() - suffix].to_vec(),
+    });
+}
+
+/// The ID of a widget, or of the widget a freeform child holds
+fn identity(value: &Value) -> Option<&Value> {
+    value.get("id").or_else(|| value.get("widget")?.get("id"))
+}
+
+fn lookup<'a>(root: &'a mut Value, path: &[PathSegment]) -> Option<&'a mut Value> {
+    path.iter().try_fold(root, |value, segment| match segment {
+        PathSegment::Key(key) => value.get_mut(key.as_str()),
+        PathSegment::Index(index) => value.get_mut(*index),
+    })
+}
+
+fn set_value(root: &mut Value, path: &[PathSegment], value: &Option<Value>) {
+    let Some((last, parent_path)) = path.split_last() else {
+        *root = value.clone().unwrap_or(Value::Null);
+        return;
+    };
+    match (lookup(root, parent_path), last, value) {
+        (Some(Value::Object(map)), PathSegment::Key(key), Some(value)) => {
+            map.insert(key.clone(), value.clone());
+        }
+        (Some(Value::Object(map)), PathSegment::Key(key), None) => {
+            map.remove(key);
+        }
+        (Some(Value::Array(items)), PathSegment::Index(index), Some(value)) if *index < items.len() => {
+            items[*index] = value.clone();
+        }
+        _ => {}
+    }
+}
+
+fn splice(root: &mut Value, path: &[PathSegment], index: usize, remove: usize, insert: &[Value]) {
+    if let Some(Value::Array(items)) = lookup(root, path) {
+        if index + remove <= items.len() {
+            items.splice(index..index + remove, insert.iter().cloned());
+        }
+    }
+}
@@ -0,0 +1,1098 @@
+//! Import hand-written egui code into the Shadow Object Model.
+//!
+//! The importer recognizes the patterns `Compiler` emits (and the common
+//! hand-written equivalents). Statements it does not understand are kept
+//! as `CustomCodeWidget`s so nothing is lost.
+
+use crate::model::{Action, CompareOp, Condition, Flag, ProjectState, Variable, VariableType, WidgetEvent, WidgetNode};
+use crate::widgets::{
+    ButtonWidget, CheckboxWidget, CustomCodeWidget, HorizontalLayout, HyperlinkWidget, LabelWidget,
+    ScrollAreaWidget, SeparatorWidget, SliderWidget, TableColumn, TableWidget, TextEditWidget,
+    VerticalLayout, WindowWidget,
+};
+use std::collections::{HashMap, HashSet};
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprMethodCall, Fields, ImplItem, Item, Lit, Member, Stmt, Token, Type};
+use uuid::Uuid;
+
+/// An event handler found after a widget, e.g. `if response.clicked() { .. }`
+struct EventBlock {
+    event: WidgetEvent,
+    actions: Vec<Action>,
+    /// Widget id recovered from a generated `AppEvents` hook call
+    hook_id: Option<Uuid>,
+}
+
+pub struct Importer {
+    /// App struct fields that turned out to be widget state (e.g. a window's open flag)
+    consumed_fields: HashSet<String>,
+    /// Local copies of window open flags -> the app struct field they copy
+    open_flags: HashMap<String, String>,
+}
+
+impl Importer {
+    /// Parse Rust source and rebuild a project from the app struct and its `update` function.
+    pub fn import_source(source: &str) -> Result<ProjectState, String> {
+        let file = syn::parse_file(source).map_err(|e| format!("Failed to parse Rust code: {}", e))?;
+
+        let app_name = find_app_struct_name(&file).ok_or("No app struct found")?;
+        let ui_stmts = find_ui_body(&file, &app_name).ok_or("No `update` function found for the app struct")?;
+
+        let mut importer = Importer {
+            consumed_fields: HashSet::new(),
+            open_flags: HashMap::new(),
+        };
+        let root = importer.import_root(&ui_stmts);
+
+        let mut variables = infer_variables(&file, &app_name);
+        for field in &importer.consumed_fields {
+            variables.remove(field);
+        }
+
+        let mut project = ProjectState::new(root);
+        project.variables = variables;
+        Ok(project)
+    }
+
+    /// A single `ui.vertical(..)` becomes the root; anything else is wrapped in one
+    fn import_root(&mut self, stmts: &[Stmt]) -> Box<dyn WidgetNode> {
+        if let [Stmt::Expr(expr, _)] = stmts {
+            if let Some(children) = ui_closure_call(expr, "vertical") {
+                return Box::new(VerticalLayout {
+                    children: self.import_block(children),
+                    ..Default::default()
+                });
+            }
+        }
+
+        Box::new(VerticalLayout {
+            children: self.import_block(stmts),
+            ..Default::default()
+        })
+    }
+
+    /// Convert a block of statements into widgets
+    fn import_block(&mut self, stmts: &[Stmt]) -> Vec<Box<dyn WidgetNode>> {
+        let mut widgets = Vec::new();
+        let mut i = 0;
+
+        while i < stmts.len() {
+            let consumed = self.import_statements(&stmts[i..], &mut widgets);
+            if consumed == 0 {
+                widgets.push(custom_code(&stmts[i..i + 1]));
+                i += 1;
+            } else {
+                i += consumed;
+            }
+        }
+
+        widgets
+    }
+
+    /// Try to import the widget starting at `stmts[0]`. Returns the number of statements used.
+    fn import_statements(&mut self, stmts: &[Stmt], widgets: &mut Vec<Box<dyn WidgetNode>>) -> usize {
+        match &stmts[0] {
+            // `let response = ui.button(..);` followed by `if response.clicked() { .. }`
+            Stmt::Local(local) => {
+                if let Some(count) = self.import_open_window(local, &stmts[1..], widgets) {
+                    return count;
+                }
+                let (syn::Pat::Ident(pat), Some(init)) = (&local.pat, &local.init) else {
+                    return 0;
+                };
+                let response = pat.ident.to_string();
+
+                let mut events = Vec::new();
+                for stmt in &stmts[1..] {
+                    match response_event(stmt, &response) {
+                        Some(event) => events.push(event),
+                        None => break,
+                    }
+                }
+
+                let count = events.len();
+                match self.import_widget(&init.expr, events) {
+                    Some(widget) => {
+                        widgets.push(widget);
+                        1 + count
+                    }
+                    None => 0,
+                }
+            }
+            Stmt::Expr(expr, _) => {
+                if let Some(widget) = self.import_common_wrapper(expr) {
+                    widgets.push(widget);
+                    return 1;
+                }
+
+                // `if ui.button(..).clicked() { .. }`
+                if let Expr::If(expr_if) = expr {
+                    if expr_if.else_branch.is_some() {
+                        return 0;
+                    }
+                    let Expr::MethodCall(call) = &*expr_if.cond else {
+                        return 0;
+                    };
+                    let Some(event) = response_method_event(&call.method.to_string()) else {
+                        return 0;
+                    };
+                    let events = vec![event_block(event, &expr_if.then_branch.stmts)];
+                    return match self.import_widget(&call.receiver, events) {
+                        Some(widget) => {
+                            widgets.push(widget);
+                            1
+                        }
+                        None => 0,
+                    };
+                }
+
+                match self.import_widget(expr, Vec::new()) {
+                    Some(widget) => {
+                        widgets.push(widget);
+                        1
+                    }
+                    None => 0,
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// A single widget wrapped to apply its common properties, as `CodegenContext::widget_code` emits:
+    /// `if <visible> { .. }`, `ui.scope(..).response.on_hover_text(..)` and `ui.add_enabled_ui(<enabled>, ..)`
+    fn import_common_wrapper(&mut self, expr: &Expr) -> Option<Box<dyn WidgetNode>> {
+        match expr {
+            Expr::If(expr_if) if expr_if.else_branch.is_none() => {
+                let visible = flag(&expr_if.cond)?;
+                let mut widget = self.import_single(&expr_if.then_branch.stmts)?;
+                widget.common_mut().visible = visible;
+                Some(widget)
+            }
+            Expr::MethodCall(call) if call.method == "on_hover_text" && call.args.len() == 1 => {
+                let Expr::Field(field) = strip_parens(&call.receiver) else {
+                    return None;
+                };
+                let Expr::MethodCall(scope) = strip_parens(&field.base) else {
+                    return None;
+                };
+                if !matches!(&field.member, Member::Named(name) if name == "response")
+                    || scope.method != "scope"
+                    || !is_ident(&scope.receiver, "ui")
+                {
+                    return None;
+                }
+                let tooltip = text_template(&call.args[0])?;
+                let mut widget = self.import_single(closure_body(scope.args.first()?)?)?;
+                widget.common_mut().tooltip = tooltip;
+                Some(widget)
+            }
+            Expr::MethodCall(call) if call.method == "add_enabled_ui" && is_ident(&call.receiver, "ui") => {
+                let [enabled, body] = [call.args.first()?, call.args.get(1)?];
+                let enabled = flag(enabled)?;
+                let mut widget = self.import_single(closure_body(body)?)?;
+                widget.common_mut().enabled = enabled;
+                Some(widget)
+            }
+            _ => None,
+        }
+    }
+
+    /// Statements that make up exactly one widget
+    fn import_single(&mut self, stmts: &[Stmt]) -> Option<Box<dyn WidgetNode>> {
+        let mut widgets = Vec::new();
+        if stmts.is_empty() || self.import_statements(stmts, &mut widgets) != stmts.len() || widgets.len() != 1 {
+            return None;
+        }
+        widgets.pop()
+    }
+
+    /// `let mut window_open = self.<flag>;`, the window using the copy, then `self.<flag> &= window_open;`
+    fn import_open_window(
+        &mut self,
+        local: &syn::Local,
+        rest: &[Stmt],
+        widgets: &mut Vec<Box<dyn WidgetNode>>,
+    ) -> Option<usize> {
+        let syn::Pat::Ident(pat) = &local.pat else {
+            return None;
+        };
+        let field = self_field(&local.init.as_ref()?.expr)?;
+        let [Stmt::Expr(window, _), Stmt::Expr(Expr::Binary(update), Some(_)), ..] = rest else {
+            return None;
+        };
+        let local_name = pat.ident.to_string();
+        let updates_flag = matches!(update.op, syn::BinOp::BitAndAssign(_))
+            && self_field(&update.left).as_ref() == Some(&field)
+            && is_ident(&update.right, &local_name);
+        if !updates_flag {
+            return None;
+        }
+
+        self.open_flags.insert(local_name, field);
+        widgets.push(self.import_widget(window, Vec::new())?);
+        Some(3)
+    }
+
+    /// The app struct field behind `&mut window_open`, for windows imported by `import_open_window`
+    fn open_flag(&self, expr: &Expr) -> Option<String> {
+        let expr = match strip_parens(expr) {
+            Expr::Reference(reference) => strip_parens(&reference.expr),
+            other => other,
+        };
+        let Expr::Path(path) = expr else {
+            return None;
+        };
+        self.open_flags.get(&path.path.get_ident()?.to_string()).cloned()
+    }
+
+    /// Recognize a widget expression. Returns `None` if the expression or any of
+    /// its events is not supported, so the caller keeps it as custom code.
+    fn import_widget(&mut self, expr: &Expr, events: Vec<EventBlock>) -> Option<Box<dyn WidgetNode>> {
+        let expr = strip_parens(expr);
+        let Expr::MethodCall(call) = expr else {
+            return None;
+        };
+
+        // Builder chains: ScrollArea, Window and TableBuilder
+        let (base, chain) = method_chain(expr);
+        if let Some((path, args)) = call_path(base) {
+            if !events.is_empty() {
+                return None;
+            }
+            return match path_tail(&path) {
+                ["ScrollArea", ctor] => self.import_scroll_area(ctor, args, &chain),
+                ["Window", "new"] => self.import_window(args, &chain),
+                ["TableBuilder", "new"] => import_table(&chain),
+                _ => None,
+            };
+        }
+
+        if !is_ident(&call.receiver, "ui") {
+            return None;
+        }
+        let args: Vec<&Expr> = call.args.iter().collect();
+
+        match (call.method.to_string().as_str(), args.as_slice()) {
+            ("button", [text]) => {
+                let mut widget = ButtonWidget::default();
+                set_text_or_binding(text, &mut widget.text, &mut widget.bindings, "text")?;
+                widget.events = take_events(events, &[WidgetEvent::Clicked, WidgetEvent::Hovered], &mut widget.id)?;
+                Some(Box::new(widget))
+            }
+            ("label", [text]) if events.is_empty() => {
+                let mut widget = LabelWidget::default();
+                set_text_or_binding(text, &mut widget.text, &mut widget.bindings, "text")?;
+                Some(Box::new(widget))
+            }
+            ("text_edit_singleline", [value]) => self.import_text_edit(value, events),
+            ("checkbox", [value, label]) => {
+                let mut widget = CheckboxWidget::default();
+                widget.bindings.insert("checked".to_string(), self_field(value)?);
+                widget.label = text_template(label)?;
+                widget.events = take_events(events, &[WidgetEvent::Changed], &mut widget.id)?;
+                Some(Box::new(widget))
+            }
+            ("add", [inner]) => {
+                let (path, inner_args) = call_path(strip_parens(inner))?;
+                match (path_tail(&path), inner_args.iter().collect::<Vec<_>>().as_slice()) {
+                    (["TextEdit", "singleline"], [value]) => self.import_text_edit(value, events),
+                    (["Slider", "new"], [value, range]) => {
+                        let Expr::Range(range) = strip_parens(range) else {
+                            return None;
+                        };
+                        let mut widget = SliderWidget::default();
+                        widget.bindings.insert("value".to_string(), self_field(value)?);
+                        widget.min = number_literal(range.start.as_deref()?)?;
+                        widget.max = number_literal(range.end.as_deref()?)?;
+                        widget.events = take_events(events, &[WidgetEvent::Changed], &mut widget.id)?;
+                        Some(Box::new(widget))
+                    }
+                    _ => None,
+                }
+            }
+            ("separator", []) if events.is_empty() => Some(Box::new(SeparatorWidget::default())),
+            ("hyperlink_to", [text, url]) if events.is_empty() => {
+                Some(Box::new(HyperlinkWidget {
+                    text: text_template(text)?,
+                    url: string_literal(url)?,
+                    ..Default::default()
+                }))
+            }
+            ("horizontal", [_]) if events.is_empty() => {
+                let stmts = ui_closure_call(expr, "horizontal")?;
+                Some(Box::new(HorizontalLayout {
+                    children: self.import_block(stmts),
+                    ..Default::default()
+                }))
+            }
+            ("vertical", [_]) if events.is_empty() => {
+                let stmts = ui_closure_call(expr, "vertical")?;
+                Some(Box::new(VerticalLayout {
+                    children: self.import_block(stmts),
+                    ..Default::default()
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    fn import_text_edit(&mut self, value: &Expr, events: Vec<EventBlock>) -> Option<Box<dyn WidgetNode>> {
+        let mut widget = TextEditWidget::default();
+        widget.bindings.insert("value".to_string(), self_field(value)?);
+        widget.events = take_events(
+            events,
+            &[WidgetEvent::Changed, WidgetEvent::Focused, WidgetEvent::LostFocus],
+            &mut widget.id,
+        )?;
+        Some(Box::new(widget))
+    }
+
+    fn import_scroll_area(
+        &mut self,
+        ctor: &str,
+        args: &Punctuated<Expr, Token![,]>,
+        chain: &[&ExprMethodCall],
+    ) -> Option<Box<dyn WidgetNode>> {
+        let mut widget = ScrollAreaWidget::default();
+        (widget.scroll_horizontal, widget.scroll_vertical) = match ctor {
+            "vertical" => (false, true),
+            "horizontal" => (true, false),
+            "both" => (true, true),
+            "new" => {
+                let Some(Expr::Array(array)) = args.first() else {
+                    return None;
+                };
+                let flags: Vec<bool> = array.elems.iter().map(bool_literal).collect::<Option<_>>()?;
+                match flags.as_slice() {
+                    [h, v] => (*h, *v),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        widget.max_height = None;
+        widget.max_width = None;
+
+        for call in chain {
+            let arg = call.args.first();
+            match call.method.to_string().as_str() {
+                "max_height" => widget.max_height = Some(number_literal(arg?)? as f32),
+                "max_width" => widget.max_width = Some(number_literal(arg?)? as f32),
+                "show" => widget.children = self.import_block(closure_body(call.args.last()?)?),
+                // Generated for titles that show variables, and regenerated on export
+                "id" => {}
+                _ => return None,
+            }
+        }
+        Some(Box::new(widget))
+    }
+
+    fn import_window(
+        &mut self,
+        args: &Punctuated<Expr, Token![,]>,
+        chain: &[&ExprMethodCall],
+    ) -> Option<Box<dyn WidgetNode>> {
+        let mut widget = WindowWidget {
+            title: text_template(args.first()?)?,
+            closeable: false,
+            default_height: None,
+            ..Default::default()
+        };
+
+        for call in chain {
+            let arg = call.args.first();
+            match call.method.to_string().as_str() {
+                "open" => {
+                    // The open flag is widget state, not a project variable
+                    let arg = arg?;
+                    let field = self_field(arg).or_else(|| self.open_flag(arg))?;
+                    if let Some(id) = embedded_uuid(&field) {
+                        widget.id = id;
+                    }
+                    self.consumed_fields.insert(field);
+                    widget.closeable = true;
+                }
+                "collapsible" => widget.collapsible = bool_literal(arg?)?,
+                "resizable" => widget.resizable = bool_literal(arg?)?,
+                "default_width" => widget.default_width = number_literal(arg?)? as f32,
+                "default_height" => widget.default_height = Some(number_literal(arg?)? as f32),
+                "show" => widget.children = self.import_block(closure_body(call.args.last()?)?),
+                // Generated for titles that show variables, and regenerated on export
+                "id" => {}
+                _ => return None,
+            }
+        }
+        Some(Box::new(widget))
+    }
+}
+
+// ===================== PATTERN HELPERS =====================
+
+fn import_table(chain: &[&ExprMethodCall]) -> Option<Box<dyn WidgetNode>> {
+    let mut widget = TableWidget::default();
+    let mut widths = Vec::new();
+    let mut headers = Vec::new();
+
+    for call in chain {
+        let arg = call.args.first();
+        match call.method.to_string().as_str() {
+            "striped" => widget.striped = bool_literal(arg?)?,
+            "resizable" => widget.resizable = bool_literal(arg?)?,
+            "cell_layout" | "min_scrolled_height" => {}
+            "column" => {
+                // Only exact columns keep a fixed width
+                let (base, _) = method_chain(arg?);
+                let (path, args) = call_path(base)?;
+                widths.push(match path_tail(&path) {
+                    ["Column", "exact"] => Some(number_literal(args.first()?)? as f32),
+                    _ => None,
+                });
+            }
+            "header" => {
+                for stmt in closure_body(call.args.last()?)? {
+                    let Stmt::Expr(Expr::MethodCall(col), _) = stmt else {
+                        return None;
+                    };
+                    let [Stmt::Expr(Expr::MethodCall(strong), _)] = closure_body(col.args.first()?)? else {
+                        return None;
+                    };
+                    headers.push(string_literal(strong.args.first()?)?);
+                }
+            }
+            "body" => {
+                // Row count comes from `for i in 0..N`
+                if let Some(Stmt::Expr(Expr::ForLoop(for_loop), _)) = closure_body(call.args.last()?)?.first() {
+                    if let Expr::Range(range) = &*for_loop.expr {
+                        widget.row_count = number_literal(range.end.as_deref()?)? as usize;
+                    }
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    widget.columns = headers
+        .into_iter()
+        .enumerate()
+        .map(|(i, header)| TableColumn {
+            header,
+            width: widths.get(i).copied().flatten(),
+        })
+        .collect();
+    Some(Box::new(widget))
+}
+
+/// Match `if <response>.clicked() { .. }` after a `let response = ..` binding
+fn response_event(stmt: &Stmt, response: &str) -> Option<EventBlock> {
+    let Stmt::Expr(Expr::If(expr_if), _) = stmt else {
+        return None;
+    };
+    if expr_if.else_branch.is_some() {
+        return None;
+    }
+    let Expr::MethodCall(call) = &*expr_if.cond else {
+        return None;
+    };
+    if !is_ident(&call.receiver, response) || !call.args.is_empty() {
+        return None;
+    }
+    let event = response_method_event(&call.method.to_string())?;
+    Some(event_block(event, &expr_if.then_branch.stmts))
+}
+
+fn response_method_event(method: &str) -> Option<WidgetEvent> {
+    Some(match method {
+        "clicked" => WidgetEvent::Clicked,
+        "changed" => WidgetEvent::Changed,
+        "hovered" => WidgetEvent::Hovered,
+        "double_clicked" => WidgetEvent::DoubleClicked,
+        "gained_focus" => WidgetEvent::Focused,
+        "lost_focus" => WidgetEvent::LostFocus,
+        _ => return None,
+    })
+}
+
+/// Turn an event body into actions, dropping generated hook calls
+fn event_block(event: WidgetEvent, stmts: &[Stmt]) -> EventBlock {
+    let mut hook_id = None;
+    let body: Vec<Stmt> = stmts
+        .iter()
+        .filter(|stmt| match hook_call(stmt) {
+            Some(name) => {
+                hook_id = hook_id.or(embedded_uuid(&name));
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    EventBlock { event, actions: statement_actions(&body), hook_id }
+}
+
+/// Recognize the statements an action generates. Runs of other statements become `Custom` actions.
+fn statement_actions(stmts: &[Stmt]) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let mut custom: Vec<Stmt> = Vec::new();
+    for stmt in stmts {
+        match statement_action(stmt) {
+            Some(action) => {
+                if !custom.is_empty() {
+                    actions.push(Action::Custom(format_stmts(&std::mem::take(&mut custom))));
+                }
+                actions.push(action);
+            }
+            None => custom.push(stmt.clone()),
+        }
+    }
+    if !custom.is_empty() {
+        actions.push(Action::Custom(format_stmts(&custom)));
+    }
+    actions
+}
+
+fn statement_action(stmt: &Stmt) -> Option<Action> {
+    match stmt {
+        Stmt::Expr(Expr::Binary(binary), Some(_)) => {
+            let field = self_field(&binary.left)?;
+            let amount = format_expr(&binary.right);
+            match binary.op {
+                syn::BinOp::AddAssign(_) if amount == "1" => Some(Action::IncrementVariable(field)),
+                syn::BinOp::AddAssign(_) => Some(Action::AddToVariable(field, amount)),
+                syn::BinOp::SubAssign(_) if amount == "1" => Some(Action::DecrementVariable(field)),
+                _ => None,
+            }
+        }
+        Stmt::Expr(Expr::Assign(assign), Some(_)) => {
+            let field = self_field(&assign.left)?;
+            Some(assignment_action(field, &assign.right))
+        }
+        Stmt::Expr(Expr::MethodCall(call), Some(_)) => method_call_action(call),
+        Stmt::Expr(Expr::If(expr_if), _) => if_action(expr_if),
+        _ => None,
+    }
+}
+
+/// `self.<field> = <value>;`
+fn assignment_action(field: String, value: &Expr) -> Action {
+    let value = strip_parens(value);
+    if let Expr::Unary(syn::ExprUnary { op: syn::UnOp::Not(_), expr, .. }) = value {
+        if self_field(expr).as_ref() == Some(&field) {
+            return Action::ToggleVariable(field);
+        }
+    }
+    // Window open flags are named after the window's id
+    if let (Some(window), Some(open)) = (field.strip_prefix("window_").and_then(embedded_uuid), bool_literal(value)) {
+        if field.ends_with("_open") {
+            return Action::SetWindowOpen { window, open };
+        }
+    }
+    let source = match value {
+        Expr::MethodCall(call) if call.method == "clone" && call.args.is_empty() => self_field(&call.receiver),
+        other => self_field(other),
+    };
+    match source {
+        Some(from) => Action::CopyVariable { from, to: field },
+        None => Action::SetVariable(field, format_expr(value)),
+    }
+}
+
+/// `self.<list>.push(..)`, `self.<list>.clear()`, `ui.ctx().open_url(..)` and `ui.ctx().copy_text(..)`
+fn method_call_action(call: &ExprMethodCall) -> Option<Action> {
+    let method = call.method.to_string();
+    if let Some(field) = self_field(&call.receiver) {
+        return match (method.as_str(), call.args.first()) {
+            ("push", Some(value)) if call.args.len() == 1 => Some(Action::PushToList(field, format_expr(value))),
+            ("clear", None) => Some(Action::ClearList(field)),
+            _ => None,
+        };
+    }
+
+    let Expr::MethodCall(receiver) = strip_parens(&call.receiver) else {
+        return None;
+    };
+    if receiver.method != "ctx" || !is_ident(&receiver.receiver, "ui") || call.args.len() != 1 {
+        return None;
+    }
+    match (method.as_str(), strip_parens(&call.args[0])) {
+        ("open_url", open) => {
+            let (path, args) = call_path(open)?;
+            match (path_tail(&path), args.first()) {
+                (["OpenUrl", "new_tab"], Some(url)) if args.len() == 1 => Some(Action::OpenUrl(text_template(url)?)),
+                _ => None,
+            }
+        }
+        ("copy_text", Expr::MethodCall(text)) if text.method == "to_string" && text.args.is_empty() => {
+            Some(Action::CopyToClipboard(text_template(&text.receiver)?))
+        }
+        ("copy_text", text) => Some(Action::CopyToClipboard(text_template(text)?)),
+        _ => None,
+    }
+}
+
+/// `if <condition> { .. } else { .. }`, where `else if` becomes an `If` in the else branch
+fn if_action(expr_if: &syn::ExprIf) -> Option<Action> {
+    let condition = condition(&expr_if.cond)?;
+    let then = statement_actions(&expr_if.then_branch.stmts);
+    let otherwise = match expr_if.else_branch.as_ref().map(|(_, expr)| expr.as_ref()) {
+        None => Vec::new(),
+        Some(Expr::Block(block)) => statement_actions(&block.block.stmts),
+        Some(Expr::If(nested)) => vec![if_action(nested)?],
+        Some(_) => return None,
+    };
+    Some(Action::If { condition, then, otherwise })
+}
+
+/// `true`, `false` or `self.<flag>`
+fn flag(expr: &Expr) -> Option<Flag> {
+    match bool_literal(expr) {
+        Some(value) => Some(Flag::Static(value)),
+        None => self_field(expr).map(Flag::Bound),
+    }
+}
+
+/// `self.flag`, `!self.flag` or `self.field <op> value`
+fn condition(expr: &Expr) -> Option<Condition> {
+    let test = |variable: String, op: CompareOp, value: String| Some(Condition { variable, op, value });
+    match strip_parens(expr) {
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Not(_), expr, .. }) => {
+            test(self_field(expr)?, CompareOp::IsFalse, String::new())
+        }
+        Expr::Binary(binary) => {
+            let op = match binary.op {
+                syn::BinOp::Eq(_) => CompareOp::Eq,
+                syn::BinOp::Ne(_) => CompareOp::Ne,
+                syn::BinOp::Lt(_) => CompareOp::Lt,
+                syn::BinOp::Le(_) => CompareOp::Le,
+                syn::BinOp::Gt(_) => CompareOp::Gt,
+                syn::BinOp::Ge(_) => CompareOp::Ge,
+                _ => return None,
+            };
+            test(self_field(&binary.left)?, op, format_expr(&binary.right))
+        }
+        other => test(self_field(other)?, CompareOp::IsTrue, String::new()),
+    }
+}
+
+/// `self.on_..();` with no arguments, as emitted for `AppEvents` hooks
+fn hook_call(stmt: &Stmt) -> Option<String> {
+    let Stmt::Expr(Expr::MethodCall(call), Some(_)) = stmt else {
+        return None;
+    };
+    let name = call.method.to_string();
+    (is_ident(&call.receiver, "self") && call.args.is_empty() && name.starts_with("on_")).then_some(name)
+}
+
+/// Collect events a widget supports. Fails if any event isn't supported.
+fn take_events(
+    events: Vec<EventBlock>,
+    supported: &[WidgetEvent],
+    id: &mut Uuid,
+) -> Option<HashMap<WidgetEvent, Vec<Action>>> {
+    let mut map = HashMap::new();
+    for block in events {
+        if !supported.contains(&block.event) || map.contains_key(&block.event) {
+            return None;
+        }
+        if let Some(hook_id) = block.hook_id {
+            *id = hook_id;
+        }
+        map.insert(block.event, block.actions);
+    }
+    Some(map)
+}
+
+/// Fill in either literal text or a variable binding
+fn set_text_or_binding(
+    expr: &Expr,
+    text: &mut String,
+    bindings: &mut HashMap<String, String>,
+    property: &str,
+) -> Option<()> {
+    if let Some(template) = text_template(expr) {
+        *text = template;
+        return Some(());
+    }
+
+    // `&self.x`, `self.x.to_string()` or `self.x.clone()`
+    let field = match strip_parens(expr) {
+        Expr::MethodCall(call) if call.args.is_empty() && ["to_string", "clone"].contains(&call.method.to_string().as_str()) => {
+            self_field(&call.receiver)
+        }
+        other => self_field(other),
+    }?;
+    bindings.insert(property.to_string(), field);
+    Some(())
+}
+
+/// `ui.<method>(|ui| { .. })`, returning the closure body
+fn ui_closure_call<'a>(expr: &'a Expr, method: &str) -> Option<&'a [Stmt]> {
+    let Expr::MethodCall(call) = strip_parens(expr) else {
+        return None;
+    };
+    if call.method != method || !is_ident(&call.receiver, "ui") || call.args.len() != 1 {
+        return None;
+    }
+    closure_body(call.args.first()?)
+}
+
+fn closure_body(expr: &Expr) -> Option<&[Stmt]> {
+    let Expr::Closure(closure) = expr else {
+        return None;
+    };
+    match &*closure.body {
+        Expr::Block(block) => Some(&block.block.stmts),
+        _ => None,
+    }
+}
+
+/// Unroll `base.a(..).b(..)` into the base expression and its calls, in call order
+fn method_chain(expr: &Expr) -> (&Expr, Vec<&ExprMethodCall>) {
+    let mut calls = Vec::new();
+    let mut current = expr;
+    while let Expr::MethodCall(call) = current {
+        calls.push(call);
+        current = &call.receiver;
+    }
+    calls.reverse();
+    (current, calls)
+}
+
+/// `a::b::c(args)` as its path segments and arguments
+fn call_path(expr: &Expr) -> Option<(Vec<String>, &Punctuated<Expr, Token![,]>)> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    let segments = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    Some((segments, &call.args))
+}
+
+/// Last two path segments, e.g. `["Window", "new"]` for `egui::Window::new`
+fn path_tail(path: &[String]) -> [&str; 2] {
+    match path {
+        [.., a, b] => [a.as_str(), b.as_str()],
+        [b] => ["", b.as_str()],
+        [] => ["", ""],
+    }
+}
+
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(strip_parens(expr), Expr::Path(path) if path.path.is_ident(name))
+}
+
+/// The field name in `self.x`, `&self.x` or `&mut self.x`
+fn self_field(expr: &Expr) -> Option<String> {
+    let expr = match strip_parens(expr) {
+        Expr::Reference(reference) => strip_parens(&reference.expr),
+        other => other,
+    };
+    let Expr::Field(field) = expr else {
+        return None;
+    };
+    match (&field.member, is_ident(&field.base, "self")) {
+        (Member::Named(ident), true) => Some(ident.to_string()),
+        _ => None,
+    }
+}
+
+fn strip_parens(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => strip_parens(&paren.expr),
+        Expr::Group(group) => strip_parens(&group.expr),
+        other => other,
+    }
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) => Some(s.value()),
+        _ => None,
+    }
+}
+
+/// Widget text from a string literal or a `format!` of app fields, as an `interpolation` template
+fn text_template(expr: &Expr) -> Option<String> {
+    use crate::interpolation;
+
+    if let Some(literal) = string_literal(expr) {
+        // Escape only text that would otherwise read as placeholders
+        if interpolation::literal_text(&literal) == literal {
+            return Some(literal);
+        }
+        return Some(interpolation::escape(&literal));
+    }
+
+    let Expr::Macro(mac) = strip_parens(expr) else {
+        return None;
+    };
+    if !mac.mac.path.is_ident("format") {
+        return None;
+    }
+    let args = mac.mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated).ok()?;
+    let mut args = args.iter();
+    let format_string = string_literal(args.next()?)?;
+    let fields: Vec<String> = args
+        .map(|arg| match strip_parens(arg) {
+            Expr::Reference(reference) => self_field(&reference.expr),
+            other => self_field(other),
+        })
+        .collect::<Option<_>>()?;
+
+    // Swap positional `{}` / `{0}` arguments for the field names
+    let mut template = String::new();
+    let mut next = 0;
+    let mut rest = format_string.as_str();
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            template.push_str(&rest[..2]);
+            rest = &rest[2..];
+        } else if c == '{' {
+            let end = rest.find('}')?;
+            let inner = &rest[1..end];
+            let (position, spec) = inner.split_once(':').unwrap_or((inner, ""));
+            let index = if position.is_empty() {
+                next += 1;
+                next - 1
+            } else {
+                position.parse().ok()?
+            };
+            template.push('{');
+            template.push_str(fields.get(index)?);
+            if !spec.is_empty() {
+                template.push(':');
+                template.push_str(spec);
+            }
+            template.push('}');
+            rest = &rest[end + 1..];
+        } else {
+            template.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Some(template)
+}
+
+fn bool_literal(expr: &Expr) -> Option<bool> {
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit { lit: Lit::Bool(b), .. }) => Some(b.value),
+        _ => None,
+    }
+}
+
+/// Numeric literal, looking through parens, casts (`0.0 as _`) and negation
+fn number_literal(expr: &Expr) -> Option<f64> {
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse().ok(),
+        Expr::Lit(syn::ExprLit { lit: Lit::Float(f), .. }) => f.base10_parse().ok(),
+        Expr::Cast(cast) => number_literal(&cast.expr),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => number_literal(expr).map(|n| -n),
+        _ => None,
+    }
+}
+
+/// Widget ids are embedded in generated names as 32 hex digits (e.g. `window_<id>_open`)
+fn embedded_uuid(name: &str) -> Option<Uuid> {
+    name.split('_')
+        .find(|part| part.len() == 32)
+        .and_then(|part| Uuid::parse_str(part).ok())
+}
+
+fn custom_code(stmts: &[Stmt]) -> Box<dyn WidgetNode> {
+    Box::new(CustomCodeWidget {
+        code: format_stmts(stmts),
+        ..Default::default()
+    })
+}
+
+fn format_expr(expr: &Expr) -> String {
+    format_stmts(&[Stmt::Expr(expr.clone(), None)])
+}
+
+/// Pretty-print statements by wrapping them in a throwaway function
+fn format_stmts(stmts: &[Stmt]) -> String {
+    if stmts.is_empty() {
+        return String::new();
+    }
+    let item: syn::ItemFn = syn::parse_quote! {
+        fn aether_import() {
+            #(#stmts)*
+        }
+    };
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: vec![Item::Fn(item)],
+    };
+    let text = prettyplease::unparse(&file);
+
+    let lines: Vec<&str> = text.lines().collect();
+    lines[1..lines.len().saturating_sub(1)]
+        .iter()
+        .map(|line| line.strip_prefix("    ").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ===================== APP STRUCT =====================
+
+/// The type that implements `eframe::App`, falling back to `MyApp`
+fn find_app_struct_name(file: &syn::File) -> Option<String> {
+    let app_impl = file.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl) => {
+            let (_, trait_path, _) = item_impl.trait_.as_ref()?;
+            if trait_path.segments.last()?.ident != "App" {
+                return None;
+            }
+            type_name(&item_impl.self_ty)
+        }
+        _ => None,
+    });
+
+    app_impl.or_else(|| {
+        file.items.iter().find_map(|item| match item {
+            Item::Struct(s) if s.ident == "MyApp" => Some(s.ident.to_string()),
+            _ => None,
+        })
+    })
+}
+
+/// Statements of the central panel closure in `update`, or the whole body if there is none
+fn find_ui_body(file: &syn::File, app_name: &str) -> Option<Vec<Stmt>> {
+    let update = file.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl) if type_name(&item_impl.self_ty).as_deref() == Some(app_name) => {
+            item_impl.items.iter().find_map(|item| match item {
+                ImplItem::Fn(f) if f.sig.ident == "update" => Some(f),
+                _ => None,
+            })
+        }
+        _ => None,
+    })?;
+
+    for stmt in &update.block.stmts {
+        let Stmt::Expr(expr, _) = stmt else {
+            continue;
+        };
+        let (base, chain) = method_chain(expr);
+        let is_central_panel = call_path(base).is_some_and(|(path, _)| path.iter().any(|s| s == "CentralPanel"));
+        if let (true, Some(show)) = (is_central_panel, chain.last()) {
+            if show.method == "show" {
+                return closure_body(show.args.last()?).map(|stmts| stmts.to_vec());
+            }
+        }
+    }
+
+    Some(update.block.stmts.clone())
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => Some(path.path.segments.last()?.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Variables from the app struct's fields, with initial values taken from `Default`
+fn infer_variables(file: &syn::File, app_name: &str) -> HashMap<String, Variable> {
+    let initial_values = default_field_values(file, app_name);
+    let mut variables = HashMap::new();
+
+    let Some(Fields::Named(fields)) = file.items.iter().find_map(|item| match item {
+        Item::Struct(s) if s.ident == app_name => Some(&s.fields),
+        _ => None,
+    }) else {
+        return variables;
+    };
+
+    for field in &fields.named {
+        let Some(ident) = &field.ident else { continue };
+        let v_type = match type_name(&field.ty).as_deref() {
+            Some("String") => VariableType::String,
+            Some("u8") => VariableType::U8,
+            Some("u32") => VariableType::U32,
+            Some("i64") => VariableType::I64,
+            Some("usize") => VariableType::Usize,
+            Some("f32") => VariableType::F32,
+            // Widths without a variable type of their own
+            Some("i8" | "i16" | "i32" | "isize" | "u16" | "u64") => VariableType::Integer,
+            Some("f64") => VariableType::Float,
+            Some("bool") => VariableType::Boolean,
+            // Other types can't be represented as variables yet
+            _ => continue,
+        };
+
+        let name = ident.to_string();
+        let value = initial_values
+            .get(&name)
+            .cloned()
+            .unwrap_or_else(|| v_type.default_value(&[]));
+        variables.insert(name.clone(), Variable { name, v_type, value });
+    }
+
+    variables
+}
+
+/// Literal field values from `impl Default for <app>`
+fn default_field_values(file: &syn::File, app_name: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+
+    let default_fn = file.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl)
+            if type_name(&item_impl.self_ty).as_deref() == Some(app_name)
+                && item_impl
+                    .trait_
+                    .as_ref()
+                    .is_some_and(|(_, path, _)| path.segments.last().is_some_and(|s| s.ident == "Default")) =>
+        {
+            item_impl.items.iter().find_map(|item| match item {
+                ImplItem::Fn(f) if f.sig.ident == "default" => Some(f),
+                _ => None,
+            })
+        }
+        _ => None,
+    });
+
+    let Some(Stmt::Expr(Expr::Struct(literal), _)) = default_fn.and_then(|f| f.block.stmts.last()) else {
+        return values;
+    };
+
+    for field in &literal.fields {
+        let Member::Named(ident) = &field.member else { continue };
+        if let Some(value) = literal_value(&field.expr) {
+            values.insert(ident.to_string(), value);
+        }
+    }
+    values
+}
+
+/// Text of a literal initializer, including `String::from("..")` and `"..".to_string()`
+fn literal_value(expr: &Expr) -> Option<String> {
+    if let Some(s) = string_literal(expr) {
+        return Some(s);
+    }
+    if let Some(b) = bool_literal(expr) {
+        return Some(b.to_string());
+    }
+    match strip_parens(expr) {
+        Expr::Lit(syn::ExprLit { lit: Lit::Int(i), .. }) => Some(i.base10_digits().to_string()),
+        Expr::Lit(syn::ExprLit { lit: Lit::Float(f), .. }) => Some(f.base10_digits().to_string()),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => {
+            literal_value(expr).map(|v| format!("-{}", v))
+        }
+        Expr::MethodCall(call) if call.args.is_empty() => match call.method.to_string().as_str() {
+            "to_string" | "to_owned" | "into" => string_literal(&call.receiver),
+            _ => None,
+        },
+        Expr::Call(_) => {
+            let (path, args) = call_path(strip_parens(expr))?;
+            match (path_tail(&path), args.first()) {
+                (["String", "from"], Some(arg)) => string_literal(arg),
+                (["String", "new"], None) => Some(String::new()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
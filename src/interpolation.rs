@@ -0,0 +1,283 @@
+//! Widget text with `{variable}` placeholders, e.g. "Count: {counter}" or
+//! "{first} {last} ({age:.1})". A placeholder may carry a Rust format spec after
+//! a colon. `{{` and `}}` are literal braces, and braces that don't form a
+//! placeholder are kept as text, so plain text never needs escaping.
+
+use crate::model::VariableType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Placeholder { var: String, spec: String },
+}
+
+impl Segment {
+    /// The segment as written, without escaping
+    pub fn source(&self) -> String {
+        match self {
+            Segment::Text(text) => text.clone(),
+            Segment::Placeholder { var, spec } if spec.is_empty() => format!("{{{}}}", var),
+            Segment::Placeholder { var, spec } => format!("{{{}:{}}}", var, spec),
+        }
+    }
+}
+
+/// Split text into literal runs and placeholders
+pub fn parse(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push(c);
+            rest = &rest[2..];
+            continue;
+        }
+        if c == '{' {
+            if let Some((var, spec, len)) = placeholder_at(rest) {
+                if !literal.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder {
+                    var: var.to_string(),
+                    spec: spec.to_string(),
+                });
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        literal.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Text(literal));
+    }
+    segments
+}
+
+/// `{name}` or `{name:spec}` at the start of `text`: the name, the spec and the length matched
+fn placeholder_at(text: &str) -> Option<(&str, &str, usize)> {
+    let end = text.find('}')?;
+    let inner = &text[1..end];
+    let (var, spec) = inner.split_once(':').unwrap_or((inner, ""));
+    let is_name = var.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_name || spec.contains('{') {
+        return None;
+    }
+    Some((var, spec, end + 1))
+}
+
+/// Whether the text refers to any variables
+pub fn has_placeholders(text: &str) -> bool {
+    parse(text).iter().any(|s| matches!(s, Segment::Placeholder { .. }))
+}
+
+/// The text with placeholders left in, as shown on the design canvas
+pub fn literal_text(text: &str) -> String {
+    parse(text).iter().map(Segment::source).collect()
+}
+
+/// Escape literal text so none of it reads as a placeholder
+pub fn escape(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+/// Problems with a placeholder for a variable of type `v_type` (`None` if there is no such variable)
+pub fn check_placeholder(var: &str, spec: &str, v_type: Option<&VariableType>) -> Result<(), String> {
+    let Some(v_type) = v_type else {
+        return Err(format!("{{{}}} refers to unknown variable '{}'", var, var));
+    };
+    let spec = FormatSpec::parse(spec).map_err(|e| format!("{{{}:{}}}: {}", var, spec, e))?;
+    match spec.kind {
+        FormatKind::LowerHex | FormatKind::UpperHex | FormatKind::Binary | FormatKind::Octal
+            if !v_type.is_integer() =>
+        {
+            Err(format!("{{{}}}: hex, binary and octal need an integer, not {}", var, v_type))
+        }
+        FormatKind::LowerExp if !v_type.is_numeric() => {
+            Err(format!("{{{}}}: exponent format needs a number, not {}", var, v_type))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// How a placeholder's value is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormatKind {
+    #[default]
+    Display,
+    Debug,
+    LowerHex,
+    UpperHex,
+    Binary,
+    Octal,
+    LowerExp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// The subset of Rust's format spec that placeholders support:
+/// `[[fill]align][+][#][0][width][.precision][?|x|X|b|o|e]`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormatSpec {
+    pub fill: Option<char>,
+    pub align: Option<Align>,
+    pub plus: bool,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub kind: FormatKind,
+}
+
+impl FormatSpec {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut result = FormatSpec::default();
+        let chars: Vec<char> = spec.chars().collect();
+        let mut i = 0;
+
+        let align = |c: char| match c {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        };
+        if let Some(a) = chars.get(1).copied().and_then(align) {
+            result.fill = Some(chars[0]);
+            result.align = Some(a);
+            i = 2;
+        } else if let Some(a) = chars.first().copied().and_then(align) {
+            result.align = Some(a);
+            i = 1;
+        }
+        if chars.get(i) == Some(&'+') {
+            result.plus = true;
+            i += 1;
+        }
+        if chars.get(i) == Some(&'#') {
+            result.alternate = true;
+            i += 1;
+        }
+        if chars.get(i) == Some(&'0') {
+            result.zero = true;
+            i += 1;
+        }
+        let number = |i: &mut usize| -> Option<usize> {
+            let start = *i;
+            while chars.get(*i).is_some_and(|c| c.is_ascii_digit()) {
+                *i += 1;
+            }
+            chars[start..*i].iter().collect::<String>().parse().ok()
+        };
+        result.width = number(&mut i);
+        if chars.get(i) == Some(&'.') {
+            i += 1;
+            result.precision = Some(number(&mut i).ok_or("expected a precision after '.'")?);
+        }
+        result.kind = match chars.get(i) {
+            None => FormatKind::Display,
+            Some('?') => FormatKind::Debug,
+            Some('x') => FormatKind::LowerHex,
+            Some('X') => FormatKind::UpperHex,
+            Some('b') => FormatKind::Binary,
+            Some('o') => FormatKind::Octal,
+            Some('e') => FormatKind::LowerExp,
+            Some(c) => return Err(format!("unsupported format '{}'", c)),
+        };
+        if result.kind != FormatKind::Display {
+            i += 1;
+        }
+        if i < chars.len() {
+            return Err("unsupported format spec".to_string());
+        }
+        Ok(result)
+    }
+
+    /// Format a variable's value text the way `format!` formats the generated field
+    pub fn apply(&self, v_type: &VariableType, value: &str) -> String {
+        let (body, numeric) = if v_type.is_integer() {
+            let n: i128 = value.trim().parse().unwrap_or_default();
+            let body = match self.kind {
+                FormatKind::LowerHex => radix(format!("{:x}", n.unsigned_abs()), "0x", self.alternate),
+                FormatKind::UpperHex => radix(format!("{:X}", n.unsigned_abs()), "0x", self.alternate),
+                FormatKind::Binary => radix(format!("{:b}", n.unsigned_abs()), "0b", self.alternate),
+                FormatKind::Octal => radix(format!("{:o}", n.unsigned_abs()), "0o", self.alternate),
+                FormatKind::LowerExp => format!("{:e}", n.unsigned_abs() as f64),
+                FormatKind::Display | FormatKind::Debug => n.unsigned_abs().to_string(),
+            };
+            (signed(body, n < 0, self.plus), true)
+        } else if v_type.is_float() {
+            let n: f64 = value.trim().parse().unwrap_or_default();
+            let body = match (self.kind, self.precision) {
+                (FormatKind::LowerExp, Some(p)) => format!("{:.*e}", p, n.abs()),
+                (FormatKind::LowerExp, None) => format!("{:e}", n.abs()),
+                (_, Some(p)) => format!("{:.*}", p, n.abs()),
+                (FormatKind::Debug, None) => format!("{:?}", n.abs()),
+                (_, None) => n.abs().to_string(),
+            };
+            (signed(body, n.is_sign_negative() && n != 0.0, self.plus), true)
+        } else {
+            let text = match (self.kind, v_type) {
+                (FormatKind::Debug, VariableType::String) => format!("{:?}", value),
+                (_, VariableType::String) => match self.precision {
+                    Some(p) => value.chars().take(p).collect(),
+                    None => value.to_string(),
+                },
+                _ => value.to_string(),
+            };
+            (text, false)
+        };
+        self.pad(body, numeric)
+    }
+
+    fn pad(&self, body: String, numeric: bool) -> String {
+        let Some(width) = self.width else {
+            return body;
+        };
+        let len = body.chars().count();
+        if len >= width {
+            return body;
+        }
+        let missing = width - len;
+        if self.zero && numeric {
+            // Zeros go between the sign or radix prefix and the digits
+            let sign_len = body.starts_with(['-', '+']) as usize;
+            let has_radix = self.alternate
+                && matches!(self.kind, FormatKind::LowerHex | FormatKind::UpperHex | FormatKind::Binary | FormatKind::Octal);
+            let prefix_len = sign_len + if has_radix { 2 } else { 0 };
+            let (prefix, digits) = body.split_at(prefix_len);
+            return format!("{}{}{}", prefix, "0".repeat(missing), digits);
+        }
+        let fill = self.fill.unwrap_or(' ').to_string();
+        let align = self.align.unwrap_or(if numeric { Align::Right } else { Align::Left });
+        let (before, after) = match align {
+            Align::Left => (0, missing),
+            Align::Right => (missing, 0),
+            Align::Center => (missing / 2, missing - missing / 2),
+        };
+        format!("{}{}{}", fill.repeat(before), body, fill.repeat(after))
+    }
+}
+
+fn radix(digits: String, prefix: &str, alternate: bool) -> String {
+    if alternate {
+        format!("{}{}", prefix, digits)
+    } else {
+        digits
+    }
+}
+
+fn signed(body: String, negative: bool, plus: bool) -> String {
+    match (negative, plus) {
+        (true, _) => format!("-{}", body),
+        (false, true) => format!("+{}", body),
+        (false, false) => body,
+    }
+}
@@ -25,6 +25,15 @@ pub fn pick_file(filter_name: &str) -> Option<PathBuf> {
         .pick_file()
 }
 
+/// Show a file picker dialog for Rust source files
+/// Returns the selected file path
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pick_rust_file() -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("Rust Source", &["rs"])
+        .pick_file()
+}
+
 /// Save dialog for project files
 #[cfg(not(target_arch = "wasm32"))]
 pub fn save_file(default_name: &str) -> Option<PathBuf> {
@@ -67,6 +76,12 @@ pub fn pick_file(_filter_name: &str) -> Option<PathBuf> {
     None
 }
 
+#[cfg(target_arch = "wasm32")]
+pub fn pick_rust_file() -> Option<PathBuf> {
+    // WASM doesn't have synchronous file picker
+    None
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn save_file(_default_name: &str) -> Option<PathBuf> {
     // WASM doesn't have native save dialog
@@ -1,9 +1,15 @@
 // Library exports for testing and potential reuse
 
 pub mod app;
+pub mod cli;
 pub mod compiler;
+pub mod history;
+pub mod importer;
+pub mod interpolation;
 pub mod io;
 pub mod model;
+pub mod preview;
+pub mod runner;
 pub mod syntax;
 pub mod theme;
 pub mod ui;
@@ -1,7 +1,13 @@
 mod app;
+mod cli;
 mod compiler;
+mod history;
+mod interpolation;
+mod importer;
 mod io;
 mod model;
+mod preview;
+mod runner;
 mod syntax;
 mod theme;
 mod ui;
@@ -11,6 +17,12 @@ mod widgets;
 use crate::app::AetherApp;
 
 fn main() -> eframe::Result {
+    // Subcommands run headless; without one, start the editor
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     // Define native window options
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -1,3 +1,4 @@
+use crate::compiler::CodegenContext;
 use egui::Ui;
 use proc_macro2::TokenStream;
 use serde::{Deserialize, Serialize};
@@ -37,19 +38,11 @@ impl std::fmt::Display for AssetType {
 }
 
 /// Manages all assets in a project
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AssetManager {
     pub assets: HashMap<String, Asset>, // name -> Asset
 }
 
-impl Default for AssetManager {
-    fn default() -> Self {
-        Self {
-            assets: HashMap::new(),
-        }
-    }
-}
-
 impl AssetManager {
     /// Create a new empty asset manager
     pub fn new() -> Self {
@@ -98,14 +91,306 @@ impl AssetManager {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum VariableType {
     String,
+    /// `i32` in generated code
     Integer,
     Boolean,
+    /// `f64` in generated code
     Float,
+    U8,
+    U32,
+    I64,
+    Usize,
+    F32,
+    /// RGBA color, `[f32; 4]` in generated code
+    Color,
+    /// A `Vec` of another type
+    List(Box<VariableType>),
+    /// A project-defined enum, by name
+    Enum(String),
+    /// A project-defined struct, by name
+    Struct(String),
 }
 
 impl std::fmt::Display for VariableType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::List(inner) => write!(f, "List<{}>", inner),
+            Self::Enum(name) | Self::Struct(name) => write!(f, "{}", name),
+            Self::U8 | Self::U32 | Self::I64 | Self::Usize | Self::F32 => {
+                write!(f, "{}", self.rust_name().unwrap_or_default())
+            }
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Nesting limit for default values, so a struct that contains itself can't recurse forever
+const MAX_VALUE_DEPTH: usize = 16;
+
+impl VariableType {
+    /// Scalar types that a variable's value text holds directly
+    pub fn is_scalar(&self) -> bool {
+        matches!(self, Self::String | Self::Boolean) || self.is_numeric()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Integer | Self::U8 | Self::U32 | Self::I64 | Self::Usize)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::Float | Self::F32)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// Name of the Rust primitive used for this type, if it is one
+    pub fn rust_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Integer => Some("i32"),
+            Self::U8 => Some("u8"),
+            Self::U32 => Some("u32"),
+            Self::I64 => Some("i64"),
+            Self::Usize => Some("usize"),
+            Self::Float => Some("f64"),
+            Self::F32 => Some("f32"),
+            Self::Boolean => Some("bool"),
+            _ => None,
+        }
+    }
+
+    /// Smallest and largest value of an integer type.
+    /// `usize` is assumed to be 64 bits, like the targets the generated apps build for.
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        match self {
+            Self::Integer => Some((i32::MIN.into(), i32::MAX.into())),
+            Self::U8 => Some((0, u8::MAX.into())),
+            Self::U32 => Some((0, u32::MAX.into())),
+            Self::I64 => Some((i64::MIN.into(), i64::MAX.into())),
+            Self::Usize => Some((0, u64::MAX.into())),
+            _ => None,
+        }
+    }
+
+    /// Whether the generated Rust type is `Copy` (project enums derive it)
+    pub fn is_copy(&self) -> bool {
+        !matches!(self, Self::String | Self::List(_) | Self::Struct(_))
+    }
+
+    /// Whether the generated code can show the value with `to_string()`
+    /// (the rest are shown with their `Debug` representation)
+    pub fn is_displayable(&self) -> bool {
+        self.is_scalar() || matches!(self, Self::Enum(_))
+    }
+
+    /// Value text of a new variable of this type
+    pub fn default_value(&self, types: &[CustomType]) -> String {
+        self.value_text(&self.default_json(types))
+    }
+
+    /// Structured form of a value: scalars are JSON strings, numbers and booleans,
+    /// colors are `"#rrggbbaa"` strings, enums are variant names, lists are arrays
+    /// and structs are objects keyed by field name.
+    pub fn default_json(&self, types: &[CustomType]) -> serde_json::Value {
+        self.default_json_at(types, 0)
+    }
+
+    fn default_json_at(&self, types: &[CustomType], depth: usize) -> serde_json::Value {
+        use serde_json::Value;
+        match self {
+            Self::String => Value::String(String::new()),
+            Self::Integer | Self::U8 | Self::U32 | Self::I64 | Self::Usize => Value::from(0),
+            Self::Float | Self::F32 => Value::from(0.0),
+            Self::Boolean => Value::Bool(false),
+            Self::Color => Value::String(format_color([255, 255, 255, 255])),
+            Self::List(_) => Value::Array(Vec::new()),
+            Self::Enum(name) => match find_type(types, name).map(|t| &t.kind) {
+                Some(CustomTypeKind::Enum { variants }) => {
+                    Value::String(variants.first().cloned().unwrap_or_default())
+                }
+                _ => Value::Null,
+            },
+            Self::Struct(name) => match find_type(types, name).map(|t| &t.kind) {
+                Some(CustomTypeKind::Struct { fields }) if depth < MAX_VALUE_DEPTH => Value::Object(
+                    fields
+                        .iter()
+                        .map(|f| (f.name.clone(), f.v_type.default_json_at(types, depth + 1)))
+                        .collect(),
+                ),
+                _ => Value::Null,
+            },
+        }
+    }
+
+    /// Parse a variable's value text. Scalars, colors and enum variants are written as is;
+    /// lists and structs are written as JSON.
+    pub fn parse_value(&self, text: &str, types: &[CustomType]) -> Result<serde_json::Value, String> {
+        use serde_json::Value;
+        let value = match self {
+            Self::String => Value::String(text.to_string()),
+            Self::Integer | Self::U8 | Self::U32 | Self::I64 | Self::Usize => {
+                let n = text.trim().parse::<i128>().map_err(|_| format!("'{}' is not an integer", text))?;
+                // Narrower ranges are checked by `check_json` below
+                u64::try_from(n)
+                    .map(Value::from)
+                    .or_else(|_| i64::try_from(n).map(Value::from))
+                    .map_err(|_| format!("{} is out of range for {}", n, self.rust_name().unwrap_or_default()))?
+            }
+            Self::Float | Self::F32 => Value::from(
+                text.trim().parse::<f64>().map_err(|_| format!("'{}' is not a number", text))?,
+            ),
+            Self::Boolean => Value::Bool(
+                text.trim().parse::<bool>().map_err(|_| format!("'{}' is not true or false", text))?,
+            ),
+            Self::Color | Self::Enum(_) => Value::String(text.trim().to_string()),
+            Self::List(_) | Self::Struct(_) => {
+                serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))?
+            }
+        };
+        self.check_json(&value, types)?;
+        Ok(value)
+    }
+
+    /// Value text for a structured value, the inverse of `parse_value`
+    pub fn value_text(&self, value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Check that a structured value matches this type
+    pub fn check_json(&self, value: &serde_json::Value, types: &[CustomType]) -> Result<(), String> {
+        use serde_json::Value;
+        match (self, value) {
+            (Self::String, Value::String(_)) | (Self::Boolean, Value::Bool(_)) => Ok(()),
+            (integer, Value::Number(n)) if integer.is_integer() => {
+                let (min, max) = integer.integer_range().unwrap_or_default();
+                let value = n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from));
+                match value {
+                    Some(value) if (min..=max).contains(&value) => Ok(()),
+                    Some(value) => Err(format!(
+                        "{} is out of range for {} ({}..={})",
+                        value,
+                        integer.rust_name().unwrap_or_default(),
+                        min,
+                        max
+                    )),
+                    None => Err(format!("{} is not an integer", n)),
+                }
+            }
+            (Self::F32, Value::Number(n)) if n.as_f64().is_some_and(|v| v.abs() > f32::MAX as f64) => {
+                Err(format!("{} is out of range for f32", n))
+            }
+            (float, Value::Number(_)) if float.is_float() => Ok(()),
+            (Self::Color, Value::String(s)) => parse_color(s)
+                .map(|_| ())
+                .ok_or_else(|| format!("'{}' is not a #rrggbb or #rrggbbaa color", s)),
+            (Self::List(inner), Value::Array(items)) => {
+                items.iter().try_for_each(|item| inner.check_json(item, types))
+            }
+            (Self::Enum(name), Value::String(variant)) => match find_type(types, name).map(|t| &t.kind) {
+                Some(CustomTypeKind::Enum { variants }) if variants.contains(variant) => Ok(()),
+                Some(CustomTypeKind::Enum { .. }) => Err(format!("'{}' is not a variant of {}", variant, name)),
+                _ => Err(format!("unknown enum '{}'", name)),
+            },
+            (Self::Struct(name), Value::Object(map)) => match find_type(types, name).map(|t| &t.kind) {
+                Some(CustomTypeKind::Struct { fields }) => {
+                    if let Some(key) = map.keys().find(|key| !fields.iter().any(|f| &f.name == *key)) {
+                        return Err(format!("{} has no field '{}'", name, key));
+                    }
+                    // Missing fields take their type's default value
+                    fields.iter().try_for_each(|field| match map.get(&field.name) {
+                        Some(value) => field
+                            .v_type
+                            .check_json(value, types)
+                            .map_err(|e| format!("{}.{}: {}", name, field.name, e)),
+                        None => Ok(()),
+                    })
+                }
+                _ => Err(format!("unknown struct '{}'", name)),
+            },
+            (expected, found) => Err(format!("expected {}, found {}", expected, found)),
+        }
+    }
+
+    /// Point references to a renamed project-defined type at its new name
+    pub fn rename_type(&mut self, old: &str, new: &str) {
+        match self {
+            Self::List(inner) => inner.rename_type(old, new),
+            Self::Enum(name) | Self::Struct(name) if name == old => *name = new.to_string(),
+            _ => {}
+        }
+    }
+}
+
+/// Look up a project-defined type by name
+pub fn find_type<'a>(types: &'a [CustomType], name: &str) -> Option<&'a CustomType> {
+    types.iter().find(|t| t.name == name)
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` color into RGBA bytes
+pub fn parse_color(text: &str) -> Option<[u8; 4]> {
+    let hex = text.trim().strip_prefix('#')?;
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { byte(6)? } else { 255 };
+    Some([byte(0)?, byte(2)?, byte(4)?, alpha])
+}
+
+/// Format RGBA bytes as `#rrggbbaa`
+pub fn format_color(rgba: [u8; 4]) -> String {
+    format!("#{:02x}{:02x}{:02x}{:02x}", rgba[0], rgba[1], rgba[2], rgba[3])
+}
+
+/// A project-defined type that variables can use
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomType {
+    pub name: String,
+    pub kind: CustomTypeKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CustomTypeKind {
+    /// A fieldless enum; the first variant is the default
+    Enum { variants: Vec<String> },
+    Struct { fields: Vec<StructField> },
+}
+
+/// A typed field of a project-defined struct
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StructField {
+    pub name: String,
+    pub v_type: VariableType,
+}
+
+impl CustomType {
+    pub fn new_enum(name: &str, variants: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: CustomTypeKind::Enum {
+                variants: variants.iter().map(|v| v.to_string()).collect(),
+            },
+        }
+    }
+
+    pub fn new_struct(name: &str, fields: Vec<StructField>) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: CustomTypeKind::Struct { fields },
+        }
+    }
+
+    /// The variable type that refers to this type
+    pub fn variable_type(&self) -> VariableType {
+        match self.kind {
+            CustomTypeKind::Enum { .. } => VariableType::Enum(self.name.clone()),
+            CustomTypeKind::Struct { .. } => VariableType::Struct(self.name.clone()),
+        }
     }
 }
 
@@ -137,43 +422,629 @@ impl std::fmt::Display for WidgetEvent {
     }
 }
 
+impl WidgetEvent {
+    /// Snake-case suffix used for generated event hook methods (e.g. `on_button_..._clicked`)
+    pub fn hook_suffix(&self) -> &'static str {
+        match self {
+            Self::Clicked => "clicked",
+            Self::Changed => "changed",
+            Self::Hovered => "hovered",
+            Self::DoubleClicked => "double_clicked",
+            Self::Focused => "focused",
+            Self::LostFocus => "lost_focus",
+        }
+    }
+}
+
+/// A widget whose state actions can change, as listed in the action editor
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionTarget {
+    Window { id: Uuid, title: String },
+    Tabs { id: Uuid, tabs: Vec<String> },
+}
+
+impl ActionTarget {
+    pub fn id(&self) -> Uuid {
+        match self {
+            ActionTarget::Window { id, .. } | ActionTarget::Tabs { id, .. } => *id,
+        }
+    }
+}
+
 /// Standard action types that can be executed on widget events
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Action {
     /// Increment a variable by 1
     IncrementVariable(String),
+    /// Decrement a variable by 1
+    DecrementVariable(String),
+    /// Add an amount (Rust code) to a number variable
+    AddToVariable(String, String),
+    /// Flip a boolean variable
+    ToggleVariable(String),
     /// Set a variable to a value
     SetVariable(String, String),
+    /// Copy the value of one variable into another of the same type
+    CopyVariable { from: String, to: String },
+    /// Append a value (Rust code) to a list variable
+    PushToList(String, String),
+    /// Remove every item from a list variable
+    ClearList(String),
+    /// Open or close a window
+    SetWindowOpen { window: Uuid, open: bool },
+    /// Switch a tab container to one of its tabs (by index)
+    SelectTab { container: Uuid, tab: usize },
+    /// Open a URL in the browser. Supports `{variable}` placeholders.
+    OpenUrl(String),
+    /// Copy text to the clipboard. Supports `{variable}` placeholders.
+    CopyToClipboard(String),
     /// Custom Rust code
     Custom(String),
+    /// Switch the app to another screen (by name)
+    NavigateTo(String),
+    /// Several actions, run in order
+    Sequence(Vec<Action>),
+    /// Run `then` if the condition holds, otherwise `otherwise`
+    If {
+        condition: Condition,
+        then: Vec<Action>,
+        otherwise: Vec<Action>,
+    },
+}
+
+/// Test of a variable for `Action::If`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Condition {
+    pub variable: String,
+    pub op: CompareOp,
+    /// Right-hand side of a comparison, as Rust code (unused for `IsTrue` / `IsFalse`)
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CompareOp {
+    IsTrue,
+    IsFalse,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    pub const ALL: [CompareOp; 8] = [
+        Self::IsTrue,
+        Self::IsFalse,
+        Self::Eq,
+        Self::Ne,
+        Self::Lt,
+        Self::Le,
+        Self::Gt,
+        Self::Ge,
+    ];
+
+    /// Whether the operator compares against a value (rather than testing a boolean)
+    pub fn takes_value(&self) -> bool {
+        !matches!(self, Self::IsTrue | Self::IsFalse)
+    }
+}
+
+impl std::fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::IsTrue => "is true",
+            Self::IsFalse => "is false",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.op.takes_value() {
+            write!(f, "{} {} {}", self.variable, self.op, self.value)
+        } else {
+            write!(f, "{} {}", self.variable, self.op)
+        }
+    }
+}
+
+impl Condition {
+    /// Numbers are written with the variable's numeric type and strings as literals;
+    /// other values are pasted as Rust code
+    pub fn to_code(&self, ctx: &CodegenContext) -> proc_macro2::TokenStream {
+        let ident = quote::format_ident!("{}", self.variable);
+        let v_type = ctx.variable_type(&self.variable);
+        let value = match (v_type, v_type.and_then(|t| self.literal_text(t))) {
+            (Some(v_type), Some(text)) if v_type.is_numeric() => {
+                crate::compiler::Compiler::numeric_literal(v_type, text.parse().unwrap_or_default())
+            }
+            (Some(_), Some(text)) => quote::quote! { #text },
+            _ => value_tokens(&self.value),
+        };
+        match self.op {
+            CompareOp::IsTrue => quote::quote! { self.#ident },
+            CompareOp::IsFalse => quote::quote! { !self.#ident },
+            CompareOp::Eq => quote::quote! { self.#ident == #value },
+            CompareOp::Ne => quote::quote! { self.#ident != #value },
+            CompareOp::Lt => quote::quote! { self.#ident < #value },
+            CompareOp::Le => quote::quote! { self.#ident <= #value },
+            CompareOp::Gt => quote::quote! { self.#ident > #value },
+            CompareOp::Ge => quote::quote! { self.#ident >= #value },
+        }
+    }
+
+    /// The compared value as value text (see `VariableType::parse_value`), for numeric
+    /// and string variables. `None` if it isn't a literal of that type.
+    fn literal_text(&self, v_type: &VariableType) -> Option<String> {
+        if v_type.is_numeric() {
+            let text = self.value.trim();
+            if text.parse::<f64>().is_ok() {
+                return Some(text.to_string());
+            }
+        } else if *v_type != VariableType::String {
+            return None;
+        }
+        literal_value(&self.value, v_type)
+    }
+
+    /// Check that the compared value fits a numeric or string variable
+    pub fn check_value(&self, v_type: &VariableType, types: &[CustomType]) -> Result<(), String> {
+        if !self.op.takes_value() || !(v_type.is_numeric() || *v_type == VariableType::String) {
+            return Ok(());
+        }
+        let text = self
+            .literal_text(v_type)
+            .ok_or_else(|| format!("condition '{}' compares with something that isn't a {}", self, v_type))?;
+        v_type
+            .parse_value(&text, types)
+            .map(|_| ())
+            .map_err(|e| format!("condition '{}': {}", self, e))
+    }
+
+    /// Evaluate against the preview's live variables, if the value is a literal
+    fn evaluate(&self, preview: &crate::preview::PreviewState) -> Result<bool, String> {
+        let var = preview
+            .variables
+            .get(&self.variable)
+            .ok_or_else(|| format!("no variable '{}'", self.variable))?;
+        if !self.op.takes_value() {
+            let flag = preview
+                .boolean(&self.variable)
+                .ok_or_else(|| format!("'{}' is not a boolean", self.variable))?;
+            return Ok(flag == (self.op == CompareOp::IsTrue));
+        }
+
+        let literal = literal_value(&self.value, &var.v_type)
+            .filter(|text| var.v_type.parse_value(text, &preview.types).is_ok())
+            .ok_or_else(|| format!("not simulated: {}", self))?;
+        let ordering = if var.v_type.is_numeric() {
+            let left: f64 = var.value.trim().parse().unwrap_or_default();
+            let right: f64 = literal.trim().parse().unwrap_or_default();
+            left.partial_cmp(&right)
+        } else {
+            let left = var.v_type.parse_value(&var.value, &preview.types).ok();
+            let right = var.v_type.parse_value(&literal, &preview.types).ok();
+            match (self.op, left == right) {
+                (CompareOp::Eq | CompareOp::Ne, equal) => {
+                    return Ok(equal == (self.op == CompareOp::Eq));
+                }
+                // Only strings order like their text
+                _ if var.v_type == VariableType::String => Some(var.value.as_str().cmp(literal.as_str())),
+                _ => return Err(format!("not simulated: {}", self)),
+            }
+        };
+        let ordering = ordering.ok_or_else(|| format!("not simulated: {}", self))?;
+        Ok(match self.op {
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+            CompareOp::IsTrue | CompareOp::IsFalse => unreachable!("handled above"),
+        })
+    }
+}
+
+/// A value written as Rust code, or as a string literal if it isn't valid Rust
+fn value_tokens(value: &str) -> proc_macro2::TokenStream {
+    match value.parse::<proc_macro2::TokenStream>() {
+        Ok(tokens) => tokens,
+        Err(_) => quote::quote! { #value.to_string() },
+    }
+}
+
+/// Load a widget's events. Projects saved when an event held a single action
+/// get that action as a one-item list.
+pub fn deserialize_events<'de, D>(deserializer: D) -> Result<HashMap<WidgetEvent, Vec<Action>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Actions {
+        List(Vec<Action>),
+        Single(Action),
+    }
+
+    let events = HashMap::<WidgetEvent, Actions>::deserialize(deserializer)?;
+    Ok(events
+        .into_iter()
+        .map(|(event, actions)| match actions {
+            Actions::List(actions) => (event, actions),
+            Actions::Single(action) => (event, vec![action]),
+        })
+        .collect())
 }
 
 impl Action {
+    /// Call `f` on this action and every action nested in it
+    pub fn visit(&self, f: &mut dyn FnMut(&Action)) {
+        f(self);
+        match self {
+            Action::Sequence(actions) => actions.iter().for_each(|action| action.visit(f)),
+            Action::If { then, otherwise, .. } => then.iter().chain(otherwise).for_each(|action| action.visit(f)),
+            _ => {}
+        }
+    }
+
+    /// Call `f` on this action and every action nested in it, allowing changes
+    pub fn visit_mut(&mut self, f: &mut dyn FnMut(&mut Action)) {
+        f(self);
+        match self {
+            Action::Sequence(actions) => actions.iter_mut().for_each(|action| action.visit_mut(f)),
+            Action::If { then, otherwise, .. } => {
+                then.iter_mut().chain(otherwise).for_each(|action| action.visit_mut(f))
+            }
+            _ => {}
+        }
+    }
+
     /// Convert the action to Rust code
-    pub fn to_code(&self) -> proc_macro2::TokenStream {
+    pub fn to_code(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         match self {
             Action::IncrementVariable(var_name) => {
                 let ident = quote::format_ident!("{}", var_name);
                 quote::quote! { self.#ident += 1; }
             }
+            Action::DecrementVariable(var_name) => {
+                let ident = quote::format_ident!("{}", var_name);
+                quote::quote! { self.#ident -= 1; }
+            }
+            Action::AddToVariable(var_name, amount) => {
+                let ident = quote::format_ident!("{}", var_name);
+                let amount = value_tokens(amount);
+                quote::quote! { self.#ident += #amount; }
+            }
+            Action::ToggleVariable(var_name) => {
+                let ident = quote::format_ident!("{}", var_name);
+                quote::quote! { self.#ident = !self.#ident; }
+            }
             Action::SetVariable(var_name, value) => {
                 let ident = quote::format_ident!("{}", var_name);
-                // Try to parse the value as a TokenStream, fallback to string literal
-                match value.parse::<proc_macro2::TokenStream>() {
-                    Ok(tokens) => quote::quote! { self.#ident = #tokens; },
-                    Err(_) => quote::quote! { self.#ident = #value.to_string(); },
+                let value = value_tokens(value);
+                quote::quote! { self.#ident = #value; }
+            }
+            Action::CopyVariable { from, to } => {
+                let from_ident = quote::format_ident!("{}", from);
+                let to_ident = quote::format_ident!("{}", to);
+                if ctx.variable_type(from).is_some_and(|t| t.is_copy()) {
+                    quote::quote! { self.#to_ident = self.#from_ident; }
+                } else {
+                    quote::quote! { self.#to_ident = self.#from_ident.clone(); }
                 }
             }
+            Action::PushToList(var_name, value) => {
+                let ident = quote::format_ident!("{}", var_name);
+                let value = value_tokens(value);
+                quote::quote! { self.#ident.push(#value); }
+            }
+            Action::ClearList(var_name) => {
+                let ident = quote::format_ident!("{}", var_name);
+                quote::quote! { self.#ident.clear(); }
+            }
+            Action::SetWindowOpen { window, open } => {
+                let field = ctx.state_field(&crate::widgets::WindowWidget::open_field_name(*window));
+                quote::quote! { self.#field = #open; }
+            }
+            Action::SelectTab { container, tab } => {
+                let field = ctx.state_field(&crate::widgets::TabContainerWidget::selected_field_name(*container));
+                let tab = proc_macro2::Literal::usize_unsuffixed(*tab);
+                quote::quote! { self.#field = #tab; }
+            }
+            Action::OpenUrl(url) => {
+                let url = ctx.text_expr(url);
+                quote::quote! { ui.ctx().open_url(egui::OpenUrl::new_tab(#url)); }
+            }
+            Action::CopyToClipboard(text) => {
+                let text = ctx.owned_text_expr(text);
+                quote::quote! { ui.ctx().copy_text(#text); }
+            }
             Action::Custom(code) => {
                 match code.parse::<proc_macro2::TokenStream>() {
                     Ok(tokens) => tokens,
                     Err(_) => quote::quote! { /* Invalid Rust code */ },
                 }
             }
+            Action::NavigateTo(screen) => ctx.navigate_code(screen),
+            Action::Sequence(actions) => {
+                let code = actions.iter().map(|action| action.to_code(ctx));
+                quote::quote! { #(#code)* }
+            }
+            Action::If { condition, then, otherwise } => {
+                let condition = condition.to_code(ctx);
+                let then: Vec<_> = then.iter().map(|action| action.to_code(ctx)).collect();
+                if otherwise.is_empty() {
+                    return quote::quote! { if #condition { #(#then)* } };
+                }
+                let otherwise: Vec<_> = otherwise.iter().map(|action| action.to_code(ctx)).collect();
+                quote::quote! {
+                    if #condition { #(#then)* } else { #(#otherwise)* }
+                }
+            }
+        }
+    }
+
+    /// Interpret the action against a canvas preview instead of generating code.
+    /// Returns a description of what happened for the preview log.
+    pub fn simulate(&self, preview: &mut crate::preview::PreviewState) -> String {
+        match self {
+            Action::IncrementVariable(var_name) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                // `+= 1` only compiles for integers
+                let Some((_, max)) = var.v_type.integer_range() else {
+                    return format!("cannot increment {} variable '{}'", var.v_type, var_name);
+                };
+                match var.value.trim().parse::<i128>() {
+                    // The generated app panics on overflow in debug builds
+                    Ok(value) if value >= max => format!("{} would overflow {}", var_name, var.v_type),
+                    Ok(value) => {
+                        var.value = (value + 1).to_string();
+                        format!("{} = {}", var_name, var.value)
+                    }
+                    Err(_) => format!("'{}' holds no integer", var_name),
+                }
+            }
+            Action::DecrementVariable(var_name) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                let Some((min, _)) = var.v_type.integer_range() else {
+                    return format!("cannot decrement {} variable '{}'", var.v_type, var_name);
+                };
+                match var.value.trim().parse::<i128>() {
+                    Ok(value) if value <= min => format!("{} would overflow {}", var_name, var.v_type),
+                    Ok(value) => {
+                        var.value = (value - 1).to_string();
+                        format!("{} = {}", var_name, var.value)
+                    }
+                    Err(_) => format!("'{}' holds no integer", var_name),
+                }
+            }
+            Action::AddToVariable(var_name, amount) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                if !var.v_type.is_numeric() {
+                    return format!("cannot add to {} variable '{}'", var.v_type, var_name);
+                }
+                let Some(amount) = literal_value(amount, &var.v_type) else {
+                    return format!("not simulated: {} += {}", var_name, amount);
+                };
+                let sum = match var.v_type.integer_range() {
+                    Some((min, max)) => {
+                        let value: i128 = var.value.trim().parse().unwrap_or_default();
+                        match amount.parse::<i128>().ok().and_then(|amount| value.checked_add(amount)) {
+                            Some(sum) if (min..=max).contains(&sum) => sum.to_string(),
+                            _ => return format!("{} would overflow {}", var_name, var.v_type),
+                        }
+                    }
+                    None => {
+                        let value: f64 = var.value.trim().parse().unwrap_or_default();
+                        let sum = value + amount.parse::<f64>().unwrap_or_default();
+                        if var.v_type == VariableType::F32 {
+                            (sum as f32).to_string()
+                        } else {
+                            sum.to_string()
+                        }
+                    }
+                };
+                var.value = sum;
+                format!("{} = {}", var_name, var.value)
+            }
+            Action::ToggleVariable(var_name) => match preview.boolean(var_name) {
+                Some(flag) => {
+                    preview.set_value(var_name, (!flag).to_string());
+                    format!("{} = {}", var_name, !flag)
+                }
+                None if preview.variables.contains_key(var_name) => {
+                    format!("cannot toggle non-boolean variable '{}'", var_name)
+                }
+                None => format!("no variable '{}'", var_name),
+            },
+            Action::CopyVariable { from, to } => {
+                let (Some(source), Some(target)) = (preview.variables.get(from), preview.variables.get(to)) else {
+                    return format!("no variable '{}'", if preview.variables.contains_key(from) { to } else { from });
+                };
+                if source.v_type != target.v_type {
+                    return format!("cannot copy {} '{}' into {} '{}'", source.v_type, from, target.v_type, to);
+                }
+                let value = source.value.clone();
+                preview.set_value(to, value);
+                format!("{} = {}", to, preview.variables[to].value)
+            }
+            Action::PushToList(var_name, value) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                let VariableType::List(item_type) = &var.v_type else {
+                    return format!("cannot push to {} variable '{}'", var.v_type, var_name);
+                };
+                let item = literal_value(value, item_type).and_then(|text| item_type.parse_value(&text, &preview.types).ok());
+                let list = var.v_type.parse_value(&var.value, &preview.types).ok();
+                match (item, list) {
+                    (Some(item), Some(serde_json::Value::Array(mut items))) => {
+                        items.push(item);
+                        var.value = var.v_type.value_text(&serde_json::Value::Array(items));
+                        format!("{} = {}", var_name, var.value)
+                    }
+                    _ => format!("not simulated: {}.push({})", var_name, value),
+                }
+            }
+            Action::ClearList(var_name) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                if !matches!(var.v_type, VariableType::List(_)) {
+                    return format!("cannot clear {} variable '{}'", var.v_type, var_name);
+                }
+                var.value = "[]".to_string();
+                format!("{} = []", var_name)
+            }
+            Action::SetWindowOpen { window, open } => {
+                preview.set_widget_value(*window, *open);
+                format!("{} window", if *open { "opened" } else { "closed" })
+            }
+            Action::SelectTab { container, tab } => {
+                preview.set_widget_value(*container, *tab);
+                format!("selected tab {}", tab + 1)
+            }
+            Action::OpenUrl(url) => {
+                let url = preview.interpolate(url);
+                preview.request(crate::preview::PreviewRequest::OpenUrl(url.clone()));
+                format!("opened {}", url)
+            }
+            Action::CopyToClipboard(text) => {
+                let text = preview.interpolate(text);
+                preview.request(crate::preview::PreviewRequest::CopyText(text.clone()));
+                format!("copied '{}'", text)
+            }
+            Action::SetVariable(var_name, value) => {
+                let Some(var) = preview.variables.get_mut(var_name) else {
+                    return format!("no variable '{}'", var_name);
+                };
+                let literal = literal_value(value, &var.v_type)
+                    .filter(|text| var.v_type.parse_value(text, &preview.types).is_ok());
+                match literal {
+                    Some(value) => {
+                        var.value = value;
+                        format!("{} = {}", var_name, var.value)
+                    }
+                    None => format!("not simulated: {} = {}", var_name, value),
+                }
+            }
+            Action::Custom(code) => format!("not simulated: {}", code.trim()),
+            Action::NavigateTo(screen) => {
+                if preview.navigate(screen) {
+                    format!("navigated to '{}'", screen)
+                } else {
+                    format!("no screen '{}'", screen)
+                }
+            }
+            Action::Sequence(actions) => Self::simulate_all(actions, preview),
+            Action::If { condition, then, otherwise } => match condition.evaluate(preview) {
+                Ok(true) => format!("if {}: {}", condition, Self::simulate_all(then, preview)),
+                Ok(false) if otherwise.is_empty() => format!("if {}: false", condition),
+                Ok(false) => format!("if {}: else {}", condition, Self::simulate_all(otherwise, preview)),
+                Err(e) => e,
+            },
+        }
+    }
+
+    /// Simulate actions in order, describing them together
+    pub fn simulate_all(actions: &[Action], preview: &mut crate::preview::PreviewState) -> String {
+        if actions.is_empty() {
+            return "nothing to do".to_string();
+        }
+        let outcomes: Vec<String> = actions.iter().map(|action| action.simulate(preview)).collect();
+        outcomes.join("; ")
+    }
+}
+
+/// Evaluate the right-hand side of a `SetVariable` action if it is a plain literal of the
+/// variable's type, e.g. `42`, `true`, `Mode::Dark` or `"text".to_string()`. Text that isn't valid Rust
+/// is assigned as a string, like the generated code does.
+fn literal_value(value: &str, v_type: &VariableType) -> Option<String> {
+    if value.parse::<proc_macro2::TokenStream>().is_err() {
+        return (*v_type == VariableType::String).then(|| value.to_string());
+    }
+
+    let mut expr = syn::parse_str::<syn::Expr>(value).ok()?;
+    // Unwrap `"..".to_string()` / `"..".into()` / `String::from("..")`
+    loop {
+        expr = match expr {
+            syn::Expr::MethodCall(call) if call.args.is_empty()
+                && matches!(call.method.to_string().as_str(), "to_string" | "to_owned" | "into") =>
+            {
+                *call.receiver
+            }
+            syn::Expr::Call(call) if call.args.len() == 1 && is_string_from(&call.func) => {
+                call.args.into_iter().next()?
+            }
+            syn::Expr::Paren(paren) => *paren.expr,
+            other => break expr_literal(&other, v_type),
+        };
+    }
+}
+
+fn is_string_from(func: &syn::Expr) -> bool {
+    let syn::Expr::Path(path) = func else {
+        return false;
+    };
+    let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    segments == ["String", "from"]
+}
+
+fn expr_literal(expr: &syn::Expr, v_type: &VariableType) -> Option<String> {
+    let (negative, lit) = match expr {
+        // `Enum::Variant`
+        syn::Expr::Path(path) => {
+            let VariableType::Enum(name) = v_type else {
+                return None;
+            };
+            return match path.path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>().as_slice() {
+                [ty, variant] if ty == name => Some(variant.clone()),
+                _ => None,
+            };
+        }
+        syn::Expr::Lit(lit) => (false, &lit.lit),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => match &**expr {
+            syn::Expr::Lit(lit) => (true, &lit.lit),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let sign = if negative { "-" } else { "" };
+    match (lit, v_type) {
+        (syn::Lit::Str(s), VariableType::String) if !negative => Some(s.value()),
+        (syn::Lit::Bool(b), VariableType::Boolean) if !negative => Some(b.value.to_string()),
+        // A suffix must name the variable's own type, e.g. `5u8` for a `u8`
+        (syn::Lit::Int(i), integer) if integer.is_integer() && suffix_matches(i.suffix(), integer) => {
+            format!("{}{}", sign, i.base10_digits()).parse::<i128>().ok().map(|v| v.to_string())
+        }
+        (syn::Lit::Float(f), float) if float.is_float() && suffix_matches(f.suffix(), float) => {
+            format!("{}{}", sign, f.base10_digits()).parse::<f64>().ok().map(|v| v.to_string())
         }
+        _ => None,
     }
 }
 
+fn suffix_matches(suffix: &str, v_type: &VariableType) -> bool {
+    suffix.is_empty() || v_type.rust_name() == Some(suffix)
+}
+
 /// A variable in the global application state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Variable {
@@ -182,11 +1053,436 @@ pub struct Variable {
     pub value: String, // Stored as string for simplicity in prototype
 }
 
-/// The contract for any element that can exist in the designer.
-/// Uses typetag to allow for polymorphic serialization of trait objects.
+/// A crate that generated code depends on, merged into the exported Cargo.toml.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateDependency {
+    pub name: String,
+    pub version: String,
+    pub features: Vec<String>,
+}
+
+impl CrateDependency {
+    pub fn new(name: &str, version: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            features: Vec::new(),
+        }
+    }
+
+    /// Add cargo features to enable for this crate
+    pub fn with_features(mut self, features: &[&str]) -> Self {
+        self.features.extend(features.iter().map(|f| f.to_string()));
+        self
+    }
+}
+
+/// Tooltip, enabled state and visibility, which every widget has.
+/// The compiler and preview apply them around the widget's own code.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CommonProperties {
+    /// Shown on hover. Supports `{variable}` placeholders.
+    pub tooltip: String,
+    pub enabled: Flag,
+    pub visible: Flag,
+}
+
+impl Default for CommonProperties {
+    fn default() -> Self {
+        Self {
+            tooltip: String::new(),
+            enabled: Flag::Static(true),
+            visible: Flag::Static(true),
+        }
+    }
+}
+
+impl CommonProperties {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A yes/no property that is either fixed or follows a boolean variable
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Flag {
+    Static(bool),
+    Bound(String),
+}
+
+impl Flag {
+    /// Whether the flag is fixed to `value`
+    pub fn is(&self, value: bool) -> bool {
+        *self == Flag::Static(value)
+    }
+
+    /// The flag as a `bool` expression in generated code
+    pub fn to_code(&self) -> TokenStream {
+        match self {
+            Flag::Static(value) => quote::quote! { #value },
+            Flag::Bound(var_name) => {
+                let ident = quote::format_ident!("{}", var_name);
+                quote::quote! { self.#ident }
+            }
+        }
+    }
+
+    /// Current value in the preview. Flags bound to a missing variable count as true.
+    pub fn evaluate(&self, preview: &crate::preview::PreviewState) -> bool {
+        match self {
+            Flag::Static(value) => *value,
+            Flag::Bound(var_name) => preview.boolean(var_name).unwrap_or(true),
+        }
+    }
+}
+
+/// The type of a widget property, which decides how it is edited
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyKind {
+    /// One line of text. `placeholders` if it may contain `{variable}` placeholders.
+    Text { placeholders: bool },
+    /// Rust source, edited as code
+    Code,
+    Bool,
+    Integer { min: i64, max: i64 },
+    Float { min: f64, max: f64, speed: f64 },
+    /// A size that may be left to egui. `initial` is used when it is first set.
+    OptionalFloat { min: f64, max: f64, initial: f64 },
+    /// RGBA, each channel 0.0 to 1.0
+    Color,
+    /// Index into a fixed list of options
+    Choice(&'static [&'static str]),
+}
+
+impl PropertyKind {
+    /// Whether values of both kinds are interchangeable. Ranges may differ.
+    pub fn same_type(&self, other: &PropertyKind) -> bool {
+        match (self, other) {
+            (PropertyKind::Choice(a), PropertyKind::Choice(b)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    /// The value if it fits this kind, clamped to its range
+    pub fn normalize(&self, value: PropertyValue) -> Option<PropertyValue> {
+        use PropertyValue as V;
+        match (self, value) {
+            (PropertyKind::Text { .. } | PropertyKind::Code, value @ V::Text(_))
+            | (PropertyKind::Bool, value @ V::Bool(_))
+            | (PropertyKind::Color, value @ V::Color(_)) => Some(value),
+            (PropertyKind::Integer { min, max }, V::Integer(n)) => Some(V::Integer(n.clamp(*min, *max))),
+            (PropertyKind::Float { min, max, .. }, V::Float(n)) => Some(V::Float(n.clamp(*min, *max))),
+            (PropertyKind::OptionalFloat { min, max, .. }, V::OptionalFloat(n)) => {
+                Some(V::OptionalFloat(n.map(|n| n.clamp(*min, *max))))
+            }
+            (PropertyKind::Choice(options), V::Choice(index)) if index < options.len() => Some(V::Choice(index)),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a widget property
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Text(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    OptionalFloat(Option<f64>),
+    Color([f32; 4]),
+    Choice(usize),
+}
+
+impl PropertyValue {
+    /// Store the value in a widget field of the matching type. Returns false if the types differ.
+    pub fn assign<T: TryFrom<PropertyValue>>(self, field: &mut T) -> bool {
+        match T::try_from(self) {
+            Ok(value) => {
+                *field = value;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyValue::Text(text) => write!(f, "{:?}", text),
+            PropertyValue::Bool(value) => write!(f, "{}", value),
+            PropertyValue::Integer(n) => write!(f, "{}", n),
+            PropertyValue::Float(n) => write!(f, "{}", n),
+            PropertyValue::OptionalFloat(Some(n)) => write!(f, "{}", n),
+            PropertyValue::OptionalFloat(None) => write!(f, "auto"),
+            PropertyValue::Color(rgba) => write!(f, "{}", format_color(rgba.map(|c| (c * 255.0).round() as u8))),
+            PropertyValue::Choice(index) => write!(f, "#{}", index),
+        }
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::Text(value)
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::Text(value.to_string())
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+impl From<usize> for PropertyValue {
+    fn from(value: usize) -> Self {
+        PropertyValue::Integer(value as i64)
+    }
+}
+
+impl From<f32> for PropertyValue {
+    fn from(value: f32) -> Self {
+        PropertyValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for PropertyValue {
+    fn from(value: f64) -> Self {
+        PropertyValue::Float(value)
+    }
+}
+
+impl From<Option<f32>> for PropertyValue {
+    fn from(value: Option<f32>) -> Self {
+        PropertyValue::OptionalFloat(value.map(f64::from))
+    }
+}
+
+impl From<[f32; 4]> for PropertyValue {
+    fn from(value: [f32; 4]) -> Self {
+        PropertyValue::Color(value)
+    }
+}
+
+impl TryFrom<PropertyValue> for String {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Text(text) => Ok(text),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for bool {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Bool(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for usize {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Integer(n) if n >= 0 => Ok(n as usize),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for f32 {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Float(n) => Ok(n as f32),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for f64 {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Float(n) => Ok(n),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for Option<f32> {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::OptionalFloat(n) => Ok(n.map(|n| n as f32)),
+            other => Err(other),
+        }
+    }
+}
+
+impl TryFrom<PropertyValue> for [f32; 4] {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Color(rgba) => Ok(rgba),
+            other => Err(other),
+        }
+    }
+}
+
+/// A property a widget exposes to editors that work on any widget, like the Inspector.
+/// `name` is also the key the property is bound under in the widget's bindings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDescriptor {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: PropertyKind,
+    /// Value of a newly created widget
+    pub default: PropertyValue,
+    /// Whether the property can follow a variable instead of its own value
+    pub bindable: bool,
+    /// Heading the property is listed under, for widgets that group their properties
+    pub group: Option<&'static str>,
+}
+
+impl PropertyDescriptor {
+    pub fn new(name: &'static str, label: &'static str, kind: PropertyKind, default: impl Into<PropertyValue>) -> Self {
+        Self {
+            name,
+            label,
+            kind,
+            default: default.into(),
+            bindable: false,
+            group: None,
+        }
+    }
+
+    pub fn bindable(mut self) -> Self {
+        self.bindable = true;
+        self
+    }
+
+    pub fn in_group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn get<W: WidgetNode + ?Sized>(&self, node: &W) -> Option<PropertyValue> {
+        node.property(self.name)
+    }
+
+    /// Set the property, clamped to its range. Returns false if the value is of the wrong type.
+    pub fn set<W: WidgetNode + ?Sized>(&self, node: &mut W, value: PropertyValue) -> bool {
+        match self.kind.normalize(value) {
+            Some(value) => node.set_property(self.name, value),
+            None => false,
+        }
+    }
+}
+
+/// Layout data a container keeps for each child besides the widget itself
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChildLayout {
+    /// Laid out in order by the container
+    #[default]
+    Flow,
+    /// At a fixed position inside the container, as in a Freeform Layout
+    Placed { x: f32, y: f32, width: Option<f32>, height: Option<f32> },
+}
+
+/// The children in one slot of a container, each with its layout data
+pub trait ChildList {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn widgets(&self) -> Vec<&dyn WidgetNode>;
+    fn widgets_mut(&mut self) -> Vec<&mut dyn WidgetNode>;
+    fn layout(&self, index: usize) -> ChildLayout;
+    fn set_layout(&mut self, index: usize, layout: ChildLayout);
+
+    /// Insert at `index`, or at the end if it is past it. Lists that position their
+    /// children give a flow child a default position; others ignore the layout.
+    fn insert(&mut self, index: usize, widget: Box<dyn WidgetNode>, layout: ChildLayout);
+    fn remove(&mut self, index: usize) -> (Box<dyn WidgetNode>, ChildLayout);
+
+    fn position(&self, id: Uuid) -> Option<usize> {
+        self.widgets().iter().position(|widget| widget.id() == id)
+    }
+}
+
+impl ChildList for Vec<Box<dyn WidgetNode>> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn widgets(&self) -> Vec<&dyn WidgetNode> {
+        self.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn WidgetNode> {
+        self.iter_mut().map(|child| child.as_mut() as &mut dyn WidgetNode).collect()
+    }
+
+    fn layout(&self, _index: usize) -> ChildLayout {
+        ChildLayout::Flow
+    }
+
+    fn set_layout(&mut self, _index: usize, _layout: ChildLayout) {}
+
+    fn insert(&mut self, index: usize, widget: Box<dyn WidgetNode>, _layout: ChildLayout) {
+        Vec::insert(self, index.min(Vec::len(self)), widget);
+    }
+
+    fn remove(&mut self, index: usize) -> (Box<dyn WidgetNode>, ChildLayout) {
+        (Vec::remove(self, index), ChildLayout::Flow)
+    }
+}
+
+/// A named group of a container's children, like one tab of a Tab Container.
+/// Containers with a single list of children have one unnamed slot.
+pub struct ChildSlot<'a> {
+    pub name: String,
+    pub children: &'a dyn ChildList,
+}
+
+pub struct ChildSlotMut<'a> {
+    pub name: String,
+    pub children: &'a mut dyn ChildList,
+}
+
+/// Where a widget sits in the tree: its parent, the parent's slot and its index in that slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChildPosition {
+    pub parent: Uuid,
+    pub slot: usize,
+    pub index: usize,
+}
+
+/// Tooltip, enabled state and visibility, shared by every widget.
+/// Widgets get this from the `widget!` macro in `widgets`.
+pub trait WidgetCommon {
+    fn common(&self) -> &CommonProperties;
+    fn common_mut(&mut self) -> &mut CommonProperties;
+}
+
+/// The contract for any element that can exist in the designer.
+/// Uses typetag to allow for polymorphic serialization of trait objects.
 /// [cite: 47, 55]
 #[typetag::serde(tag = "type")]
-pub trait WidgetNode: std::fmt::Debug {
+pub trait WidgetNode: WidgetCommon + std::fmt::Debug {
     /// Clone this widget node into a boxed trait object
     fn clone_box(&self) -> Box<dyn WidgetNode>;
     /// Distinct behavior 1: Editor Visualization
@@ -198,12 +1494,42 @@ pub trait WidgetNode: std::fmt::Debug {
     /// How the widget exposes configurable fields to the Inspector.
     /// [cite: 51, 134]
     /// known_assets: Vec of (asset_name, filename) pairs
-    fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], known_assets: &[(String, String)]);
+    /// Defaults to an editor built from `properties`.
+    fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
+        crate::widgets::inspect_properties(self, ui, known_variables);
+    }
+
+    /// Typed descriptions of the widget's editable properties.
+    /// Widgets implement this, `property` and `set_property` together with `widget_properties!`.
+    fn properties(&self) -> Vec<PropertyDescriptor> {
+        Vec::new()
+    }
+
+    /// Current value of a property listed by `properties`
+    fn property(&self, _name: &str) -> Option<PropertyValue> {
+        None
+    }
+
+    /// Change a property listed by `properties`. Returns false for unknown names and
+    /// mismatched types. Prefer `PropertyDescriptor::set`, which also clamps the value.
+    fn set_property(&mut self, _name: &str, _value: PropertyValue) -> bool {
+        false
+    }
+
+    /// Property name -> variable name, for widgets with bindable properties
+    fn bindings(&self) -> Option<&HashMap<String, String>> {
+        None
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut HashMap<String, String>> {
+        None
+    }
 
     /// Distinct behavior 3: Code Generation
     /// Synthesizes the Rust code required to instantiate this widget.
     /// [cite: 52, 184]
-    fn codegen(&self) -> TokenStream {
+    /// `ctx` lets widgets register private state fields on the generated app struct.
+    fn codegen(&self, _ctx: &mut CodegenContext) -> TokenStream {
         quote::quote! { /* Default no-op */ }
     }
 
@@ -214,13 +1540,43 @@ pub trait WidgetNode: std::fmt::Debug {
         Vec::new()
     }
 
+    /// Distinct behavior 5: Dependency Declaration
+    /// Crates (beyond egui/eframe) that this widget's generated code needs.
+    /// Children are collected separately by the compiler.
+    fn dependencies(&self) -> Vec<CrateDependency> {
+        Vec::new()
+    }
+
+    /// Distinct behavior 6: Interactive Preview
+    /// Renders the widget the way its generated code would, against the preview's
+    /// live variables, firing its events through `PreviewState::fire`.
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut crate::preview::PreviewState) {
+        ui.label(format!("{} (no preview)", self.name()));
+    }
+
     /// Unique identifier for the widget instance.
     fn id(&self) -> Uuid;
 
     /// Helper to get the display name for the Hierarchy View
     fn name(&self) -> &str;
 
-    /// Helper to get children (if container) for tree traversal
+    /// Actions configured for each event of this widget, if it supports events
+    fn events(&self) -> Option<&HashMap<WidgetEvent, Vec<Action>>> {
+        None
+    }
+
+    fn events_mut(&mut self) -> Option<&mut HashMap<WidgetEvent, Vec<Action>>> {
+        None
+    }
+
+    /// State of this widget that actions elsewhere can change (a window's open flag, the selected tab)
+    fn action_target(&self) -> Option<ActionTarget> {
+        None
+    }
+
+    /// The children of a container that keeps them in one plain list.
+    /// Containers with several slots or per-child layout data (Tab Container, Freeform Layout)
+    /// have no such list and return None. Code that walks or edits the tree goes through `slots`.
     fn children(&self) -> Option<&Vec<Box<dyn WidgetNode>>> {
         None
     }
@@ -229,15 +1585,153 @@ pub trait WidgetNode: std::fmt::Debug {
     fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn WidgetNode>>> {
         None
     }
+
+    /// Every group of children with its per-child layout data. Defaults to one
+    /// unnamed slot holding `children`; containers with tabs or positioned children override it.
+    fn slots(&self) -> Vec<ChildSlot<'_>> {
+        self.children()
+            .map(|children| vec![ChildSlot { name: String::new(), children }])
+            .unwrap_or_default()
+    }
+
+    fn slots_mut(&mut self) -> Vec<ChildSlotMut<'_>> {
+        self.children_mut()
+            .map(|children| vec![ChildSlotMut { name: String::new(), children }])
+            .unwrap_or_default()
+    }
+
+    /// Slot that widgets dropped onto the container go into, e.g. the selected tab
+    fn default_slot(&self) -> usize {
+        0
+    }
+
+    /// The children in every slot
+    fn child_widgets(&self) -> Vec<&dyn WidgetNode> {
+        self.slots().into_iter().flat_map(|slot| slot.children.widgets()).collect()
+    }
+
+    fn child_widgets_mut(&mut self) -> Vec<&mut dyn WidgetNode> {
+        self.slots_mut()
+            .into_iter()
+            .flat_map(|slot| {
+                let ChildSlotMut { children, .. } = slot;
+                children.widgets_mut()
+            })
+            .collect()
+    }
+}
+
+/// What the compiler produces on export.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ExportMode {
+    /// A runnable eframe app with `main.rs` and `Cargo.toml`
+    #[default]
+    App,
+    /// A state struct with `fn ui(&mut self, ui: &mut egui::Ui)`
+    ComponentUi,
+    /// A state struct implementing `egui::Widget` for `&mut Self`
+    ComponentWidget,
+}
+
+impl std::fmt::Display for ExportMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::App => "eframe App",
+                Self::ComponentUi => "Component (fn ui)",
+                Self::ComponentWidget => "Component (impl Widget)",
+            }
+        )
+    }
+}
+
+/// Export configuration, edited in the Project Settings window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSettings {
+    pub mode: ExportMode,
+    /// Name of the generated struct in component mode
+    pub struct_name: String,
+    /// Module path of the component relative to `src/`, e.g. `panels::settings`
+    pub module_path: String,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            mode: ExportMode::App,
+            struct_name: "MyComponent".to_string(),
+            module_path: "my_component".to_string(),
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn is_component(&self) -> bool {
+        self.mode != ExportMode::App
+    }
+
+    /// Struct name to generate, falling back to the default if it isn't a valid identifier
+    pub fn struct_ident(&self) -> proc_macro2::Ident {
+        let name = self.struct_name.trim();
+        match syn::parse_str::<proc_macro2::Ident>(name) {
+            Ok(ident) => ident,
+            Err(_) => quote::format_ident!("{}", Self::default().struct_name),
+        }
+    }
+
+    /// Module path split into valid identifiers, falling back to the default
+    pub fn module_segments(&self) -> Vec<String> {
+        let segments: Vec<String> = self.module_path.split("::").map(|s| s.trim().to_string()).collect();
+        if segments.iter().all(|s| syn::parse_str::<proc_macro2::Ident>(s).is_ok()) {
+            segments
+        } else {
+            vec![Self::default().module_path]
+        }
+    }
+}
+
+/// A named page of the app with its own widget tree.
+#[derive(Serialize, Deserialize)]
+pub struct Screen {
+    pub id: Uuid,
+    pub name: String,
+    pub root: Box<dyn WidgetNode>,
+}
+
+impl Clone for Screen {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            name: self.name.clone(),
+            root: self.root.clone_box(),
+        }
+    }
+}
+
+impl Screen {
+    pub fn new(name: &str, root: Box<dyn WidgetNode>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            root,
+        }
+    }
 }
 
 /// The root container for the entire application definition.
 /// [cite: 61]
 #[derive(Serialize, Deserialize)]
+#[serde(from = "ProjectStateFile")]
 pub struct ProjectState {
-    /// The entry point of the UI tree.
+    /// The screens of the app. There is always at least one; the first is shown on startup.
+    ///
+    pub screens: Vec<Screen>,
+
+    /// Index of the screen being edited in the canvas and hierarchy.
     ///
-    pub root_node: Box<dyn WidgetNode>,
+    pub active_screen: usize,
 
     /// Selection set for gizmos and inspector.
     ///
@@ -247,6 +1741,11 @@ pub struct ProjectState {
     ///
     pub variables: HashMap<String, Variable>,
 
+    /// Enums and structs defined by the project, for use as variable types.
+    ///
+    #[serde(default)]
+    pub types: Vec<CustomType>,
+
     /// Project name used for code generation.
     ///
     #[serde(default = "default_project_name")]
@@ -255,27 +1754,109 @@ pub struct ProjectState {
     /// Asset manager for images and other resources.
     ///
     #[serde(default)]
-    pub assets: AssetManager,
+    pub assets: AssetManager,
+
+    /// Whether to export a full app or an embeddable component.
+    ///
+    #[serde(default)]
+    pub export: ExportSettings,
 
     /// Pending reorder operation (source_id, target_id).
     /// Not serialized - runtime only.
     #[serde(skip)]
     pub pending_reorder: Option<(Uuid, Uuid)>,
+
+    /// Pending property change on the selected widgets, applied as one undo step.
+    /// Not serialized - runtime only.
+    #[serde(skip)]
+    pub pending_property_edit: Option<PropertyEdit>,
+}
+
+/// A property several widgets have in common
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedProperty {
+    /// The first widget's descriptor; ranges may differ on the others
+    pub descriptor: PropertyDescriptor,
+    /// The first widget's value
+    pub value: PropertyValue,
+    /// Whether the widgets have different values
+    pub mixed: bool,
+}
+
+/// One property set to the same value on several widgets
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyEdit {
+    pub widgets: Vec<Uuid>,
+    pub property: String,
+    pub value: PropertyValue,
 }
 
 fn default_project_name() -> String {
     "my_app".to_string()
 }
 
+/// On-disk form of `ProjectState`.
+/// Projects saved before screens existed have a single `root_node` instead of `screens`.
+#[derive(Deserialize)]
+struct ProjectStateFile {
+    #[serde(default)]
+    screens: Vec<Screen>,
+    #[serde(default)]
+    active_screen: usize,
+    #[serde(default)]
+    root_node: Option<Box<dyn WidgetNode>>,
+    #[serde(default)]
+    selection: HashSet<Uuid>,
+    #[serde(default)]
+    variables: HashMap<String, Variable>,
+    #[serde(default)]
+    types: Vec<CustomType>,
+    #[serde(default = "default_project_name")]
+    project_name: String,
+    #[serde(default)]
+    assets: AssetManager,
+    #[serde(default)]
+    export: ExportSettings,
+}
+
+impl From<ProjectStateFile> for ProjectState {
+    fn from(file: ProjectStateFile) -> Self {
+        let mut screens = file.screens;
+        if let Some(root) = file.root_node {
+            screens.insert(0, Screen::new("Main", root));
+        }
+        if screens.is_empty() {
+            screens.push(Screen::new("Main", Box::new(crate::widgets::VerticalLayout::default())));
+        }
+
+        Self {
+            active_screen: file.active_screen.min(screens.len() - 1),
+            screens,
+            selection: file.selection,
+            variables: file.variables,
+            types: file.types,
+            project_name: file.project_name,
+            assets: file.assets,
+            export: file.export,
+            pending_reorder: None,
+            pending_property_edit: None,
+        }
+    }
+}
+
 impl Clone for ProjectState {
     fn clone(&self) -> Self {
         Self {
-            root_node: self.root_node.clone_box(),
+            screens: self.screens.clone(),
+            active_screen: self.active_screen,
             selection: self.selection.clone(),
             variables: self.variables.clone(),
+            types: self.types.clone(),
             project_name: self.project_name.clone(),
             assets: self.assets.clone(),
+            export: self.export.clone(),
             pending_reorder: None, // Reset pending operations on clone
+            pending_property_edit: None,
         }
     }
 }
@@ -283,12 +1864,16 @@ impl Clone for ProjectState {
 impl ProjectState {
     pub fn new(root: Box<dyn WidgetNode>) -> Self {
         Self {
-            root_node: root,
+            screens: vec![Screen::new("Main", root)],
+            active_screen: 0,
             selection: HashSet::new(),
             variables: HashMap::new(),
+            types: Vec::new(),
             project_name: default_project_name(),
             assets: AssetManager::new(),
+            export: ExportSettings::default(),
             pending_reorder: None,
+            pending_property_edit: None,
         }
     }
 
@@ -320,7 +1905,7 @@ impl ProjectState {
         };
         increment_button.events.insert(
             crate::model::WidgetEvent::Clicked,
-            crate::model::Action::IncrementVariable("counter".to_string()),
+            vec![crate::model::Action::IncrementVariable("counter".to_string())],
         );
         root.children.push(Box::new(increment_button));
 
@@ -464,350 +2049,690 @@ impl ProjectState {
         serde_json::to_string_pretty(self).unwrap_or_default()
     }
 
+    /// Root widget of the screen being edited
+    pub fn root(&self) -> &dyn WidgetNode {
+        self.screens[self.active_screen].root.as_ref()
+    }
+
+    /// Mutable root widget of the screen being edited
+    pub fn root_mut(&mut self) -> &mut Box<dyn WidgetNode> {
+        &mut self.screens[self.active_screen].root
+    }
+
+    /// Switch the canvas and hierarchy to another screen
+    pub fn set_active_screen(&mut self, index: usize) {
+        if index < self.screens.len() && index != self.active_screen {
+            self.active_screen = index;
+            self.selection.clear();
+        }
+    }
+
+    /// Add an empty screen with a unique name and make it active. Returns its index.
+    pub fn add_screen(&mut self) -> usize {
+        let mut n = self.screens.len() + 1;
+        while self.screens.iter().any(|s| s.name == format!("Screen {}", n)) {
+            n += 1;
+        }
+        self.screens.push(Screen::new(
+            &format!("Screen {}", n),
+            Box::new(crate::widgets::VerticalLayout::default()),
+        ));
+        let index = self.screens.len() - 1;
+        self.set_active_screen(index);
+        index
+    }
+
+    /// Remove a screen. The last remaining screen can't be removed.
+    pub fn remove_screen(&mut self, index: usize) -> bool {
+        if self.screens.len() <= 1 || index >= self.screens.len() {
+            return false;
+        }
+        self.screens.remove(index);
+        if self.active_screen >= index && self.active_screen > 0 {
+            self.active_screen -= 1;
+        }
+        self.selection.clear();
+        true
+    }
+
+    /// Rename a screen, updating the navigation actions that target it by name.
+    /// References are left alone while another screen still has the old name.
+    pub fn rename_screen(&mut self, index: usize, name: &str) {
+        let Some(old) = self.screens.get(index).map(|screen| screen.name.clone()) else {
+            return;
+        };
+        self.screens[index].name = name.to_string();
+        if self.screens.iter().any(|screen| screen.name == old) {
+            return;
+        }
+        for screen in &mut self.screens {
+            for_each_widget_mut(screen.root.as_mut(), &mut |widget| {
+                for action in widget.events_mut().into_iter().flat_map(|events| events.values_mut().flatten()) {
+                    action.visit_mut(&mut |action| {
+                        if let Action::NavigateTo(target) = action {
+                            if *target == old {
+                                *target = name.to_string();
+                            }
+                        }
+                    });
+                }
+            });
+        }
+    }
+
+    /// Rename a project-defined type, updating the variables and struct fields that use it
+    pub fn rename_type(&mut self, old: &str, new: &str) {
+        for t in &mut self.types {
+            if t.name == old {
+                t.name = new.to_string();
+            }
+            if let CustomTypeKind::Struct { fields } = &mut t.kind {
+                for field in fields {
+                    field.v_type.rename_type(old, new);
+                }
+            }
+        }
+        for var in self.variables.values_mut() {
+            var.v_type.rename_type(old, new);
+        }
+    }
+
+    /// Select a widget, switching to the screen that contains it.
+    /// Returns false if no screen has a widget with this id.
+    pub fn reveal_widget(&mut self, id: Uuid) -> bool {
+        let screen = self.screens.iter().position(|screen| {
+            let mut ids = Vec::new();
+            collect_widget_ids(screen.root.as_ref(), &mut ids);
+            ids.contains(&id)
+        });
+        match screen {
+            Some(index) => {
+                self.set_active_screen(index);
+                self.selection.clear();
+                self.selection.insert(id);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Recursively find a node by its UUID.
     pub fn find_node_mut(&mut self, id: Uuid) -> Option<&mut dyn WidgetNode> {
-        find_node_recursive_mut(self.root_node.as_mut(), id)
+        find_node_recursive_mut(self.root_mut().as_mut(), id)
+    }
+
+    pub fn find_node(&self, id: Uuid) -> Option<&dyn WidgetNode> {
+        find_node_by_id(self.root(), id)
+    }
+
+    /// Properties that all of the widgets have, matched by name and type
+    pub fn shared_properties(&self, ids: &[Uuid]) -> Vec<SharedProperty> {
+        let nodes: Vec<&dyn WidgetNode> = ids.iter().filter_map(|id| self.find_node(*id)).collect();
+        let Some((first, rest)) = nodes.split_first() else {
+            return Vec::new();
+        };
+        first
+            .properties()
+            .into_iter()
+            .filter_map(|descriptor| {
+                let value = descriptor.get(*first)?;
+                // A bound widget has no value of its own to show
+                let mut mixed = is_bound(*first, descriptor.name);
+                for node in rest {
+                    let other = node.properties().into_iter().find(|d| d.name == descriptor.name)?;
+                    if !other.kind.same_type(&descriptor.kind) {
+                        return None;
+                    }
+                    mixed |= is_bound(*node, descriptor.name) || other.get(*node).as_ref() != Some(&value);
+                }
+                Some(SharedProperty { descriptor, value, mixed })
+            })
+            .collect()
+    }
+
+    /// Set a property on each of the widgets that has it and doesn't bind it to a variable.
+    /// Returns how many were changed.
+    pub fn set_property(&mut self, ids: &[Uuid], property: &str, value: &PropertyValue) -> usize {
+        let mut changed = 0;
+        for id in ids {
+            let Some(node) = self.find_node_mut(*id) else {
+                continue;
+            };
+            // Bound widgets follow their variable; a static value would be ignored
+            if is_bound(node, property) {
+                continue;
+            }
+            let Some(descriptor) = node.properties().into_iter().find(|d| d.name == property) else {
+                continue;
+            };
+            if descriptor.get(node).as_ref() != Some(value) && descriptor.set(node, value.clone()) {
+                changed += 1;
+            }
+        }
+        changed
     }
 
     /// Delete a widget by its ID. Returns true if the widget was found and deleted.
     /// Cannot delete the root node.
     pub fn delete_widget(&mut self, id: Uuid) -> bool {
         // Don't allow deleting the root node
-        if self.root_node.id() == id {
+        if self.root().id() == id {
             return false;
         }
 
-        delete_node_recursive(self.root_node.as_mut(), id)
+        self.extract_widget(id).is_some()
     }
 
     /// Find the parent of a widget by the child's ID
     pub fn find_parent_mut(&mut self, child_id: Uuid) -> Option<&mut dyn WidgetNode> {
-        find_parent_recursive_mut(self.root_node.as_mut(), child_id)
+        let parent = self.child_position(child_id)?.parent;
+        self.find_node_mut(parent)
     }
 
-    /// Move a widget within its parent's children list
+    /// Move a widget within its slot of its parent's children
     pub fn reorder_widget(&mut self, widget_id: Uuid, new_index: usize) -> bool {
-        reorder_widget_recursive(self.root_node.as_mut(), widget_id, new_index)
+        let Some(position) = self.child_position(widget_id) else {
+            return false;
+        };
+        self.with_slot(position.parent, position.slot, |children| {
+            if new_index >= children.len() {
+                return false;
+            }
+            let (widget, layout) = children.remove(position.index);
+            let insert_index = if new_index > position.index {
+                new_index - 1
+            } else {
+                new_index
+            };
+            children.insert(insert_index, widget, layout);
+            true
+        })
+        .unwrap_or(false)
     }
 
     /// Move a widget up in its parent's children list (towards index 0)
     pub fn move_widget_up(&mut self, widget_id: Uuid) -> bool {
-        move_widget_in_parent(self.root_node.as_mut(), widget_id, -1)
+        self.move_widget_by(widget_id, -1)
     }
 
     /// Move a widget down in its parent's children list (towards end)
     pub fn move_widget_down(&mut self, widget_id: Uuid) -> bool {
-        move_widget_in_parent(self.root_node.as_mut(), widget_id, 1)
+        self.move_widget_by(widget_id, 1)
+    }
+
+    fn move_widget_by(&mut self, widget_id: Uuid, delta: isize) -> bool {
+        let Some(position) = self.child_position(widget_id) else {
+            return false;
+        };
+        self.with_slot(position.parent, position.slot, |children| {
+            // Can't move further in that direction
+            let Some(new_index) = position.index.checked_add_signed(delta).filter(|i| *i < children.len()) else {
+                return false;
+            };
+            let (widget, layout) = children.remove(position.index);
+            children.insert(new_index, widget, layout);
+            true
+        })
+        .unwrap_or(false)
+    }
+
+    /// Where a widget sits in the tree, or `None` for the root and unknown ids
+    pub fn child_position(&self, widget_id: Uuid) -> Option<ChildPosition> {
+        find_position(self.root(), widget_id)
+    }
+
+    /// Layout data of the widget at `position`
+    pub fn child_layout(&self, position: ChildPosition) -> Option<ChildLayout> {
+        let parent = self.find_node(position.parent)?;
+        let slots = parent.slots();
+        let children = slots.get(position.slot)?.children;
+        (position.index < children.len()).then(|| children.layout(position.index))
+    }
+
+    /// Run `f` on one slot of a container's children
+    fn with_slot<R>(&mut self, parent: Uuid, slot: usize, f: impl FnOnce(&mut dyn ChildList) -> R) -> Option<R> {
+        let mut slots = self.find_node_mut(parent)?.slots_mut();
+        if slot >= slots.len() {
+            return None;
+        }
+        let ChildSlotMut { children, .. } = slots.swap_remove(slot);
+        Some(f(children))
+    }
+
+    /// Take a widget out of the tree, along with its layout data
+    fn extract_widget(&mut self, widget_id: Uuid) -> Option<(Box<dyn WidgetNode>, ChildLayout)> {
+        let position = self.child_position(widget_id)?;
+        self.with_slot(position.parent, position.slot, |children| children.remove(position.index))
+    }
+
+    /// Add a widget at the end of a container's default slot. Returns false if it isn't a container.
+    pub fn add_widget(&mut self, container_id: Uuid, widget: Box<dyn WidgetNode>) -> bool {
+        let Some(slot) = self.find_node(container_id).map(|container| container.default_slot()) else {
+            return false;
+        };
+        let position = ChildPosition { parent: container_id, slot, index: usize::MAX };
+        self.insert_widget(position, widget, ChildLayout::Flow)
+    }
+
+    /// Put a widget into a slot of a container. Returns false if there is no such slot.
+    fn insert_widget(&mut self, position: ChildPosition, widget: Box<dyn WidgetNode>, layout: ChildLayout) -> bool {
+        self.with_slot(position.parent, position.slot, |children| children.insert(position.index, widget, layout))
+            .is_some()
+    }
+
+    /// Insert a copy of a widget, with fresh ids, right after it. Returns the copy's id.
+    pub fn duplicate_widget(&mut self, widget_id: Uuid) -> Option<Uuid> {
+        let position = self.child_position(widget_id)?;
+        let copy = with_fresh_ids(&[self.find_node(widget_id)?.clone_box()])?.pop()?;
+        let copy_id = copy.id();
+        // A positioned copy is offset so it doesn't hide the original
+        let layout = match self.child_layout(position)? {
+            ChildLayout::Placed { x, y, width, height } => ChildLayout::Placed { x: x + 10.0, y: y + 10.0, width, height },
+            ChildLayout::Flow => ChildLayout::Flow,
+        };
+        let after = ChildPosition { index: position.index + 1, ..position };
+        self.insert_widget(after, copy, layout).then_some(copy_id)
+    }
+
+    /// Put a widget inside a new container (one of `CONTAINER_TYPES`) where it was.
+    /// Returns the container's id.
+    pub fn wrap_widget(&mut self, widget_id: Uuid, container_type: &str) -> Option<Uuid> {
+        use crate::widgets::{create_widget_by_name, CONTAINER_TYPES};
+
+        if !CONTAINER_TYPES.contains(&container_type) {
+            return None;
+        }
+        let position = self.child_position(widget_id)?;
+        let mut container = create_widget_by_name(container_type)?;
+        let container_id = container.id();
+        let slot = container.default_slot();
+        if slot >= container.slots().len() {
+            return None;
+        }
+        let (widget, layout) = self.extract_widget(widget_id)?;
+        container.slots_mut().swap_remove(slot).children.insert(0, widget, ChildLayout::Flow);
+        self.insert_widget(position, container, layout).then_some(container_id)
+    }
+
+    /// Replace a container with its children, from every slot. Returns their ids.
+    /// Inside a positioned parent the children keep their place relative to the container.
+    pub fn unwrap_container(&mut self, container_id: Uuid) -> Option<Vec<Uuid>> {
+        use crate::widgets::CONTAINER_TYPES;
+
+        if !CONTAINER_TYPES.contains(&self.find_node(container_id)?.name()) {
+            return None;
+        }
+        let position = self.child_position(container_id)?;
+        let (mut container, layout) = self.extract_widget(container_id)?;
+        let children = take_children(container.as_mut());
+        let ids: Vec<Uuid> = children.iter().map(|(child, _)| child.id()).collect();
+        for (offset, (child, child_layout)) in children.into_iter().enumerate() {
+            let child_layout = match (layout, child_layout) {
+                (ChildLayout::Flow, _) => ChildLayout::Flow,
+                (ChildLayout::Placed { x, y, .. }, ChildLayout::Placed { x: dx, y: dy, width, height }) => {
+                    ChildLayout::Placed { x: x + dx, y: y + dy, width, height }
+                }
+                (ChildLayout::Placed { x, y, .. }, ChildLayout::Flow) => {
+                    ChildLayout::Placed { x, y: y + offset as f32 * FLOW_STAGGER, width: None, height: None }
+                }
+            };
+            let at = ChildPosition { index: position.index + offset, ..position };
+            self.insert_widget(at, child, child_layout);
+        }
+        Some(ids)
+    }
+
+    /// Turn a container into another of `CONTAINER_TYPES`, keeping its id, children and
+    /// common properties. Children of every slot go into the new container's default slot.
+    /// The root can only become another layout.
+    pub fn convert_container(&mut self, container_id: Uuid, container_type: &str) -> bool {
+        use crate::widgets::{create_widget_by_name, CONTAINER_TYPES};
+
+        let Some(node) = self.find_node(container_id) else {
+            return false;
+        };
+        if !CONTAINER_TYPES.contains(&node.name()) || !CONTAINER_TYPES.contains(&container_type) {
+            return false;
+        }
+        if node.name() == container_type {
+            return true;
+        }
+        let position = self.child_position(container_id);
+        if position.is_none() && !container_type.ends_with("Layout") {
+            return false;
+        }
+
+        // Carry the id and common properties over through the serialized form
+        let (Some(replacement), Ok(old)) = (create_widget_by_name(container_type), serde_json::to_value(node)) else {
+            return false;
+        };
+        let Ok(mut value) = serde_json::to_value(&replacement) else {
+            return false;
+        };
+        for key in ["id", "common"] {
+            if let Some(field) = old.get(key) {
+                value[key] = field.clone();
+            }
+        }
+        let Ok(mut converted) = serde_json::from_value::<Box<dyn WidgetNode>>(value) else {
+            return false;
+        };
+        let slot = converted.default_slot();
+        if slot >= converted.slots().len() {
+            return false;
+        }
+
+        // Move the children across; flow children are stacked if the new container positions them
+        let Some(children) = self.find_node_mut(container_id).map(take_children) else {
+            return false;
+        };
+        let ChildSlotMut { children: list, .. } = converted.slots_mut().swap_remove(slot);
+        for (index, (child, layout)) in children.into_iter().enumerate() {
+            let layout = match layout {
+                ChildLayout::Flow => {
+                    ChildLayout::Placed { x: 0.0, y: index as f32 * FLOW_STAGGER, width: None, height: None }
+                }
+                placed => placed,
+            };
+            list.insert(usize::MAX, child, layout);
+        }
+
+        let Some(position) = position else {
+            *self.root_mut() = converted;
+            return true;
+        };
+        self.with_slot(position.parent, position.slot, |children| {
+            let (_, layout) = children.remove(position.index);
+            children.insert(position.index, converted, layout);
+        })
+        .is_some()
+    }
+
+    /// The widgets as clipboard text: a JSON array of serialized widgets in hierarchy order.
+    /// A widget inside another copied one is only copied as part of it.
+    pub fn copy_widgets(&self, ids: &[Uuid]) -> Option<String> {
+        let widgets: Vec<&dyn WidgetNode> = self
+            .get_all_widget_ids()
+            .into_iter()
+            .filter(|id| ids.contains(id))
+            .filter(|id| !ids.iter().any(|other| other != id && self.is_descendant_of(*id, *other)))
+            .filter_map(|id| self.find_node(id))
+            .collect();
+        if widgets.is_empty() {
+            return None;
+        }
+        serde_json::to_string(&widgets).ok()
+    }
+
+    /// Paste clipboard text from `copy_widgets` (or a single serialized widget) with fresh ids.
+    /// The widgets go into the selected container, or after the last selected widget,
+    /// or at the end of the root. They become the selection; returns their ids.
+    pub fn paste_widgets(&mut self, text: &str) -> Vec<Uuid> {
+        let Some(widgets) = parse_clipboard(text).and_then(|widgets| with_fresh_ids(&widgets)) else {
+            return Vec::new();
+        };
+        let selected: Vec<Uuid> = self
+            .get_all_widget_ids()
+            .into_iter()
+            .filter(|id| self.selection.contains(id))
+            .collect();
+        let into = |container: &dyn WidgetNode| ChildPosition {
+            parent: container.id(),
+            slot: container.default_slot(),
+            index: usize::MAX,
+        };
+        let mut position = match selected.as_slice() {
+            [id] if self.is_container(*id) => self.find_node(*id).map(into),
+            [.., last] => self
+                .child_position(*last)
+                .map(|position| ChildPosition { index: position.index + 1, ..position }),
+            [] => None,
+        }
+        .unwrap_or_else(|| into(self.root()));
+
+        let mut pasted = Vec::new();
+        for widget in widgets {
+            let id = widget.id();
+            if !self.insert_widget(position, widget, ChildLayout::Flow) {
+                break;
+            }
+            position.index = position.index.saturating_add(1);
+            pasted.push(id);
+        }
+        if !pasted.is_empty() {
+            self.selection = pasted.iter().copied().collect();
+        }
+        pasted
+    }
+
+    /// Windows and tab containers on every screen, for actions that open windows or switch tabs
+    pub fn action_targets(&self) -> Vec<ActionTarget> {
+        fn collect(node: &dyn WidgetNode, targets: &mut Vec<ActionTarget>) {
+            targets.extend(node.action_target());
+            for child in node.child_widgets() {
+                collect(child, targets);
+            }
+        }
+        let mut targets = Vec::new();
+        for screen in &self.screens {
+            collect(screen.root.as_ref(), &mut targets);
+        }
+        targets
     }
 
     /// Get all widget IDs in hierarchy order (depth-first traversal)
     pub fn get_all_widget_ids(&self) -> Vec<Uuid> {
         let mut ids = Vec::new();
-        collect_widget_ids(self.root_node.as_ref(), &mut ids);
+        collect_widget_ids(self.root(), &mut ids);
         ids
     }
 
     /// Get the current root layout type as a string
     pub fn root_layout_type(&self) -> &str {
-        self.root_node.name()
+        self.root().name()
     }
 
-    /// Change the root layout type (preserves children and ID)
+    /// Change the root layout type (preserves children and ID).
+    /// Unknown and non-layout types are ignored.
     pub fn set_root_layout_type(&mut self, layout_type: &str) {
-        use crate::widgets::{VerticalLayout, HorizontalLayout, GridLayout};
-
-        // Extract current children and ID
-        let current_id = self.root_node.id();
-        let current_children = if let Some(children) = self.root_node.children() {
-            children.iter().map(|c| c.clone_box()).collect()
-        } else {
-            Vec::new()
-        };
+        let root_id = self.root().id();
+        self.convert_container(root_id, layout_type);
+    }
 
-        // Create new root with same ID and children
-        self.root_node = match layout_type {
-            "Vertical Layout" => Box::new(VerticalLayout {
-                id: current_id,
-                children: current_children,
-                spacing: 5.0,
-                ..Default::default()
-            }),
-            "Horizontal Layout" => Box::new(HorizontalLayout {
-                id: current_id,
-                children: current_children,
-                spacing: 5.0,
-            }),
-            "Grid Layout" => Box::new(GridLayout {
-                id: current_id,
-                children: current_children,
-                columns: 2,
-                spacing: 5.0,
-            }),
-            _ => return, // Unknown layout type, do nothing
+    /// Re-parent a widget: move it from its current parent to a new container at a specific index
+    /// of the container's default slot. Returns true if successful
+    pub fn reparent_widget(&mut self, widget_id: Uuid, new_parent_id: Uuid, index: usize) -> bool {
+        let Some(slot) = self.find_node(new_parent_id).map(|parent| parent.default_slot()) else {
+            return false;
         };
+        self.move_widget_to(widget_id, ChildPosition { parent: new_parent_id, slot, index })
     }
 
-    /// Re-parent a widget: move it from its current parent to a new container at a specific index
+    /// Move a widget to a slot of a container, keeping its layout data.
     /// Returns true if successful
-    pub fn reparent_widget(&mut self, widget_id: Uuid, new_parent_id: Uuid, index: usize) -> bool {
+    pub fn move_widget_to(&mut self, widget_id: Uuid, mut position: ChildPosition) -> bool {
         // Can't re-parent the root node
-        if widget_id == self.root_node.id() {
+        if widget_id == self.root().id() {
             return false;
         }
 
         // Can't move a widget into itself or its descendants
-        if widget_id == new_parent_id || self.is_descendant_of(new_parent_id, widget_id) {
+        if widget_id == position.parent || self.is_descendant_of(position.parent, widget_id) {
             return false;
         }
 
-        // Extract the widget from its current location
-        if let Some(widget) = extract_widget_recursive(self.root_node.as_mut(), widget_id) {
-            // Insert it at the new location
-            if insert_widget_at(self.root_node.as_mut(), new_parent_id, widget, index) {
-                return true;
-            }
+        // The target slot must exist before the widget is taken out
+        let has_slot = self
+            .find_node(position.parent)
+            .is_some_and(|parent| position.slot < parent.slots().len());
+        let Some(old) = self.child_position(widget_id).filter(|_| has_slot) else {
+            return false;
+        };
+        // Later siblings shift down once the widget is out
+        if old.parent == position.parent && old.slot == position.slot && old.index < position.index {
+            position.index -= 1;
+        }
+        match self.extract_widget(widget_id) {
+            Some((widget, layout)) => self.insert_widget(position, widget, layout),
+            None => false,
         }
-        false
     }
 
     /// Move a widget before another widget (for drop insertion)
     pub fn move_widget_before(&mut self, widget_id: Uuid, before_id: Uuid) -> bool {
-        // Find the parent and index of the "before" widget
-        if let Some(parent_id) = self.get_parent_id(before_id) {
-            if let Some(parent) = self.find_node_mut(parent_id) {
-                if let Some(index) = get_widget_index(parent, before_id) {
-                    return self.reparent_widget(widget_id, parent_id, index);
-                }
-            }
+        match self.child_position(before_id) {
+            Some(position) => self.move_widget_to(widget_id, position),
+            None => false,
         }
-        false
     }
 
     /// Move a widget after another widget (for drop insertion)
     pub fn move_widget_after(&mut self, widget_id: Uuid, after_id: Uuid) -> bool {
-        if let Some(parent_id) = self.get_parent_id(after_id) {
-            if let Some(parent) = self.find_node_mut(parent_id) {
-                if let Some(index) = get_widget_index(parent, after_id) {
-                    return self.reparent_widget(widget_id, parent_id, index + 1);
-                }
-            }
+        match self.child_position(after_id) {
+            Some(position) => self.move_widget_to(widget_id, ChildPosition { index: position.index + 1, ..position }),
+            None => false,
         }
-        false
     }
 
     /// Get the parent ID of a widget
     pub fn get_parent_id(&self, child_id: Uuid) -> Option<Uuid> {
-        get_parent_id_recursive(self.root_node.as_ref(), child_id)
+        self.child_position(child_id).map(|position| position.parent)
     }
 
     /// Check if `potential_descendant` is a descendant of `potential_ancestor`
     fn is_descendant_of(&self, potential_descendant: Uuid, potential_ancestor: Uuid) -> bool {
-        if let Some(node) = find_node_by_id(self.root_node.as_ref(), potential_ancestor) {
+        if let Some(node) = find_node_by_id(self.root(), potential_ancestor) {
             return contains_id_recursive(node, potential_descendant);
         }
         false
     }
 
     /// Check if a widget is a container that can accept children
-    #[allow(dead_code)]
     pub fn is_container(&self, widget_id: Uuid) -> bool {
-        if let Some(node) = find_node_by_id(self.root_node.as_ref(), widget_id) {
+        if let Some(node) = find_node_by_id(self.root(), widget_id) {
             return is_container(node);
         }
         false
     }
 }
 
-fn collect_widget_ids(node: &dyn WidgetNode, ids: &mut Vec<Uuid>) {
-    ids.push(node.id());
-    if let Some(children) = node.children() {
-        for child in children {
-            collect_widget_ids(child.as_ref(), ids);
-        }
-    }
+/// Widgets from clipboard text: a JSON array of widgets or a single widget
+pub fn parse_clipboard(text: &str) -> Option<Vec<Box<dyn WidgetNode>>> {
+    let text = text.trim();
+    serde_json::from_str::<Vec<Box<dyn WidgetNode>>>(text)
+        .or_else(|_| serde_json::from_str::<Box<dyn WidgetNode>>(text).map(|widget| vec![widget]))
+        .ok()
+        .filter(|widgets| !widgets.is_empty())
 }
 
-fn find_node_recursive_mut(node: &mut dyn WidgetNode, target: Uuid) -> Option<&mut dyn WidgetNode> {
-    if node.id() == target {
-        return Some(node);
-    }
-    if let Some(children) = node.children_mut() {
-        for child in children {
-            if let Some(found) = find_node_recursive_mut(child.as_mut(), target) {
-                return Some(found);
+/// Copies of the widgets with new ids for them and every widget inside them.
+/// References between the copied widgets, like a button opening a copied window, follow the new ids.
+/// Returns None if the widgets can't be copied, rather than copies that share ids with the originals.
+pub fn with_fresh_ids(widgets: &[Box<dyn WidgetNode>]) -> Option<Vec<Box<dyn WidgetNode>>> {
+    fn collect_ids(value: &serde_json::Value, ids: &mut HashMap<String, String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if let (Some(_), Some(serde_json::Value::String(id))) = (map.get("type"), map.get("id")) {
+                    ids.insert(id.clone(), Uuid::new_v4().to_string());
+                }
+                map.values().for_each(|v| collect_ids(v, ids));
             }
+            serde_json::Value::Array(items) => items.iter().for_each(|v| collect_ids(v, ids)),
+            _ => {}
         }
     }
-    None
-}
-
-fn delete_node_recursive(node: &mut dyn WidgetNode, target: Uuid) -> bool {
-    if let Some(children) = node.children_mut() {
-        // First, check if any direct child matches the target
-        if let Some(index) = children.iter().position(|c| c.id() == target) {
-            children.remove(index);
-            return true;
-        }
-
-        // If not found in direct children, recurse into each child
-        for child in children.iter_mut() {
-            if delete_node_recursive(child.as_mut(), target) {
-                return true;
+    /// Only the fields that hold widget ids are rewritten; text that happens to equal an id is left alone
+    fn replace_ids(value: &mut serde_json::Value, ids: &HashMap<String, String>) {
+        let replace = |field: Option<&mut serde_json::Value>| {
+            if let Some(serde_json::Value::String(id)) = field {
+                if let Some(new_id) = ids.get(id.as_str()) {
+                    *id = new_id.clone();
+                }
+            }
+        };
+        match value {
+            serde_json::Value::Object(map) => {
+                if map.contains_key("type") {
+                    replace(map.get_mut("id"));
+                }
+                // Actions are externally tagged: {"SetWindowOpen": {"window": .., "open": ..}}
+                for (variant, field) in [("SetWindowOpen", "window"), ("SelectTab", "container")] {
+                    if let Some(serde_json::Value::Object(action)) = map.get_mut(variant) {
+                        replace(action.get_mut(field));
+                    }
+                }
+                map.values_mut().for_each(|v| replace_ids(v, ids));
             }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| replace_ids(v, ids)),
+            _ => {}
         }
     }
-    false
-}
 
-fn find_parent_recursive_mut(node: &mut dyn WidgetNode, child_id: Uuid) -> Option<&mut dyn WidgetNode> {
-    // First, check if any direct child matches (without holding the borrow)
-    let has_matching_child = if let Some(children) = node.children() {
-        children.iter().any(|c| c.id() == child_id)
-    } else {
-        false
-    };
+    let mut value = serde_json::to_value(widgets).ok()?;
+    let mut ids = HashMap::new();
+    collect_ids(&value, &mut ids);
+    replace_ids(&mut value, &ids);
+    serde_json::from_value(value).ok()
+}
 
-    if has_matching_child {
-        return Some(node);
-    }
+/// Vertical distance between flow children given positions, so they don't cover each other
+const FLOW_STAGGER: f32 = 30.0;
 
-    // Recurse into each child
-    if let Some(children) = node.children_mut() {
-        for child in children.iter_mut() {
-            if let Some(parent) = find_parent_recursive_mut(child.as_mut(), child_id) {
-                return Some(parent);
-            }
+/// Take every child out of a container, slot by slot, with its layout data
+fn take_children(container: &mut dyn WidgetNode) -> Vec<(Box<dyn WidgetNode>, ChildLayout)> {
+    let mut taken = Vec::new();
+    for ChildSlotMut { children, .. } in container.slots_mut() {
+        while !children.is_empty() {
+            taken.push(children.remove(0));
         }
     }
-    None
+    taken
 }
 
-fn reorder_widget_recursive(node: &mut dyn WidgetNode, widget_id: Uuid, new_index: usize) -> bool {
-    if let Some(children) = node.children_mut() {
-        // Check if the widget is a direct child
-        if let Some(old_index) = children.iter().position(|c| c.id() == widget_id) {
-            if new_index < children.len() {
-                let widget = children.remove(old_index);
-                let insert_index = if new_index > old_index {
-                    new_index - 1
-                } else {
-                    new_index
-                };
-                children.insert(insert_index.min(children.len()), widget);
-                return true;
-            }
-        }
-
-        // Recurse into each child
-        for child in children.iter_mut() {
-            if reorder_widget_recursive(child.as_mut(), widget_id, new_index) {
-                return true;
-            }
-        }
+fn collect_widget_ids(node: &dyn WidgetNode, ids: &mut Vec<Uuid>) {
+    ids.push(node.id());
+    for child in node.child_widgets() {
+        collect_widget_ids(child, ids);
     }
-    false
 }
 
-/// Move a widget up or down within its parent's children list
-/// delta: -1 for up (towards index 0), +1 for down (towards end)
-fn move_widget_in_parent(node: &mut dyn WidgetNode, widget_id: Uuid, delta: i32) -> bool {
-    if let Some(children) = node.children_mut() {
-        // Check if the widget is a direct child
-        if let Some(current_index) = children.iter().position(|c| c.id() == widget_id) {
-            let new_index = current_index as i32 + delta;
-
-            // Check bounds
-            if new_index >= 0 && new_index < children.len() as i32 {
-                let widget = children.remove(current_index);
-                children.insert(new_index as usize, widget);
-                return true;
-            }
-            return false; // Can't move further in that direction
-        }
+/// Whether the widget's property follows a variable
+fn is_bound(node: &dyn WidgetNode, property: &str) -> bool {
+    node.bindings().is_some_and(|bindings| bindings.contains_key(property))
+}
 
-        // Recurse into each child
-        for child in children.iter_mut() {
-            if move_widget_in_parent(child.as_mut(), widget_id, delta) {
-                return true;
-            }
-        }
+fn for_each_widget_mut(node: &mut dyn WidgetNode, f: &mut dyn FnMut(&mut dyn WidgetNode)) {
+    f(node);
+    for child in node.child_widgets_mut() {
+        for_each_widget_mut(child, f);
     }
-    false
 }
 
-/// Remove a widget from the tree and return it (if found)
-fn extract_widget_recursive(node: &mut dyn WidgetNode, widget_id: Uuid) -> Option<Box<dyn WidgetNode>> {
-    if let Some(children) = node.children_mut() {
-        // Check if any direct child matches
-        if let Some(index) = children.iter().position(|c| c.id() == widget_id) {
-            return Some(children.remove(index));
-        }
-
-        // Recurse into each child
-        for child in children.iter_mut() {
-            if let Some(found) = extract_widget_recursive(child.as_mut(), widget_id) {
-                return Some(found);
-            }
+fn find_node_recursive_mut(node: &mut dyn WidgetNode, target: Uuid) -> Option<&mut dyn WidgetNode> {
+    if node.id() == target {
+        return Some(node);
+    }
+    for child in node.child_widgets_mut() {
+        if let Some(found) = find_node_recursive_mut(child, target) {
+            return Some(found);
         }
     }
     None
 }
 
-/// Insert a widget as a child of the target container at the specified index
-fn insert_widget_at(node: &mut dyn WidgetNode, target_id: Uuid, widget: Box<dyn WidgetNode>, index: usize) -> bool {
-    if node.id() == target_id {
-        if let Some(children) = node.children_mut() {
-            let insert_at = index.min(children.len());
-            children.insert(insert_at, widget);
-            return true;
-        }
-        return false; // Target is not a container
-    }
-
-    if let Some(children) = node.children_mut() {
-        for child in children.iter_mut() {
-            if insert_widget_at(child.as_mut(), target_id, widget.clone_box(), index) {
-                return true;
-            }
+/// Where a widget sits below `node`, searching every slot
+fn find_position(node: &dyn WidgetNode, widget_id: Uuid) -> Option<ChildPosition> {
+    for (slot, children) in node.slots().iter().enumerate() {
+        if let Some(index) = children.children.position(widget_id) {
+            return Some(ChildPosition { parent: node.id(), slot, index });
         }
     }
-    false
+    node.child_widgets().into_iter().find_map(|child| find_position(child, widget_id))
 }
 
 /// Check if a widget is a container (has children capability)
-#[allow(dead_code)]
 pub fn is_container(node: &dyn WidgetNode) -> bool {
-    node.children().is_some()
-}
-
-/// Get the index of a widget within its parent's children
-fn get_widget_index(node: &dyn WidgetNode, widget_id: Uuid) -> Option<usize> {
-    if let Some(children) = node.children() {
-        for (i, child) in children.iter().enumerate() {
-            if child.id() == widget_id {
-                return Some(i);
-            }
-        }
-    }
-    None
-}
-
-/// Get the parent ID of a widget
-fn get_parent_id_recursive(node: &dyn WidgetNode, child_id: Uuid) -> Option<Uuid> {
-    if let Some(children) = node.children() {
-        // Check if any direct child matches
-        if children.iter().any(|c| c.id() == child_id) {
-            return Some(node.id());
-        }
-
-        // Recurse into children
-        for child in children {
-            if let Some(parent_id) = get_parent_id_recursive(child.as_ref(), child_id) {
-                return Some(parent_id);
-            }
-        }
-    }
-    None
+    !node.slots().is_empty()
 }
 
 /// Find a node by ID (immutable version)
@@ -815,29 +2740,12 @@ fn find_node_by_id(node: &dyn WidgetNode, target_id: Uuid) -> Option<&dyn Widget
     if node.id() == target_id {
         return Some(node);
     }
-    if let Some(children) = node.children() {
-        for child in children {
-            if let Some(found) = find_node_by_id(child.as_ref(), target_id) {
-                return Some(found);
-            }
-        }
-    }
-    None
+    node.child_widgets().into_iter().find_map(|child| find_node_by_id(child, target_id))
 }
 
 /// Check if a node contains a specific ID in its descendants
 fn contains_id_recursive(node: &dyn WidgetNode, target_id: Uuid) -> bool {
-    if node.id() == target_id {
-        return true;
-    }
-    if let Some(children) = node.children() {
-        for child in children {
-            if contains_id_recursive(child.as_ref(), target_id) {
-                return true;
-            }
-        }
-    }
-    false
+    node.id() == target_id || node.child_widgets().into_iter().any(|child| contains_id_recursive(child, target_id))
 }
 
 #[cfg(test)]
@@ -0,0 +1,243 @@
+//! Interactive preview: runs the design on the canvas against a live copy of
+//! the project variables, interpreting event actions instead of compiling them.
+
+use crate::model::{format_color, parse_color, Action, CustomType, ProjectState, Variable, VariableType, WidgetEvent};
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Runtime state of a preview session
+pub struct PreviewState {
+    /// Live copy of the project variables, edited by the previewed widgets
+    pub variables: HashMap<String, Variable>,
+    /// The project's enums and structs, for parsing values
+    pub types: Vec<CustomType>,
+    /// Index of the screen being previewed
+    pub current_screen: usize,
+    screen_names: Vec<String>,
+    /// Asset filename -> file on disk, for previewing images
+    asset_paths: HashMap<String, PathBuf>,
+    /// State the generated app keeps in private fields (open windows, selected tabs, ...)
+    widget_values: HashMap<Uuid, Box<dyn Any>>,
+    log: Vec<PreviewLogEntry>,
+    requests: Vec<PreviewRequest>,
+}
+
+/// Something an action asked the editor itself to do, carried out after the frame's preview is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewRequest {
+    OpenUrl(String),
+    CopyText(String),
+}
+
+/// A line of the preview log. Actions that fire every frame (e.g. on hover)
+/// are folded into one entry instead of flooding the log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewLogEntry {
+    pub message: String,
+    pub repeats: usize,
+}
+
+impl std::fmt::Display for PreviewLogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.repeats > 1 {
+            write!(f, "{} (×{})", self.message, self.repeats)
+        } else {
+            write!(f, "{}", self.message)
+        }
+    }
+}
+
+impl PreviewState {
+    /// Older log lines are dropped past this point
+    pub const MAX_LOG_LINES: usize = 200;
+
+    /// Start a preview of the screen being edited, with the variables at their initial values
+    pub fn new(project_state: &ProjectState) -> Self {
+        let mut preview = Self {
+            variables: project_state.variables.clone(),
+            types: Vec::new(),
+            current_screen: project_state.active_screen,
+            screen_names: Vec::new(),
+            asset_paths: HashMap::new(),
+            widget_values: HashMap::new(),
+            log: Vec::new(),
+            requests: Vec::new(),
+        };
+        preview.sync(project_state);
+        preview
+    }
+
+    /// Pick up project edits made while previewing: new, removed or retyped variables,
+    /// renamed or removed screens and newly imported assets. Live values are kept.
+    pub fn sync(&mut self, project_state: &ProjectState) {
+        self.variables.retain(|name, _| project_state.variables.contains_key(name));
+        for (name, var) in &project_state.variables {
+            match self.variables.get(name) {
+                Some(live) if live.v_type == var.v_type => {}
+                _ => {
+                    self.variables.insert(name.clone(), var.clone());
+                }
+            }
+        }
+
+        self.types = project_state.types.clone();
+        self.screen_names = project_state.screens.iter().map(|s| s.name.clone()).collect();
+        self.current_screen = self.current_screen.min(self.screen_names.len().saturating_sub(1));
+
+        self.asset_paths = project_state
+            .assets
+            .assets
+            .values()
+            .filter_map(|asset| {
+                let filename = asset.path.file_name()?.to_string_lossy().to_string();
+                Some((filename, asset.path.clone()))
+            })
+            .collect();
+    }
+
+    pub fn log(&self) -> &[PreviewLogEntry] {
+        &self.log
+    }
+
+    pub fn log_message(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if let Some(last) = self.log.last_mut() {
+            if last.message == message {
+                last.repeats += 1;
+                return;
+            }
+        }
+        self.log.push(PreviewLogEntry { message, repeats: 1 });
+        if self.log.len() > Self::MAX_LOG_LINES {
+            let excess = self.log.len() - Self::MAX_LOG_LINES;
+            self.log.drain(..excess);
+        }
+    }
+
+    /// Current value of a variable as the generated app would display it
+    pub fn display(&self, var_name: &str) -> Option<String> {
+        self.variables.get(var_name).map(|v| v.value.clone())
+    }
+
+    /// Widget text with its `{variable}` placeholders filled in from the live values.
+    /// Placeholders the generated code would write literally are left as they are.
+    pub fn interpolate(&self, text: &str) -> String {
+        use crate::interpolation::{self, FormatSpec, Segment};
+
+        interpolation::parse(text)
+            .iter()
+            .map(|segment| {
+                let Segment::Placeholder { var, spec } = segment else {
+                    return segment.source();
+                };
+                let live = self.variables.get(var);
+                match (live, FormatSpec::parse(spec)) {
+                    (Some(live), Ok(format))
+                        if interpolation::check_placeholder(var, spec, Some(&live.v_type)).is_ok() =>
+                    {
+                        format.apply(&live.v_type, &live.value)
+                    }
+                    _ => segment.source(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn number(&self, var_name: &str) -> Option<f64> {
+        self.variables.get(var_name)?.value.trim().parse().ok()
+    }
+
+    pub fn boolean(&self, var_name: &str) -> Option<bool> {
+        self.variables.get(var_name)?.value.trim().parse().ok()
+    }
+
+    /// Store a number, rounding and clamping it for integer variables
+    pub fn set_number(&mut self, var_name: &str, value: f64) {
+        if let Some(var) = self.variables.get_mut(var_name) {
+            var.value = match var.v_type.integer_range() {
+                Some((min, max)) => (value.round() as i128).clamp(min, max).to_string(),
+                None if var.v_type == VariableType::F32 => (value as f32).to_string(),
+                None => value.to_string(),
+            };
+        }
+    }
+
+    /// A color variable as the `[f32; 4]` the generated code uses
+    pub fn color(&self, var_name: &str) -> Option<[f32; 4]> {
+        let rgba = parse_color(&self.variables.get(var_name)?.value)?;
+        Some(rgba.map(|c| c as f32 / 255.0))
+    }
+
+    pub fn set_color(&mut self, var_name: &str, rgba: [f32; 4]) {
+        self.set_value(var_name, format_color(rgba.map(|c| (c * 255.0).round() as u8)));
+    }
+
+    pub fn set_value(&mut self, var_name: &str, value: String) {
+        if let Some(var) = self.variables.get_mut(var_name) {
+            var.value = value;
+        }
+    }
+
+    /// Parse `text` as a value of the variable's type and store it.
+    /// Returns false, leaving the value unchanged, if it doesn't parse.
+    pub fn set_parsed(&mut self, var_name: &str, text: &str) -> bool {
+        let Some(var) = self.variables.get_mut(var_name) else {
+            return false;
+        };
+        match var.v_type.parse_value(text, &self.types) {
+            Ok(value) => {
+                var.value = var.v_type.value_text(&value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn request(&mut self, request: PreviewRequest) {
+        self.requests.push(request);
+    }
+
+    /// Requests made since the last call
+    pub fn take_requests(&mut self) -> Vec<PreviewRequest> {
+        std::mem::take(&mut self.requests)
+    }
+
+    /// Widget state kept between frames, or `default` if the widget hasn't stored any yet
+    pub fn widget_value<T: Clone + 'static>(&self, id: Uuid, default: T) -> T {
+        self.widget_values
+            .get(&id)
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+            .unwrap_or(default)
+    }
+
+    pub fn set_widget_value<T: 'static>(&mut self, id: Uuid, value: T) {
+        self.widget_values.insert(id, Box::new(value));
+    }
+
+    /// File on disk of an imported asset
+    pub fn asset_path(&self, filename: &str) -> Option<&Path> {
+        self.asset_paths.get(filename).map(|p| p.as_path())
+    }
+
+    /// Switch to a screen by name. Returns false if there is no such screen.
+    pub fn navigate(&mut self, screen: &str) -> bool {
+        match self.screen_names.iter().position(|name| name == screen) {
+            Some(index) => {
+                self.current_screen = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run the actions a widget has configured for `event`, if any, and log what happened
+    pub fn fire(&mut self, widget_name: &str, events: &HashMap<WidgetEvent, Vec<Action>>, event: WidgetEvent) {
+        if let Some(actions) = events.get(&event).filter(|actions| !actions.is_empty()) {
+            let outcome = Action::simulate_all(actions, self);
+            self.log_message(format!("{} {}: {}", widget_name, event, outcome));
+        }
+    }
+}
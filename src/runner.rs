@@ -0,0 +1,285 @@
+//! "Play" mode: build the generated app in a workspace and run it as a child
+//! process, streaming build and program output into a log.
+
+use crate::compiler::Compiler;
+use crate::model::ProjectState;
+use crate::validator::CodeValidator;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Package (and binary) name of the play workspace
+const PLAY_PACKAGE: &str = "aether_play";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayState {
+    Building,
+    Running,
+    /// The app exited by itself, with its exit code if it had one
+    Exited(Option<i32>),
+    BuildFailed,
+    Stopped,
+}
+
+impl PlayState {
+    /// Whether cargo or the app is still running
+    pub fn is_active(&self) -> bool {
+        matches!(self, PlayState::Building | PlayState::Running)
+    }
+
+    pub fn display_text(&self) -> String {
+        match self {
+            PlayState::Building => "Building...".to_string(),
+            PlayState::Running => "▶ Running".to_string(),
+            PlayState::Exited(Some(code)) => format!("Exited with code {}", code),
+            PlayState::Exited(None) => "Exited".to_string(),
+            PlayState::BuildFailed => "✗ Build failed".to_string(),
+            PlayState::Stopped => "Stopped".to_string(),
+        }
+    }
+}
+
+enum PlayEvent {
+    Log(String),
+    State(PlayState),
+}
+
+/// Callback the worker uses to wake up the editor, e.g. to request a repaint
+type UpdateCallback = Arc<dyn Fn() + Send + Sync>;
+
+/// A build-and-run of the generated app
+pub struct PlaySession {
+    events: mpsc::Receiver<PlayEvent>,
+    stop: Arc<AtomicBool>,
+    /// cargo while building, then the app itself
+    child: Arc<Mutex<Option<Child>>>,
+    /// Joined on drop so the next session never shares the workspace with a running build
+    worker: Option<JoinHandle<()>>,
+    log: Vec<String>,
+    state: PlayState,
+    /// Hash of the project the session was started from, to detect project changes
+    fingerprint: u64,
+}
+
+impl PlaySession {
+    /// Older log lines are dropped past this point
+    pub const MAX_LOG_LINES: usize = 5000;
+
+    /// Crate the app is built in, one per editor process. Removed when the session ends;
+    /// the build cache lives in the validator's shared target directory.
+    pub fn workspace_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("aether_play_{}", std::process::id()))
+    }
+
+    /// Write the generated crate and start building it on a worker thread.
+    /// The app is launched once the build succeeds.
+    pub fn start(project_state: &ProjectState, on_update: impl Fn() + Send + Sync + 'static) -> Self {
+        let (sender, events) = mpsc::channel();
+        let mut session = Self {
+            events,
+            stop: Arc::new(AtomicBool::new(false)),
+            child: Arc::new(Mutex::new(None)),
+            worker: None,
+            log: Vec::new(),
+            state: PlayState::Building,
+            fingerprint: Self::fingerprint(project_state),
+        };
+
+        // The app runs in the workspace, so images load from its `assets` folder as in an export
+        let workspace = Self::workspace_dir();
+        let written = CodeValidator::write_scratch_crate(project_state, &workspace, PLAY_PACKAGE).and_then(|_| {
+            Compiler::copy_assets(project_state, &workspace).map_err(|e| format!("Failed to copy assets: {}", e))
+        });
+        if let Err(e) = written {
+            session.log.push(e);
+            session.state = PlayState::BuildFailed;
+            return session;
+        }
+
+        let stop = session.stop.clone();
+        let child = session.child.clone();
+        let on_update: UpdateCallback = Arc::new(on_update);
+        session.worker =
+            Some(std::thread::spawn(move || build_and_run(&workspace, &sender, &stop, &child, &on_update)));
+        session
+    }
+
+    /// Stop the build or the running app
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Ok(mut child) = self.child.lock() {
+            if let Some(child) = child.as_mut() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    /// Apply output and state changes from the worker
+    pub fn poll(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                PlayEvent::Log(line) => self.log.push(line),
+                PlayEvent::State(state) => self.state = state,
+            }
+        }
+        if self.log.len() > Self::MAX_LOG_LINES {
+            let excess = self.log.len() - Self::MAX_LOG_LINES;
+            self.log.drain(..excess);
+        }
+    }
+
+    pub fn state(&self) -> &PlayState {
+        &self.state
+    }
+
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    /// Whether the project changed since this session was started.
+    /// Cheap enough to poll: it hashes the saved project instead of generating code.
+    pub fn is_stale(&self, project_state: &ProjectState) -> bool {
+        self.fingerprint != Self::fingerprint(project_state)
+    }
+
+    /// Hash of everything that is saved except the editor's selection and open screen
+    fn fingerprint(project_state: &ProjectState) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        if let Ok(serde_json::Value::Object(mut fields)) = serde_json::to_value(project_state) {
+            fields.remove("selection");
+            fields.remove("active_screen");
+            serde_json::Value::Object(fields).to_string().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+impl Drop for PlaySession {
+    fn drop(&mut self) {
+        // Don't leave the app running after the editor closes
+        self.stop();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        let _ = std::fs::remove_dir_all(Self::workspace_dir());
+    }
+}
+
+/// Worker: `cargo build` the workspace, then run the binary until it exits or is stopped
+fn build_and_run(
+    workspace: &Path,
+    sender: &mpsc::Sender<PlayEvent>,
+    stop: &AtomicBool,
+    child: &Mutex<Option<Child>>,
+    on_update: &UpdateCallback,
+) {
+    let send = |event: PlayEvent| {
+        let _ = sender.send(event);
+        on_update();
+    };
+
+    // Share the validator's target directory so dependencies are only compiled once
+    let target_dir = CodeValidator::scratch_dir().join("target");
+    let mut build = Command::new("cargo");
+    build
+        .args(["build", "--color=never", "--manifest-path"])
+        .arg(workspace.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir);
+    match run_streaming(&mut build, sender, stop, child, on_update) {
+        Ok(Some(status)) if status.success() => {}
+        Ok(Some(_)) => return send(PlayEvent::State(PlayState::BuildFailed)),
+        Ok(None) => return send(PlayEvent::State(PlayState::Stopped)),
+        Err(e) => {
+            send(PlayEvent::Log(format!("Failed to run cargo build: {}", e)));
+            return send(PlayEvent::State(PlayState::BuildFailed));
+        }
+    }
+
+    let binary = target_dir
+        .join("debug")
+        .join(format!("{}{}", PLAY_PACKAGE, std::env::consts::EXE_SUFFIX));
+    send(PlayEvent::State(PlayState::Running));
+    let mut app = Command::new(&binary);
+    app.current_dir(workspace);
+    match run_streaming(&mut app, sender, stop, child, on_update) {
+        Ok(Some(status)) => send(PlayEvent::State(PlayState::Exited(status.code()))),
+        Ok(None) => send(PlayEvent::State(PlayState::Stopped)),
+        Err(e) => {
+            send(PlayEvent::Log(format!("Failed to launch {}: {}", binary.display(), e)));
+            send(PlayEvent::State(PlayState::Exited(None)));
+        }
+    }
+}
+
+/// Run a command, forwarding its stdout and stderr lines to the log.
+/// Returns `None` if it was stopped before it exited.
+fn run_streaming(
+    command: &mut Command,
+    sender: &mpsc::Sender<PlayEvent>,
+    stop: &AtomicBool,
+    child: &Mutex<Option<Child>>,
+    on_update: &UpdateCallback,
+) -> std::io::Result<Option<ExitStatus>> {
+    if stop.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+    let mut process = command.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    let mut readers = Vec::new();
+    if let Some(stdout) = process.stdout.take() {
+        readers.push(forward_lines(stdout, sender.clone(), on_update.clone()));
+    }
+    if let Some(stderr) = process.stderr.take() {
+        readers.push(forward_lines(stderr, sender.clone(), on_update.clone()));
+    }
+    *child.lock().unwrap_or_else(|e| e.into_inner()) = Some(process);
+
+    let status = loop {
+        let mut slot = child.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(process) = slot.as_mut() else {
+            break None;
+        };
+        if stop.load(Ordering::Relaxed) {
+            let _ = process.kill();
+            let _ = process.wait();
+            *slot = None;
+            break None;
+        }
+        if let Some(status) = process.try_wait()? {
+            *slot = None;
+            break Some(status);
+        }
+        drop(slot);
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    // Let the readers flush the last lines before reporting the new state.
+    // A killed cargo may leave rustc holding the pipes, so don't wait after a stop.
+    if status.is_some() {
+        for reader in readers {
+            let _ = reader.join();
+        }
+    }
+    Ok(status)
+}
+
+fn forward_lines(
+    stream: impl Read + Send + 'static,
+    sender: mpsc::Sender<PlayEvent>,
+    on_update: UpdateCallback,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if sender.send(PlayEvent::Log(line)).is_err() {
+                break;
+            }
+            on_update();
+        }
+    })
+}
@@ -45,8 +45,10 @@ impl SyntaxHighlighter {
             None => {
                 // Fallback: return plain text with fallback color
                 let mut job = LayoutJob::default();
-                let mut format = TextFormat::default();
-                format.color = fallback_color;
+                let format = TextFormat {
+                    color: fallback_color,
+                    ..Default::default()
+                };
                 job.append(code, 0.0, format);
                 return job;
             }
@@ -65,23 +67,29 @@ impl SyntaxHighlighter {
                         fallback_color
                     };
 
-                    let mut format = TextFormat::default();
-                    format.color = color;
+                    let mut format = TextFormat {
+                        color,
+                        ..Default::default()
+                    };
                     format.font_id.family = egui::FontFamily::Monospace;
 
                     job.append(text, 0.0, format);
                 }
             } else {
-                let mut format = TextFormat::default();
-                format.color = fallback_color;
+                let mut format = TextFormat {
+                    color: fallback_color,
+                    ..Default::default()
+                };
                 format.font_id.family = egui::FontFamily::Monospace;
 
                 job.append(line, 0.0, format);
             }
 
             // Add newline at end of line
-            let mut format = TextFormat::default();
-            format.color = fallback_color;
+            let mut format = TextFormat {
+                color: fallback_color,
+                ..Default::default()
+            };
             format.font_id.family = egui::FontFamily::Monospace;
 
             job.append("\n", 0.0, format);
@@ -88,6 +88,7 @@ impl WidgetLabels {
             "Spinner" => "◌ Spinner",
             "Hyperlink" => "🔗 Hyperlink",
             "Color Picker" => "🎨 Color Picker",
+            "Custom Code" => "{ } Custom Code",
 
             // Default
             _ => "Widget",
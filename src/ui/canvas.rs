@@ -1,5 +1,6 @@
 use super::EditorContext;
-use crate::theme::AetherColors;
+use crate::preview::{PreviewRequest, PreviewState};
+use crate::theme::{self, AetherColors};
 use egui::{Color32, CornerRadius, RichText, Ui};
 
 pub fn render_canvas(ui: &mut Ui, ctx: &mut EditorContext) {
@@ -58,6 +59,26 @@ pub fn render_canvas(ui: &mut Ui, ctx: &mut EditorContext) {
                         .color(muted_color),
                 );
 
+                ui.separator();
+
+                // Preview toggle: run the design against a live copy of the variables
+                let mut previewing = ctx.preview.is_some();
+                if ui
+                    .toggle_value(&mut previewing, "▶ Preview")
+                    .on_hover_text("Interact with the design, simulating bindings and actions")
+                    .changed()
+                {
+                    *ctx.preview = previewing.then(|| PreviewState::new(ctx.project_state));
+                }
+                if ctx.preview.is_some()
+                    && ui
+                        .button("⟲ Reset")
+                        .on_hover_text("Restore the initial variable values")
+                        .clicked()
+                {
+                    *ctx.preview = Some(PreviewState::new(ctx.project_state));
+                }
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(
                         RichText::new(format!(
@@ -71,6 +92,12 @@ pub fn render_canvas(ui: &mut Ui, ctx: &mut EditorContext) {
             });
             ui.add_space(8.0);
 
+            if let Some(preview) = ctx.preview.as_mut() {
+                preview.sync(ctx.project_state);
+                render_preview_log(ui, preview, &ctx.project_state.screens[preview.current_screen].name);
+                ui.add_space(4.0);
+            }
+
             // Canvas content area with scroll and zoom
             let canvas_bg = if is_light {
                 Color32::from_rgb(240, 240, 245)
@@ -143,9 +170,21 @@ pub fn render_canvas(ui: &mut Ui, ctx: &mut EditorContext) {
                             ui.add_space(8.0 * zoom);
 
                             // Render the widget tree
-                            ctx.project_state
-                                .root_node
-                                .render_editor(ui, &mut ctx.project_state.selection);
+                            let state = &mut *ctx.project_state;
+                            match ctx.preview.as_mut() {
+                                Some(preview) => {
+                                    state.screens[preview.current_screen].root.render_preview(ui, preview);
+                                    for request in preview.take_requests() {
+                                        match request {
+                                            PreviewRequest::OpenUrl(url) => ui.ctx().open_url(egui::OpenUrl::new_tab(url)),
+                                            PreviewRequest::CopyText(text) => ui.ctx().copy_text(text),
+                                        }
+                                    }
+                                }
+                                None => state.screens[state.active_screen]
+                                    .root
+                                    .render_editor(ui, &mut state.selection),
+                            }
 
                             // Restore original text styles
                             ui.style_mut().text_styles = original_text_style;
@@ -172,3 +211,30 @@ pub fn render_canvas(ui: &mut Ui, ctx: &mut EditorContext) {
             }
         });
 }
+
+/// Actions fired while previewing, and which screen is shown
+fn render_preview_log(ui: &mut Ui, preview: &PreviewState, screen_name: &str) {
+    egui::CollapsingHeader::new(
+        RichText::new(format!("Preview Log · Screen: {}", screen_name)).size(11.0),
+    )
+    .id_salt("preview_log")
+    .default_open(true)
+    .show(ui, |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(80.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                if preview.log().is_empty() {
+                    ui.label(
+                        RichText::new("No actions fired yet")
+                            .size(11.0)
+                            .color(theme::muted_color(ui.ctx())),
+                    );
+                }
+                for entry in preview.log() {
+                    ui.label(RichText::new(entry.to_string()).monospace().size(11.0));
+                }
+            });
+    });
+}
@@ -16,26 +16,23 @@ pub fn render_code_preview(ui: &mut Ui, ctx: &mut EditorContext) {
     ui.add_space(8.0);
 
     egui::ScrollArea::vertical().show(ui, |ui| {
-        // Cargo.toml
-        render_code_section(
-            ui,
-            "📦 Cargo.toml",
-            &Compiler::generate_cargo_toml(&ctx.project_state.project_name),
-        );
+        for (i, file) in Compiler::generate_project_files(ctx.project_state).iter().enumerate() {
+            if i > 0 {
+                ui.add_space(8.0);
+            }
 
-        ui.add_space(8.0);
-
-        // main.rs
-        render_code_section(ui, "🚀 src/main.rs", &Compiler::generate_main_rs());
-
-        ui.add_space(8.0);
-
-        // app.rs
-        render_code_section(
-            ui,
-            "⚙️ src/app.rs",
-            &Compiler::generate_app_rs(&ctx.project_state),
-        );
+            let icon = if file.path == "Cargo.toml" {
+                "📦"
+            } else if file.path == "src/main.rs" {
+                "🚀"
+            } else if file.user_owned {
+                // Created once, then owned by the user
+                "✍"
+            } else {
+                "⚙️"
+            };
+            render_code_section(ui, &format!("{} {}", icon, file.path), &file.contents);
+        }
     });
 }
 
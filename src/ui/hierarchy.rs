@@ -1,5 +1,5 @@
 use super::EditorContext;
-use crate::model::WidgetNode;
+use crate::model::{ChildLayout, ChildPosition, WidgetNode};
 use crate::theme::{self, AetherColors};
 use egui::{Color32, RichText, Ui};
 use std::collections::HashSet;
@@ -11,16 +11,59 @@ struct HierarchyDragPayload {
     widget_id: Uuid,
 }
 
+/// Screen picker with rename, add and delete controls
+fn render_screen_switcher(ui: &mut Ui, ctx: &mut EditorContext) {
+    let state = &mut *ctx.project_state;
+    let mut active = state.active_screen;
+
+    ui.horizontal(|ui| {
+        ui.label(theme::subheading("Screen"));
+        egui::ComboBox::from_id_salt("active_screen")
+            .selected_text(state.screens[active].name.as_str())
+            .show_ui(ui, |ui| {
+                for (i, screen) in state.screens.iter().enumerate() {
+                    ui.selectable_value(&mut active, i, screen.name.as_str());
+                }
+            });
+        if ui.small_button("+").on_hover_text("Add screen").clicked() {
+            active = state.add_screen();
+        }
+        let can_delete = state.screens.len() > 1;
+        if ui
+            .add_enabled(can_delete, egui::Button::new("🗑").small())
+            .on_hover_text("Delete screen")
+            .clicked()
+        {
+            state.remove_screen(active);
+            active = state.active_screen;
+        }
+    });
+    if active != state.active_screen {
+        state.set_active_screen(active);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Name:");
+        let mut name = state.screens[state.active_screen].name.clone();
+        if ui.text_edit_singleline(&mut name).changed() {
+            state.rename_screen(state.active_screen, &name);
+        }
+    });
+}
+
 /// Position for drop operations
 #[derive(Clone, Copy, Debug)]
 enum DropPosition {
     Before,
     After,
-    Into,
+    /// At the end of a slot of the container
+    Into(usize),
 }
 
 pub fn render_hierarchy(ui: &mut Ui, ctx: &mut EditorContext) {
     ui.add_space(4.0);
+    render_screen_switcher(ui, ctx);
+    ui.add_space(8.0);
     ui.label(theme::heading("Widget Tree"));
     ui.add_space(4.0);
 
@@ -86,7 +129,7 @@ pub fn render_hierarchy(ui: &mut Ui, ctx: &mut EditorContext) {
             // Delete key to remove selected widget
             if i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace) {
                 if let Some(current) = current_selected {
-                    if current != ctx.project_state.root_node.id() {
+                    if current != ctx.project_state.root().id() {
                         ctx.project_state.delete_widget(current);
                         ctx.project_state.selection.clear();
                     }
@@ -101,10 +144,12 @@ pub fn render_hierarchy(ui: &mut Ui, ctx: &mut EditorContext) {
     // Tree view with styled frame
     theme::section_frame(ui.ctx()).show(ui, |ui| {
         egui::ScrollArea::vertical().show(ui, |ui| {
+            let state = &mut *ctx.project_state;
             draw_hierarchy_node_styled(
                 ui,
-                ctx.project_state.root_node.as_ref(),
-                &mut ctx.project_state.selection,
+                state.screens[state.active_screen].root.as_ref(),
+                ChildLayout::Flow,
+                &mut state.selection,
                 0,
                 &mut pending_drop,
             );
@@ -114,7 +159,7 @@ pub fn render_hierarchy(ui: &mut Ui, ctx: &mut EditorContext) {
     // Handle any pending drop operations
     if let Some((source_id, target_id, position)) = pending_drop {
         // Only proceed if we have a valid source (not the root)
-        if source_id != ctx.project_state.root_node.id() && source_id != target_id {
+        if source_id != ctx.project_state.root().id() && source_id != target_id {
             match position {
                 DropPosition::Before => {
                     ctx.project_state.move_widget_before(source_id, target_id);
@@ -122,31 +167,92 @@ pub fn render_hierarchy(ui: &mut Ui, ctx: &mut EditorContext) {
                 DropPosition::After => {
                     ctx.project_state.move_widget_after(source_id, target_id);
                 }
-                DropPosition::Into => {
+                DropPosition::Into(slot) => {
                     // Move into container at end
-                    ctx.project_state.reparent_widget(source_id, target_id, usize::MAX);
+                    let position = ChildPosition { parent: target_id, slot, index: usize::MAX };
+                    ctx.project_state.move_widget_to(source_id, position);
                 }
             }
         }
     }
 }
 
-/// Styled hierarchy node rendering with icons, depth indication, and DnD support
+/// Drop zone for moving a dragged widget into a container. Returns the dropped widget.
+fn render_drop_zone(ui: &mut Ui, container_id: Uuid, hint: &str) -> Option<Uuid> {
+    // Only show drop zone if something is being dragged
+    let is_dragging = ui.ctx().dragged_id().is_some();
+    let zone_height = if is_dragging { 16.0 } else { 4.0 };
+
+    let (drop_rect, drop_response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), zone_height),
+        egui::Sense::hover(),
+    );
+
+    // Check for hover payload
+    if let Some(hovered_payload) = drop_response.dnd_hover_payload::<HierarchyDragPayload>() {
+        // Don't allow dropping into self
+        if hovered_payload.widget_id != container_id {
+            ui.painter().rect_filled(
+                drop_rect,
+                2.0,
+                Color32::from_rgba_unmultiplied(100, 200, 100, 120),
+            );
+            ui.painter().rect_stroke(
+                drop_rect,
+                2.0,
+                egui::Stroke::new(2.0, AetherColors::ACCENT),
+                egui::StrokeKind::Inside,
+            );
+
+            // Show hint text
+            ui.painter().text(
+                drop_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("▸ Drop into {}", hint),
+                egui::FontId::proportional(9.0),
+                AetherColors::ACCENT,
+            );
+
+            // Handle drop
+            if let Some(released) = drop_response.dnd_release_payload::<HierarchyDragPayload>() {
+                return Some(released.widget_id);
+            }
+        }
+    } else if is_dragging {
+        // Show faint hint when dragging but not hovering
+        ui.painter().rect_stroke(
+            drop_rect,
+            2.0,
+            egui::Stroke::new(1.0, Color32::from_rgba_unmultiplied(100, 200, 100, 80)),
+            egui::StrokeKind::Inside,
+        );
+    }
+    None
+}
+
+/// Styled hierarchy node rendering with icons, depth indication, and DnD support.
+/// `layout` is the node's layout data in its parent, shown for positioned children.
 fn draw_hierarchy_node_styled(
     ui: &mut Ui,
     node: &dyn WidgetNode,
+    layout: ChildLayout,
     selection: &mut HashSet<Uuid>,
     depth: usize,
     pending_drop: &mut Option<(Uuid, Uuid, DropPosition)>,
 ) {
     let id = node.id();
     let is_selected = selection.contains(&id);
-    let label = theme::WidgetLabels::get(node.name());
+    let label = match layout {
+        ChildLayout::Placed { x, y, .. } => format!("{}  @ {:.0}, {:.0}", theme::WidgetLabels::get(node.name()), x, y),
+        ChildLayout::Flow => theme::WidgetLabels::get(node.name()).to_string(),
+    };
     let category_color = theme::widget_category_color(node.name());
 
-    let children = node.children();
-    let has_children = children.map_or(false, |c| !c.is_empty());
-    let is_container = node.children().is_some();
+    let slots = node.slots();
+    // Named slots (e.g. tabs) are listed even when empty so widgets can be dropped into them
+    let named_slots = slots.iter().any(|slot| !slot.name.is_empty());
+    let has_children = named_slots || slots.iter().any(|slot| !slot.children.is_empty());
+    let is_container = !slots.is_empty();
 
     // Indent based on depth
     let indent = depth as f32 * 12.0;
@@ -242,61 +348,29 @@ fn draw_hierarchy_node_styled(
         let state_id = ui.make_persistent_id(id);
         if egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), state_id, true).is_open() {
             // Container drop zone (for dropping INTO this container)
-            if is_container {
-                // Only show drop zone if something is being dragged
-                let is_dragging = ui.ctx().dragged_id().is_some();
-                let zone_height = if is_dragging { 16.0 } else { 4.0 };
-
-                let (drop_rect, drop_response) = ui.allocate_exact_size(
-                    egui::vec2(ui.available_width(), zone_height),
-                    egui::Sense::hover(),
-                );
-
-                // Check for hover payload
-                if let Some(hovered_payload) = drop_response.dnd_hover_payload::<HierarchyDragPayload>() {
-                    // Don't allow dropping into self
-                    if hovered_payload.widget_id != id {
-                        ui.painter().rect_filled(
-                            drop_rect,
-                            2.0,
-                            Color32::from_rgba_unmultiplied(100, 200, 100, 120),
-                        );
-                        ui.painter().rect_stroke(
-                            drop_rect,
-                            2.0,
-                            egui::Stroke::new(2.0, AetherColors::ACCENT),
-                            egui::StrokeKind::Inside,
-                        );
-
-                        // Show hint text
-                        let text = format!("▸ Drop into {}", label);
-                        ui.painter().text(
-                            drop_rect.center(),
-                            egui::Align2::CENTER_CENTER,
-                            text,
-                            egui::FontId::proportional(9.0),
-                            AetherColors::ACCENT,
-                        );
-
-                        // Handle drop
-                        if let Some(released) = drop_response.dnd_release_payload::<HierarchyDragPayload>() {
-                            *pending_drop = Some((released.widget_id, id, DropPosition::Into));
-                        }
-                    }
-                } else if is_dragging {
-                    // Show faint hint when dragging but not hovering
-                    ui.painter().rect_stroke(
-                        drop_rect,
-                        2.0,
-                        egui::Stroke::new(1.0, Color32::from_rgba_unmultiplied(100, 200, 100, 80)),
-                        egui::StrokeKind::Inside,
-                    );
+            if is_container && !named_slots {
+                if let Some(dropped) = render_drop_zone(ui, id, theme::WidgetLabels::get(node.name())) {
+                    *pending_drop = Some((dropped, id, DropPosition::Into(node.default_slot())));
                 }
             }
 
-            if let Some(children) = children {
-                for child in children {
-                    draw_hierarchy_node_styled(ui, child.as_ref(), selection, depth + 1, pending_drop);
+            for (slot_index, slot) in slots.iter().enumerate() {
+                let child_depth = if named_slots {
+                    // Slot header with its own drop zone
+                    ui.horizontal(|ui| {
+                        ui.add_space(indent + 28.0);
+                        ui.label(RichText::new(format!("▾ {}", slot.name)).color(theme::muted_color(ui.ctx())));
+                    });
+                    if let Some(dropped) = render_drop_zone(ui, id, &slot.name) {
+                        *pending_drop = Some((dropped, id, DropPosition::Into(slot_index)));
+                    }
+                    depth + 2
+                } else {
+                    depth + 1
+                };
+                for (index, child) in slot.children.widgets().into_iter().enumerate() {
+                    let layout = slot.children.layout(index);
+                    draw_hierarchy_node_styled(ui, child, layout, selection, child_depth, pending_drop);
                 }
             }
         }
@@ -0,0 +1,40 @@
+use super::EditorContext;
+use crate::theme;
+use egui::{RichText, Ui};
+
+/// Every undo step, oldest first. Clicking one undoes or redoes up to it.
+pub fn render_history(ui: &mut Ui, ctx: &mut EditorContext) {
+    ui.add_space(4.0);
+    ui.label(theme::heading("History"));
+    ui.add_space(4.0);
+    ui.label(
+        RichText::new("Click a step to go back or forward to it")
+            .size(11.0)
+            .color(theme::muted_color(ui.ctx())),
+    );
+    ui.add_space(8.0);
+
+    let position = ctx.history.position();
+    let labels: Vec<String> = std::iter::once("Initial state".to_string())
+        .chain(ctx.history.entries().map(|entry| entry.label.clone()))
+        .collect();
+
+    let mut target = None;
+    egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+        for (step, label) in labels.iter().enumerate() {
+            // Undone steps stay listed, greyed out, until a new edit replaces them
+            let text = if step > position {
+                RichText::new(label).color(theme::muted_color(ui.ctx())).italics()
+            } else {
+                RichText::new(label)
+            };
+            if ui.selectable_label(step == position, text).clicked() && step != position {
+                target = Some(step);
+            }
+        }
+    });
+
+    if let Some(step) = target {
+        ctx.history.go_to(ctx.project_state, step);
+    }
+}
@@ -1,6 +1,8 @@
 use super::EditorContext;
+use crate::model::{CommonProperties, Flag, PropertyEdit, SharedProperty, VariableType};
 use crate::theme;
 use egui::{RichText, Ui};
+use uuid::Uuid;
 
 pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
     ui.add_space(4.0);
@@ -23,6 +25,43 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
         );
         ui.add_space(8.0);
 
+        // Properties the selected widgets share, edited on all of them at once
+        let ids: Vec<Uuid> = ctx
+            .project_state
+            .get_all_widget_ids()
+            .into_iter()
+            .filter(|id| ctx.project_state.selection.contains(id))
+            .collect();
+        let shared = ctx.project_state.shared_properties(&ids);
+        theme::section_frame(ui.ctx()).show(ui, |ui| {
+            ui.label(theme::subheading("Shared Properties"));
+            ui.add_space(6.0);
+            if shared.is_empty() {
+                ui.label(
+                    RichText::new("The selected widgets have no properties in common")
+                        .color(theme::muted_color(ui.ctx()))
+                        .size(11.0),
+                );
+            }
+            for property in shared {
+                let SharedProperty { descriptor, mut value, mixed } = property;
+                ui.push_id(descriptor.name, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", descriptor.label));
+                        if crate::widgets::property_editor(ui, &descriptor.kind, &mut value, mixed) {
+                            ctx.project_state.pending_property_edit = Some(PropertyEdit {
+                                widgets: ids.clone(),
+                                property: descriptor.name.to_string(),
+                                value,
+                            });
+                            ui.ctx().request_repaint();
+                        }
+                    });
+                });
+            }
+        });
+        ui.add_space(8.0);
+
         // Show common actions for all selected widgets
         theme::section_frame(ui.ctx()).show(ui, |ui| {
             ui.label(theme::subheading("Bulk Actions"));
@@ -38,6 +77,17 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
         });
     } else if let Some(id) = ctx.project_state.selection.iter().next().cloned() {
         let known_vars: Vec<String> = ctx.project_state.variables.keys().cloned().collect();
+        let mut bool_vars: Vec<String> = ctx
+            .project_state
+            .variables
+            .values()
+            .filter(|v| v.v_type == VariableType::Boolean)
+            .map(|v| v.name.clone())
+            .collect();
+        bool_vars.sort();
+        let known_screens = ctx.project_state.screens.iter().map(|s| s.name.clone()).collect();
+        crate::widgets::set_known_screens(ui.ctx(), known_screens);
+        crate::widgets::set_known_action_targets(ui.ctx(), ctx.project_state.action_targets());
         // Build (name, filename) pairs for asset selection
         let known_assets: Vec<(String, String)> = ctx.project_state.assets.assets.values()
             .map(|asset| {
@@ -48,7 +98,7 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
                 (asset.name.clone(), filename)
             })
             .collect();
-        let is_root = id == ctx.project_state.root_node.id();
+        let is_root = id == ctx.project_state.root().id();
 
         // Get widget name for header
         let widget_name = if let Some(node) = ctx.project_state.find_node_mut(id) {
@@ -104,6 +154,13 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
                 node.inspect(ui, &known_vars, &known_assets);
             });
 
+            ui.add_space(8.0);
+            theme::section_frame(ui.ctx()).show(ui, |ui| {
+                ui.label(theme::subheading("Common"));
+                ui.add_space(6.0);
+                render_common_properties(ui, node.common_mut(), &bool_vars);
+            });
+
             // Widget actions (not for root)
             if !is_root {
                 ui.add_space(8.0);
@@ -129,10 +186,9 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
                             RichText::new("✕ Delete Widget").color(theme::error_color(ui.ctx())),
                         ))
                         .clicked()
+                        && ctx.project_state.delete_widget(id)
                     {
-                        if ctx.project_state.delete_widget(id) {
-                            ctx.project_state.selection.clear();
-                        }
+                        ctx.project_state.selection.clear();
                     }
                 });
             }
@@ -159,3 +215,37 @@ pub fn render_inspector(ui: &mut Ui, ctx: &mut EditorContext) {
         });
     });
 }
+
+/// Tooltip, enabled state and visibility, which every widget has
+fn render_common_properties(ui: &mut Ui, common: &mut CommonProperties, bool_vars: &[String]) {
+    ui.horizontal(|ui| {
+        ui.label("Tooltip:");
+        ui.text_edit_singleline(&mut common.tooltip)
+            .on_hover_text(crate::widgets::TEXT_PLACEHOLDER_HINT);
+    });
+    render_flag(ui, "Enabled:", &mut common.enabled, bool_vars);
+    render_flag(ui, "Visible:", &mut common.visible, bool_vars);
+}
+
+/// Pick a fixed value for a flag, or a boolean variable for it to follow
+fn render_flag(ui: &mut Ui, label: &str, flag: &mut Flag, bool_vars: &[String]) {
+    let text = |flag: &Flag| match flag {
+        Flag::Static(true) => "Always".to_string(),
+        Flag::Static(false) => "Never".to_string(),
+        Flag::Bound(var) => format!("When {}", var),
+    };
+    ui.horizontal(|ui| {
+        ui.label(label);
+        egui::ComboBox::from_id_salt(label)
+            .selected_text(text(flag))
+            .show_ui(ui, |ui| {
+                for option in [Flag::Static(true), Flag::Static(false)]
+                    .into_iter()
+                    .chain(bool_vars.iter().map(|var| Flag::Bound(var.clone())))
+                {
+                    let option_text = text(&option);
+                    ui.selectable_value(flag, option, option_text);
+                }
+            });
+    });
+}
@@ -1,4 +1,6 @@
+use crate::history::History;
 use crate::model::ProjectState;
+use crate::preview::PreviewState;
 use crate::theme::ThemeMode;
 use crate::validator::ValidationStatus;
 use egui::Vec2;
@@ -7,17 +9,23 @@ pub mod assets;
 pub mod canvas;
 pub mod code_preview;
 pub mod hierarchy;
+pub mod history;
 pub mod inspector;
 pub mod palette;
+pub mod play_log;
 pub mod variables;
 
 /// Shared context passed to all UI panels
 #[allow(dead_code)]
 pub struct EditorContext<'a> {
     pub project_state: &'a mut ProjectState,
+    /// Undo history; the app records the frame's changes to the project after the panels run
+    pub history: &'a mut History,
     pub validation_status: &'a mut ValidationStatus,
     pub theme_mode: &'a mut ThemeMode,
     pub canvas_zoom: &'a mut f32,
     pub canvas_pan: &'a mut Vec2,
     pub clipboard: &'a mut Option<String>,
+    /// Running canvas preview, if the Preview toggle is on
+    pub preview: &'a mut Option<PreviewState>,
 }
\ No newline at end of file
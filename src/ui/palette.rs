@@ -56,6 +56,7 @@ pub fn render_palette(ui: &mut Ui, ctx: &mut EditorContext) {
                 "Spinner",
                 "Hyperlink",
                 "Color Picker",
+                "Custom Code",
             ],
             AetherColors::DISPLAY_COLOR,
         ) {
@@ -67,22 +68,15 @@ pub fn render_palette(ui: &mut Ui, ctx: &mut EditorContext) {
     if let Some(widget_type) = widget_to_add {
         if let Some(new_widget) = widgets::create_widget_by_name(&widget_type) {
             // Try to add to selected container, or fall back to root
-            let target_id = ctx.project_state.selection.iter().next().cloned();
-
-            if let Some(id) = target_id {
-                // Check if selected widget is a container
-                if let Some(node) = ctx.project_state.find_node_mut(id) {
-                    if let Some(children) = node.children_mut() {
-                        children.push(new_widget);
-                        return;
-                    }
-                }
-            }
-
-            // Fall back to root
-            if let Some(children) = ctx.project_state.root_node.children_mut() {
-                children.push(new_widget);
-            }
+            let target_id = ctx
+                .project_state
+                .selection
+                .iter()
+                .next()
+                .copied()
+                .filter(|id| ctx.project_state.is_container(*id))
+                .unwrap_or_else(|| ctx.project_state.root().id());
+            ctx.project_state.add_widget(target_id, new_widget);
         }
     }
 }
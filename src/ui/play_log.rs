@@ -0,0 +1,50 @@
+use crate::runner::{PlaySession, PlayState};
+use crate::theme;
+use egui::{RichText, Ui};
+
+/// What the user asked the play session to do
+pub enum PlayCommand {
+    Stop,
+    Rebuild,
+}
+
+/// Build and app output of a play session, with stop and rebuild controls
+pub fn render_play_log(ui: &mut Ui, session: &PlaySession, auto_rebuild: &mut bool) -> Option<PlayCommand> {
+    let mut command = None;
+    let state = session.state();
+
+    ui.horizontal(|ui| {
+        if state.is_active() {
+            ui.add(egui::Spinner::new().size(12.0));
+        }
+        let color = match state {
+            PlayState::Running => theme::success_color(ui.ctx()),
+            PlayState::BuildFailed => theme::error_color(ui.ctx()),
+            _ => theme::muted_color(ui.ctx()),
+        };
+        ui.label(RichText::new(state.display_text()).color(color));
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("⟳ Rebuild").clicked() {
+                command = Some(PlayCommand::Rebuild);
+            }
+            if ui.add_enabled(state.is_active(), egui::Button::new("⏹ Stop")).clicked() {
+                command = Some(PlayCommand::Stop);
+            }
+            ui.checkbox(auto_rebuild, "Rebuild on change")
+                .on_hover_text("Restart the app whenever the generated code changes");
+        });
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for line in session.log() {
+                ui.label(RichText::new(line).monospace().size(11.0));
+            }
+        });
+
+    command
+}
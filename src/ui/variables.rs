@@ -1,5 +1,7 @@
 use super::EditorContext;
-use crate::model::{Variable, VariableType};
+use crate::model::{
+    format_color, parse_color, CustomType, CustomTypeKind, ProjectState, StructField, Variable, VariableType,
+};
 use crate::theme;
 use egui::{RichText, Ui};
 
@@ -25,80 +27,403 @@ pub fn render_variables(ui: &mut Ui, ctx: &mut EditorContext) {
 
     ui.add_space(8.0);
 
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new(format!("Custom Types ({})", ctx.project_state.types.len()))
+            .id_salt("custom_types")
+            .show(ui, |ui| render_custom_types(ui, ctx.project_state));
+        ui.add_space(4.0);
+        render_variable_list(ui, ctx);
+    });
+}
+
+fn render_variable_list(ui: &mut Ui, ctx: &mut EditorContext) {
     // Variable list
     let mut keys: Vec<String> = ctx.project_state.variables.keys().cloned().collect();
     keys.sort();
 
     let mut to_remove = None;
 
-    egui::ScrollArea::vertical().show(ui, |ui| {
-        for key in keys {
-            theme::section_frame(ui.ctx()).show(ui, |ui| {
-                if let Some(var) = ctx.project_state.variables.get_mut(&key) {
-                    ui.horizontal(|ui| {
-                        ui.label(RichText::new(&var.name).strong());
-                        ui.with_layout(
-                            egui::Layout::right_to_left(egui::Align::Center),
-                            |ui| {
-                                if ui
-                                    .add(
-                                        egui::Button::new(
-                                            RichText::new("✕").color(theme::error_color(ui.ctx())),
-                                        )
-                                        .small(),
+    for key in keys {
+        theme::section_frame(ui.ctx()).show(ui, |ui| {
+            if let Some(var) = ctx.project_state.variables.get_mut(&key) {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&var.name).strong());
+                    ui.with_layout(
+                        egui::Layout::right_to_left(egui::Align::Center),
+                        |ui| {
+                            if ui
+                                .add(
+                                    egui::Button::new(
+                                        RichText::new("✕").color(theme::error_color(ui.ctx())),
                                     )
-                                    .clicked()
-                                {
-                                    to_remove = Some(key.clone());
-                                }
-                            },
-                        );
-                    });
+                                    .small(),
+                                )
+                                .clicked()
+                            {
+                                to_remove = Some(key.clone());
+                            }
+                        },
+                    );
+                });
 
-                    ui.add_space(4.0);
+                ui.add_space(4.0);
 
-                    ui.horizontal(|ui| {
-                        ui.label(RichText::new("Type:").size(11.0).color(theme::muted_color(ui.ctx())));
-                        egui::ComboBox::from_id_salt(format!("type_{}", key))
-                            .selected_text(format!("{}", var.v_type))
-                            .width(80.0)
-                            .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut var.v_type,
-                                    VariableType::String,
-                                    "String",
-                                );
-                                ui.selectable_value(
-                                    &mut var.v_type,
-                                    VariableType::Integer,
-                                    "Integer",
-                                );
-                                ui.selectable_value(
-                                    &mut var.v_type,
-                                    VariableType::Boolean,
-                                    "Boolean",
-                                );
-                                ui.selectable_value(
-                                    &mut var.v_type,
-                                    VariableType::Float,
-                                    "Float",
-                                );
-                            });
+                let types = &ctx.project_state.types;
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Type:").size(11.0).color(theme::muted_color(ui.ctx())));
+                    if type_picker(ui, format!("type_{}", key), &mut var.v_type, types) {
+                        var.value = var.v_type.default_value(types);
+                    }
 
+                    if var.v_type.is_scalar() {
                         ui.label(
                             RichText::new("Value:")
                                 .size(11.0)
                                 .color(theme::muted_color(ui.ctx())),
                         );
                         ui.add(egui::TextEdit::singleline(&mut var.value).desired_width(80.0));
+                    }
+                });
+                render_initial_value(ui, &key, var, types);
+
+                // Value while the canvas preview is running
+                if let Some(live) = ctx.preview.as_ref().and_then(|p| p.variables.get(&key)) {
+                    let live = match live.v_type {
+                        VariableType::String => format!("{:?}", live.value),
+                        _ => live.value.clone(),
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Live:").size(11.0).color(theme::muted_color(ui.ctx())));
+                        ui.label(
+                            RichText::new(live)
+                                .monospace()
+                                .color(theme::success_color(ui.ctx())),
+                        );
+                    });
+                }
+            }
+        });
+        ui.add_space(4.0);
+    }
+
+    if let Some(key) = to_remove {
+        ctx.project_state.variables.remove(&key);
+    }
+}
+
+/// Editors for a variable's initial value that don't fit on the type row:
+/// colors, enum variants, lists and structs. Values that don't parse are shown as text.
+fn render_initial_value(ui: &mut Ui, key: &str, var: &mut Variable, types: &[CustomType]) {
+    match var.v_type.parse_value(&var.value, types) {
+        Ok(mut value) => {
+            if var.v_type.is_scalar() {
+                return;
+            }
+            let id = egui::Id::new("initial_value").with(key);
+            if value_editor(ui, id, &var.v_type, &mut value, types) {
+                var.value = var.v_type.value_text(&value);
+            }
+        }
+        Err(e) => {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("⚠ {}", e)).size(11.0).color(theme::error_color(ui.ctx())));
+                if ui.small_button("Reset").clicked() {
+                    var.value = var.v_type.default_value(types);
+                }
+            });
+            if !var.v_type.is_scalar() {
+                ui.add(
+                    egui::TextEdit::multiline(&mut var.value)
+                        .code_editor()
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+            }
+        }
+    }
+}
+
+/// Pick a variable type: a built-in type, a list of another type, or a project-defined type.
+/// Returns true if the type changed.
+fn type_picker(ui: &mut Ui, id_salt: impl std::hash::Hash, v_type: &mut VariableType, types: &[CustomType]) -> bool {
+    let id = egui::Id::new(id_salt);
+    let mut changed = false;
+
+    let selected_text = match &*v_type {
+        VariableType::List(_) => "List".to_string(),
+        other => other.to_string(),
+    };
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected_text)
+        .width(80.0)
+        .show_ui(ui, |ui| {
+            let builtins = [
+                VariableType::String,
+                VariableType::Integer,
+                VariableType::Boolean,
+                VariableType::Float,
+                VariableType::Color,
+            ];
+            for option in builtins {
+                let label = option.to_string();
+                let hover = option.rust_name().unwrap_or_default();
+                changed |= ui.selectable_value(v_type, option, label).on_hover_text(hover).changed();
+            }
+            ui.separator();
+            // Concrete numeric types, for state that needs a specific width
+            for option in [
+                VariableType::U8,
+                VariableType::U32,
+                VariableType::I64,
+                VariableType::Usize,
+                VariableType::F32,
+            ] {
+                let label = option.to_string();
+                changed |= ui.selectable_value(v_type, option, label).changed();
+            }
+            let is_list = matches!(v_type, VariableType::List(_));
+            if ui.selectable_label(is_list, "List").clicked() && !is_list {
+                *v_type = VariableType::List(Box::new(VariableType::String));
+                changed = true;
+            }
+            if !types.is_empty() {
+                ui.separator();
+            }
+            for t in types {
+                changed |= ui.selectable_value(v_type, t.variable_type(), &t.name).changed();
+            }
+        });
+
+    if let VariableType::List(item) = v_type {
+        ui.label(RichText::new("of").size(11.0).color(theme::muted_color(ui.ctx())));
+        changed |= type_picker(ui, id.with("item"), item, types);
+    }
+    changed
+}
+
+/// Edit a structured value (see `VariableType::default_json`) in place.
+/// Returns true if it changed.
+fn value_editor(
+    ui: &mut Ui,
+    id: egui::Id,
+    v_type: &VariableType,
+    value: &mut serde_json::Value,
+    types: &[CustomType],
+) -> bool {
+    use serde_json::Value;
+
+    if v_type.check_json(value, types).is_err() {
+        *value = v_type.default_json(types);
+    }
+
+    match v_type {
+        VariableType::String => {
+            let mut text = value.as_str().unwrap_or_default().to_string();
+            let changed = ui.add(egui::TextEdit::singleline(&mut text).desired_width(100.0)).changed();
+            *value = Value::String(text);
+            changed
+        }
+        VariableType::Integer | VariableType::U8 | VariableType::U32 | VariableType::I64 | VariableType::Usize => {
+            let (min, max) = v_type.integer_range().unwrap_or_default();
+            if min >= 0 {
+                let mut number = value.as_u64().unwrap_or_default();
+                let range = min as u64..=max as u64;
+                let changed = ui.add(egui::DragValue::new(&mut number).range(range)).changed();
+                *value = Value::from(number);
+                changed
+            } else {
+                let mut number = value.as_i64().unwrap_or_default();
+                let range = min as i64..=max as i64;
+                let changed = ui.add(egui::DragValue::new(&mut number).range(range)).changed();
+                *value = Value::from(number);
+                changed
+            }
+        }
+        VariableType::Float | VariableType::F32 => {
+            let mut number = value.as_f64().unwrap_or_default();
+            let changed = ui.add(egui::DragValue::new(&mut number).speed(0.1)).changed();
+            *value = Value::from(number);
+            changed
+        }
+        VariableType::Boolean => {
+            let mut flag = value.as_bool().unwrap_or_default();
+            let changed = ui.checkbox(&mut flag, "").changed();
+            *value = Value::Bool(flag);
+            changed
+        }
+        VariableType::Color => {
+            let [r, g, b, a] = value.as_str().and_then(parse_color).unwrap_or([255; 4]);
+            let mut color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+            let changed = ui.color_edit_button_srgba(&mut color).changed();
+            *value = Value::String(format_color(color.to_srgba_unmultiplied()));
+            changed
+        }
+        VariableType::Enum(name) => {
+            let Some(CustomTypeKind::Enum { variants }) = crate::model::find_type(types, name).map(|t| &t.kind) else {
+                ui.label(RichText::new(format!("unknown enum '{}'", name)).color(theme::error_color(ui.ctx())));
+                return false;
+            };
+            let mut selected = value.as_str().unwrap_or_default().to_string();
+            let mut changed = false;
+            egui::ComboBox::from_id_salt(id)
+                .selected_text(&selected)
+                .show_ui(ui, |ui| {
+                    for variant in variants {
+                        changed |= ui.selectable_value(&mut selected, variant.clone(), variant).changed();
+                    }
+                });
+            *value = Value::String(selected);
+            changed
+        }
+        VariableType::List(item_type) => {
+            let Value::Array(items) = value else {
+                return false;
+            };
+            let mut changed = false;
+            let mut to_remove = None;
+            ui.vertical(|ui| {
+                for (i, item) in items.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("[{}]", i)).size(11.0).color(theme::muted_color(ui.ctx())));
+                        changed |= value_editor(ui, id.with(i), item_type, item, types);
+                        if ui.small_button("✕").clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+                if ui.small_button("+ Add item").clicked() {
+                    items.push(item_type.default_json(types));
+                    changed = true;
+                }
+            });
+            if let Some(i) = to_remove {
+                items.remove(i);
+                changed = true;
+            }
+            changed
+        }
+        VariableType::Struct(name) => {
+            let Some(CustomTypeKind::Struct { fields }) = crate::model::find_type(types, name).map(|t| &t.kind) else {
+                ui.label(RichText::new(format!("unknown struct '{}'", name)).color(theme::error_color(ui.ctx())));
+                return false;
+            };
+            let Value::Object(map) = value else {
+                return false;
+            };
+            let mut changed = false;
+            ui.vertical(|ui| {
+                for field in fields {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("{}:", field.name)).size(11.0));
+                        let entry = map
+                            .entry(field.name.clone())
+                            .or_insert_with(|| field.v_type.default_json(types));
+                        changed |= value_editor(ui, id.with(&field.name), &field.v_type, entry, types);
                     });
                 }
             });
-            ui.add_space(4.0);
+            changed
+        }
+    }
+}
+
+/// Define the project's enums and structs
+fn render_custom_types(ui: &mut Ui, project_state: &mut ProjectState) {
+    ui.horizontal(|ui| {
+        if ui.button("+ Enum").clicked() {
+            let name = unique_type_name(&project_state.types, "NewEnum");
+            project_state.types.push(CustomType::new_enum(&name, &["First"]));
+        }
+        if ui.button("+ Struct").clicked() {
+            let name = unique_type_name(&project_state.types, "NewStruct");
+            project_state.types.push(CustomType::new_struct(&name, Vec::new()));
         }
     });
 
-    if let Some(key) = to_remove {
-        ctx.project_state.variables.remove(&key);
+    let mut rename = None;
+    let mut to_remove = None;
+    let known = project_state.types.clone();
+
+    for (index, custom) in project_state.types.iter_mut().enumerate() {
+        theme::section_frame(ui.ctx()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let kind = match custom.kind {
+                    CustomTypeKind::Enum { .. } => "enum",
+                    CustomTypeKind::Struct { .. } => "struct",
+                };
+                ui.label(RichText::new(kind).size(11.0).color(theme::muted_color(ui.ctx())));
+                let mut name = custom.name.clone();
+                if ui.add(egui::TextEdit::singleline(&mut name).desired_width(120.0)).changed() {
+                    rename = Some((custom.name.clone(), name));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(egui::Button::new(RichText::new("✕").color(theme::error_color(ui.ctx()))).small())
+                        .clicked()
+                    {
+                        to_remove = Some(index);
+                    }
+                });
+            });
+
+            match &mut custom.kind {
+                CustomTypeKind::Enum { variants } => {
+                    let mut remove_variant = None;
+                    for (i, variant) in variants.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(variant).desired_width(120.0));
+                            if ui.small_button("✕").clicked() {
+                                remove_variant = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove_variant {
+                        variants.remove(i);
+                    }
+                    if ui.small_button("+ Variant").clicked() {
+                        variants.push(format!("Variant{}", variants.len() + 1));
+                    }
+                }
+                CustomTypeKind::Struct { fields } => {
+                    let mut remove_field = None;
+                    for (i, field) in fields.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut field.name).desired_width(90.0));
+                            type_picker(ui, ("field_type", index, i), &mut field.v_type, &known);
+                            if ui.small_button("✕").clicked() {
+                                remove_field = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove_field {
+                        fields.remove(i);
+                    }
+                    if ui.small_button("+ Field").clicked() {
+                        fields.push(StructField {
+                            name: format!("field_{}", fields.len() + 1),
+                            v_type: VariableType::String,
+                        });
+                    }
+                }
+            }
+        });
+        ui.add_space(4.0);
+    }
+
+    if let Some((old, new)) = rename {
+        project_state.rename_type(&old, &new);
+    }
+    if let Some(index) = to_remove {
+        project_state.types.remove(index);
+    }
+}
+
+fn unique_type_name(types: &[CustomType], base: &str) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while types.iter().any(|t| t.name == name) {
+        name = format!("{}{}", base, n);
+        n += 1;
     }
-}
\ No newline at end of file
+    name
+}
@@ -1,23 +1,76 @@
-use crate::compiler::Compiler;
+use crate::compiler::{Compiler, GeneratedFile, SourceMap};
 use crate::model::ProjectState;
-use std::process::Command;
+use std::fs::{File, TryLockError};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// A single problem found while validating, optionally traced back to a widget
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    /// Generated file and line the compiler pointed at
+    pub location: Option<(String, usize)>,
+    /// Widget whose generated code (or configuration) caused the problem
+    pub widget: Option<Uuid>,
+    /// Full compiler output for this diagnostic
+    pub rendered: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+            widget: None,
+            rendered: None,
+        }
+    }
+
+    pub fn for_widget(message: impl Into<String>, widget: Uuid) -> Self {
+        Self {
+            widget: Some(widget),
+            ..Self::new(message)
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.rendered, &self.location) {
+            (Some(rendered), _) => write!(f, "{}", rendered.trim_end()),
+            (None, Some((file, line))) => write!(f, "{}:{}: {}", file, line, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum ValidationStatus {
     NotRun,
-    Checking,
+    /// Running, with cargo's latest progress line
+    Checking(String),
     Success,
-    Failed(String),
+    Failed(Vec<Diagnostic>),
+    Cancelled,
 }
 
 impl ValidationStatus {
     pub fn display_text(&self) -> String {
         match self {
             ValidationStatus::NotRun => "Click 'Check Code' to validate compilation".to_string(),
-            ValidationStatus::Checking => "Cargo check in progress...".to_string(),
+            ValidationStatus::Checking(progress) => format!("Cargo check in progress... {}", progress),
             ValidationStatus::Success => "✓ Code compiles successfully!".to_string(),
-            ValidationStatus::Failed(err) => format!("✗ Compilation failed:\n{}", err),
+            ValidationStatus::Failed(diagnostics) => match diagnostics.as_slice() {
+                [only] => format!("✗ Validation failed: {}", only.message),
+                _ => format!("✗ Validation failed with {} errors", diagnostics.len()),
+            },
+            ValidationStatus::Cancelled => "Validation cancelled".to_string(),
         }
     }
 
@@ -26,83 +79,669 @@ impl ValidationStatus {
     }
 
     pub fn is_checking(&self) -> bool {
-        matches!(self, ValidationStatus::Checking)
+        matches!(self, ValidationStatus::Checking(_))
     }
 }
 
 pub struct CodeValidator;
 
+/// Package name of the scratch crate that `cargo check` runs in
+const SCRATCH_PACKAGE: &str = "aether_check";
+
 impl CodeValidator {
-    /// Validate that generated code compiles by running cargo check
-    pub fn validate(project_state: &ProjectState) -> Result<String, String> {
-        // 1. Perform logical validation on the widget tree
-        let mut logical_errors = Vec::new();
-        validate_node_recursive(project_state.root_node.as_ref(), &project_state.variables, &mut logical_errors);
+    /// Validate that generated code compiles by running cargo check.
+    /// Errors are traced back to widgets through the generated code's source map.
+    /// Cargo's progress lines are reported as they arrive; setting `cancel` or
+    /// exceeding `timeout` stops cargo early.
+    pub fn validate(
+        project_state: &ProjectState,
+        on_progress: &mut dyn FnMut(&str),
+        cancel: &AtomicBool,
+        timeout: Option<Duration>,
+    ) -> Result<String, Vec<Diagnostic>> {
+        let prepared = Self::prepare_check(project_state)?;
+        Self::run_check(&prepared, on_progress, cancel, timeout)
+    }
 
+    /// Errors that can be found without compiling: widget bindings, screens and types
+    pub fn validate_logic(project_state: &ProjectState) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+        for screen in &project_state.screens {
+            validate_node_recursive(screen.root.as_ref(), &project_state.variables, &mut errors);
+        }
+        let targets = project_state.action_targets();
+        for screen in &project_state.screens {
+            collect_action_errors(screen.root.as_ref(), project_state, &targets, &mut errors);
+        }
+        errors.extend(validate_screens(project_state));
+        errors.extend(validate_types(project_state));
+        errors
+    }
+
+    /// Validate the widget tree and generate the crate to check
+    fn prepare_check(project_state: &ProjectState) -> Result<PreparedCheck, Vec<Diagnostic>> {
+        // 1. Perform logical validation on the widget tree
+        let logical_errors = Self::validate_logic(project_state);
         if !logical_errors.is_empty() {
-            return Err(format!("Logical Validation Failed:\n- {}", logical_errors.join("\n- ")));
+            return Err(logical_errors);
         }
 
-        // 2. Run Cargo Check (slow but thorough)
-        // Create a temporary directory
-        let temp_dir = std::env::temp_dir().join(format!("aether_check_{}", uuid::Uuid::new_v4()));
+        // 2. Generate the crate for cargo check
+        let (files, source_map) = Self::scratch_crate_files(project_state, SCRATCH_PACKAGE);
+        Ok(PreparedCheck { files, source_map })
+    }
 
-        // Create directory structure
-        std::fs::create_dir_all(&temp_dir)
-            .map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    /// Write a prepared check into the scratch crate and run `cargo check` on it,
+    /// waiting first for any other check that is using the crate
+    fn run_check(
+        prepared: &PreparedCheck,
+        on_progress: &mut dyn FnMut(&str),
+        cancel: &AtomicBool,
+        timeout: Option<Duration>,
+    ) -> Result<String, Vec<Diagnostic>> {
+        let dir = Self::scratch_dir();
+        let _lock = Self::lock_scratch_dir(&dir, on_progress, cancel)?;
+        Self::write_crate(&prepared.files, &dir).map_err(|e| vec![Diagnostic::new(e)])?;
+        Self::run_cargo_check(&dir.join("Cargo.toml"), &prepared.source_map, on_progress, cancel, timeout)
+    }
 
-        let src_dir = temp_dir.join("src");
-        std::fs::create_dir(&src_dir).map_err(|e| format!("Failed to create src dir: {}", e))?;
+    /// Write the project as a runnable crate in `dir`, reusing the directory between runs.
+    /// Only the sources are replaced; Cargo.lock and any build output are kept.
+    pub fn write_scratch_crate(project_state: &ProjectState, dir: &Path, package: &str) -> Result<SourceMap, String> {
+        let (files, source_map) = Self::scratch_crate_files(project_state, package);
+        Self::write_crate(&files, dir)?;
+        Ok(source_map)
+    }
 
-        // Write Cargo.toml
-        let cargo_toml = Compiler::generate_cargo_toml(&project_state.project_name);
-        let cargo_path = temp_dir.join("Cargo.toml");
-        std::fs::write(&cargo_path, cargo_toml)
-            .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+    /// The files of the project as a runnable crate named `package`.
+    /// Components are hosted by a small `main.rs` that shows them in a window.
+    fn scratch_crate_files(project_state: &ProjectState, package: &str) -> (Vec<GeneratedFile>, SourceMap) {
+        let (mut files, source_map) = if project_state.export.is_component() {
+            let mut check_state = project_state.clone();
+            check_state.export.module_path = "component".to_string();
+            let mut files = Compiler::generate_project_files(&check_state);
+            files.push(GeneratedFile {
+                path: "src/main.rs".to_string(),
+                contents: Compiler::generate_component_host_main_rs(&check_state),
+                user_owned: false,
+            });
+            (files, Compiler::generate_primary_rs_mapped(&check_state).1)
+        } else {
+            let files = Compiler::generate_project_files(project_state);
+            (files, Compiler::generate_primary_rs_mapped(project_state).1)
+        };
 
-        // Write main.rs
-        let main_rs = Compiler::generate_main_rs();
-        std::fs::write(src_dir.join("main.rs"), main_rs)
-            .map_err(|e| format!("Failed to write main.rs: {}", e))?;
+        // A fixed package name lets every project share the scratch crate's build cache
+        files.retain(|file| file.path != "Cargo.toml");
+        files.push(GeneratedFile {
+            path: "Cargo.toml".to_string(),
+            contents: Compiler::generate_cargo_toml(package, &Compiler::collect_dependencies(project_state)),
+            user_owned: false,
+        });
+        (files, source_map)
+    }
 
-        // Write app.rs
-        let app_rs = Compiler::generate_app_rs(project_state);
-        std::fs::write(src_dir.join("app.rs"), app_rs)
-            .map_err(|e| format!("Failed to write app.rs: {}", e))?;
+    /// Replace the sources of the crate in `dir` with `files`
+    fn write_crate(files: &[GeneratedFile], dir: &Path) -> Result<(), String> {
+        let _ = std::fs::remove_dir_all(dir.join("src"));
+        for file in files {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create scratch crate: {}", e))?;
+            }
+            std::fs::write(&path, &file.contents).map_err(|e| format!("Failed to write {}: {}", file.path, e))?;
+        }
+        Ok(())
+    }
 
-        // Run cargo check
-        let output = Command::new("cargo")
-            .args(&["check", "--manifest-path"])
-            .arg(&cargo_path)
-            .output()
-            .map_err(|e| format!("Failed to run cargo check: {}", e))?;
+    /// Wait until no other check, in this process or another, is using the scratch crate.
+    /// The crate is ours until the returned lock file is dropped.
+    fn lock_scratch_dir(dir: &Path, on_progress: &mut dyn FnMut(&str), cancel: &AtomicBool) -> Result<File, Vec<Diagnostic>> {
+        let fail = |message: String| vec![Diagnostic::new(message)];
+        std::fs::create_dir_all(dir).map_err(|e| fail(format!("Failed to create scratch crate: {}", e)))?;
+        let lock = File::create(dir.join(".lock")).map_err(|e| fail(format!("Failed to lock scratch crate: {}", e)))?;
+        let mut waiting = false;
+        loop {
+            match lock.try_lock() {
+                Ok(()) => return Ok(lock),
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(e)) => return Err(fail(format!("Failed to lock scratch crate: {}", e))),
+            }
+            if cancel.load(Ordering::Relaxed) {
+                return Err(fail("Validation cancelled".to_string()));
+            }
+            if !waiting {
+                on_progress("Waiting for another check to finish");
+                waiting = true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Run `cargo check` on the crate at `cargo_path`
+    fn run_cargo_check(
+        cargo_path: &Path,
+        source_map: &SourceMap,
+        on_progress: &mut dyn FnMut(&str),
+        cancel: &AtomicBool,
+        timeout: Option<Duration>,
+    ) -> Result<String, Vec<Diagnostic>> {
+        let fail = |message: String| vec![Diagnostic::new(message)];
 
-        // Clean up temp directory
-        let _ = std::fs::remove_dir_all(&temp_dir);
+        // Run cargo check with machine-readable diagnostics on stdout;
+        // its human-readable progress ("Compiling foo ...") arrives on stderr
+        let mut child = Command::new("cargo")
+            .args(["check", "--message-format=json", "--color=never", "--manifest-path"])
+            .arg(cargo_path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| fail(format!("Failed to run cargo check: {}", e)))?;
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stdout_reader = std::thread::spawn(move || {
+            let mut json = String::new();
+            let _ = std::io::Read::read_to_string(&mut BufReader::new(stdout), &mut json);
+            json
+        });
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let (line_sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                if line_sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let started = Instant::now();
+        let mut stderr_text = String::new();
+        let mut forward = |line: String, stderr_text: &mut String| {
+            on_progress(line.trim());
+            stderr_text.push_str(&line);
+            stderr_text.push('\n');
+        };
+        let status = loop {
+            while let Ok(line) = lines.try_recv() {
+                forward(line, &mut stderr_text);
+            }
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(fail("Validation cancelled".to_string()));
+            }
+            if let Some(timeout) = timeout.filter(|t| started.elapsed() > *t) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(fail(format!("cargo check timed out after {} s", timeout.as_secs())));
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+                Err(e) => return Err(fail(format!("Failed to wait for cargo check: {}", e))),
+            }
+        };
+        // Pick up whatever was printed just before cargo exited
+        for line in lines.iter() {
+            forward(line, &mut stderr_text);
+        }
+        let stdout = stdout_reader.join().unwrap_or_default();
 
         // Check result
-        if output.status.success() {
+        if status.success() {
             Ok("Compilation check passed".to_string())
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Err(format!("{}\n{}", stdout, stderr))
+            let diagnostics = parse_cargo_diagnostics(&stdout, source_map);
+            if diagnostics.is_empty() {
+                // Cargo itself failed (e.g. dependency resolution), so there is nothing to map
+                Err(fail(stderr_text.trim().to_string()))
+            } else {
+                Err(diagnostics)
+            }
+        }
+    }
+
+    /// Persistent crate that `cargo check` runs in. Every editor and CLI process shares it,
+    /// and its build cache, taking turns through a lock file.
+    pub fn scratch_dir() -> PathBuf {
+        std::env::temp_dir().join(SCRATCH_PACKAGE)
+    }
+}
+
+/// Generated crate waiting to be checked
+struct PreparedCheck {
+    files: Vec<GeneratedFile>,
+    source_map: SourceMap,
+}
+
+/// A validation running on a worker thread
+pub struct BackgroundValidation {
+    updates: mpsc::Receiver<ValidationStatus>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl BackgroundValidation {
+    /// How long a check may run before it is abandoned
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+    /// Start validating the project. The code is generated right away; the worker
+    /// writes it once it has the scratch crate to itself, then runs `cargo check`. `on_update` is called from the
+    /// worker whenever a new status is available, e.g. to request a repaint.
+    pub fn start(
+        project_state: &ProjectState,
+        timeout: Option<Duration>,
+        on_update: impl Fn() + Send + 'static,
+    ) -> Self {
+        let (sender, updates) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = cancel.clone();
+
+        let prepared = match CodeValidator::prepare_check(project_state) {
+            Ok(prepared) => prepared,
+            Err(diagnostics) => {
+                let _ = sender.send(ValidationStatus::Failed(diagnostics));
+                return Self { updates, cancel };
+            }
+        };
+
+        std::thread::spawn(move || {
+            let mut on_progress = |line: &str| {
+                if !line.is_empty() {
+                    let _ = sender.send(ValidationStatus::Checking(line.to_string()));
+                    on_update();
+                }
+            };
+            let result = CodeValidator::run_check(
+                &prepared,
+                &mut on_progress,
+                &worker_cancel,
+                timeout,
+            );
+            let status = match result {
+                _ if worker_cancel.load(Ordering::Relaxed) => ValidationStatus::Cancelled,
+                Ok(_) => ValidationStatus::Success,
+                Err(diagnostics) => ValidationStatus::Failed(diagnostics),
+            };
+            let _ = sender.send(status);
+            on_update();
+        });
+
+        Self { updates, cancel }
+    }
+
+    /// Ask the worker to stop. The final `Cancelled` status still arrives through `poll`.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Apply any new statuses to `status`. Returns true once the validation has finished.
+    pub fn poll(&self, status: &mut ValidationStatus) -> bool {
+        loop {
+            match self.updates.try_recv() {
+                Ok(update) => {
+                    *status = update;
+                    if !status.is_checking() {
+                        return true;
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => return false,
+                Err(mpsc::TryRecvError::Disconnected) => return true,
+            }
+        }
+    }
+}
+
+/// Parse the errors from `cargo check --message-format=json` output,
+/// using `source_map` to find the widget behind each primary span.
+pub fn parse_cargo_diagnostics(json_lines: &str, source_map: &SourceMap) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for line in json_lines.lines() {
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-message" {
+            continue;
         }
+        let message = &message["message"];
+        if message["level"] != "error" {
+            continue;
+        }
+
+        let primary = message["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true));
+        let Some(span) = primary else {
+            // Summaries like "aborting due to 2 previous errors" have no location
+            continue;
+        };
+
+        let file = span["file_name"].as_str().unwrap_or_default().replace('\\', "/");
+        let line = span["line_start"].as_u64().unwrap_or_default() as usize;
+        let widget = if file.ends_with(&source_map.file) {
+            source_map.widget_at(line)
+        } else {
+            None
+        };
+
+        diagnostics.push(Diagnostic {
+            message: message["message"].as_str().unwrap_or_default().to_string(),
+            location: Some((file, line)),
+            widget,
+            rendered: message["rendered"].as_str().map(str::to_string),
+        });
     }
+
+    diagnostics
 }
 
 fn validate_node_recursive(
     node: &dyn crate::model::WidgetNode,
     variables: &std::collections::HashMap<String, crate::model::Variable>,
-    errors: &mut Vec<String>,
+    errors: &mut Vec<Diagnostic>,
 ) {
     // Validate current node
-    errors.extend(node.validate(variables));
+    let id = node.id();
+    errors.extend(node.validate(variables).into_iter().map(|e| Diagnostic::for_widget(e, id)));
+    errors.extend(validate_common(node, variables).into_iter().map(|e| Diagnostic::for_widget(e, id)));
 
     // Recurse into children
-    if let Some(children) = node.children() {
-        for child in children {
-            validate_node_recursive(child.as_ref(), variables, errors);
+    for child in node.child_widgets() {
+        validate_node_recursive(child, variables, errors);
+    }
+}
+
+/// Enabled and visible flags must follow boolean variables, and tooltips may only show existing ones
+fn validate_common(
+    node: &dyn crate::model::WidgetNode,
+    variables: &std::collections::HashMap<String, crate::model::Variable>,
+) -> Vec<String> {
+    use crate::interpolation::{self, Segment};
+    use crate::model::{Flag, VariableType};
+
+    let common = node.common();
+    let mut errors = Vec::new();
+    for (property, flag) in [("enabled", &common.enabled), ("visible", &common.visible)] {
+        let Flag::Bound(var_name) = flag else {
+            continue;
+        };
+        match variables.get(var_name) {
+            None => errors.push(format!(
+                "{} '{}' {} state follows missing variable '{}'",
+                node.name(),
+                node.id(),
+                property,
+                var_name
+            )),
+            Some(var) if var.v_type != VariableType::Boolean => errors.push(format!(
+                "{} '{}' {} state needs a boolean variable, not {} '{}'",
+                node.name(),
+                node.id(),
+                property,
+                var.v_type,
+                var_name
+            )),
+            Some(_) => {}
         }
     }
+    for segment in interpolation::parse(&common.tooltip) {
+        if let Segment::Placeholder { var, spec } = segment {
+            if let Err(e) = interpolation::check_placeholder(&var, &spec, variables.get(&var).map(|v| &v.v_type)) {
+                errors.push(format!("{} '{}' tooltip: {}", node.name(), node.id(), e));
+            }
+        }
+    }
+    errors
+}
+
+/// Screen names must map to distinct enum variants, and navigation must target an existing screen
+fn validate_screens(project_state: &ProjectState) -> Vec<Diagnostic> {
+    let mut errors = Vec::new();
+    let mut variants = std::collections::HashSet::new();
+    for screen in &project_state.screens {
+        if !variants.insert(Compiler::screen_variant(&screen.name).to_string()) {
+            errors.push(Diagnostic::new(format!("Screen name '{}' clashes with another screen", screen.name)));
+        }
+    }
+
+    let names: Vec<&str> = project_state.screens.iter().map(|s| s.name.as_str()).collect();
+    for screen in &project_state.screens {
+        collect_navigation_errors(screen.root.as_ref(), &names, &mut errors);
+    }
+    errors
+}
+
+fn collect_navigation_errors(node: &dyn crate::model::WidgetNode, screens: &[&str], errors: &mut Vec<Diagnostic>) {
+    if let Some(events) = node.events() {
+        for action in events.values().flatten() {
+            action.visit(&mut |action| {
+                if let crate::model::Action::NavigateTo(target) = action {
+                    if !screens.contains(&target.as_str()) {
+                        errors.push(Diagnostic::for_widget(
+                            format!("{} '{}' navigates to missing screen '{}'", node.name(), node.id(), target),
+                            node.id(),
+                        ));
+                    }
+                }
+            });
+        }
+    }
+
+    for child in node.child_widgets() {
+        collect_navigation_errors(child, screens, errors);
+    }
+}
+
+/// Actions must refer to existing variables of a type they work on, and to existing windows and tabs
+fn collect_action_errors(
+    node: &dyn crate::model::WidgetNode,
+    project_state: &ProjectState,
+    targets: &[crate::model::ActionTarget],
+    errors: &mut Vec<Diagnostic>,
+) {
+    use crate::model::{Action, ActionTarget, VariableType};
+
+    let variables = &project_state.variables;
+    // A variable's type if it passes `check`, or why the action can't use it
+    let variable = |name: &str, what: &str, check: fn(&VariableType) -> bool| match variables.get(name) {
+        None => Err(format!("uses missing variable '{}'", name)),
+        Some(var) if !check(&var.v_type) => Err(format!("cannot {} {} variable '{}'", what, var.v_type, name)),
+        Some(var) => Ok(&var.v_type),
+    };
+    let text = |text: &str| {
+        crate::interpolation::parse(text).into_iter().find_map(|segment| match segment {
+            crate::interpolation::Segment::Placeholder { var, spec } => {
+                crate::interpolation::check_placeholder(&var, &spec, variables.get(&var).map(|v| &v.v_type)).err()
+            }
+            crate::interpolation::Segment::Text(_) => None,
+        })
+    };
+
+    if let Some(events) = node.events() {
+        for action in events.values().flatten() {
+            action.visit(&mut |action| {
+                let problem = match action {
+                    Action::IncrementVariable(name) => variable(name, "increment", VariableType::is_integer).err(),
+                    Action::DecrementVariable(name) => variable(name, "decrement", VariableType::is_integer).err(),
+                    Action::AddToVariable(name, amount) => match variable(name, "add to", VariableType::is_numeric) {
+                        Err(e) => Some(e),
+                        Ok(_) if amount.trim().is_empty() => Some(format!("adds nothing to '{}'", name)),
+                        Ok(_) => None,
+                    },
+                    Action::ToggleVariable(name) => variable(name, "toggle", |t| *t == VariableType::Boolean).err(),
+                    Action::SetVariable(name, _) => variable(name, "set", |_| true).err(),
+                    Action::CopyVariable { from, to } => {
+                        match (variable(from, "copy", |_| true), variable(to, "copy into", |_| true)) {
+                            (Err(e), _) | (_, Err(e)) => Some(e),
+                            (Ok(from_type), Ok(to_type)) if from_type != to_type => Some(format!(
+                                "cannot copy {} '{}' into {} '{}'",
+                                from_type, from, to_type, to
+                            )),
+                            _ => None,
+                        }
+                    }
+                    Action::PushToList(name, value) => {
+                        match variable(name, "push to", |t| matches!(t, VariableType::List(_))) {
+                            Err(e) => Some(e),
+                            Ok(_) if value.trim().is_empty() => Some(format!("pushes nothing to '{}'", name)),
+                            Ok(_) => None,
+                        }
+                    }
+                    Action::ClearList(name) => variable(name, "clear", |t| matches!(t, VariableType::List(_))).err(),
+                    Action::SetWindowOpen { window, .. } => {
+                        let exists = targets
+                            .iter()
+                            .any(|t| matches!(t, ActionTarget::Window { id, .. } if id == window));
+                        (!exists).then(|| "opens or closes a missing window".to_string())
+                    }
+                    Action::SelectTab { container, tab } => match targets.iter().find(|t| t.id() == *container) {
+                        Some(ActionTarget::Tabs { tabs, .. }) if *tab < tabs.len() => None,
+                        Some(ActionTarget::Tabs { .. }) => Some(format!("selects missing tab {}", tab + 1)),
+                        _ => Some("selects a tab of a missing tab container".to_string()),
+                    },
+                    Action::OpenUrl(url) if url.trim().is_empty() => Some("opens an empty URL".to_string()),
+                    Action::OpenUrl(url) => text(url),
+                    Action::CopyToClipboard(content) => text(content),
+                    Action::If { condition, .. } => match variables.get(&condition.variable) {
+                        None => Some(format!("condition tests missing variable '{}'", condition.variable)),
+                        Some(var) if !condition.op.takes_value() && var.v_type != VariableType::Boolean => {
+                            Some(format!("condition '{}' needs a boolean variable, not {}", condition, var.v_type))
+                        }
+                        Some(_) if condition.op.takes_value() && condition.value.trim().is_empty() => {
+                            Some(format!("condition '{}' has nothing to compare with", condition))
+                        }
+                        Some(var) => condition.check_value(&var.v_type, &project_state.types).err(),
+                    },
+                    Action::Custom(_) | Action::NavigateTo(_) | Action::Sequence(_) => None,
+                };
+                if let Some(problem) = problem {
+                    errors.push(Diagnostic::for_widget(
+                        format!("{} '{}' {}", node.name(), node.id(), problem),
+                        node.id(),
+                    ));
+                }
+            });
+        }
+    }
+
+    for child in node.child_widgets() {
+        collect_action_errors(child, project_state, targets, errors);
+    }
+}
+
+/// Project-defined types must generate valid Rust, and variables must use known
+/// types with initial values that match them
+fn validate_types(project_state: &ProjectState) -> Vec<Diagnostic> {
+    use crate::model::CustomTypeKind;
+
+    let types = &project_state.types;
+    let mut errors = Vec::new();
+    let is_ident = |name: &str| syn::parse_str::<syn::Ident>(name).is_ok();
+
+    // Names the generated code already uses for its own items
+    let component = project_state.export.struct_ident().to_string();
+    let reserved = ["MyApp", "AppEvents", "Screen", "String", "Vec", component.as_str()];
+
+    let mut names = std::collections::HashSet::new();
+    for t in types {
+        if !is_ident(&t.name) {
+            errors.push(Diagnostic::new(format!("Type name '{}' is not a valid Rust identifier", t.name)));
+        } else if reserved.contains(&t.name.as_str()) {
+            errors.push(Diagnostic::new(format!("Type name '{}' is used by the generated code", t.name)));
+        }
+        if !names.insert(t.name.as_str()) {
+            errors.push(Diagnostic::new(format!("Type '{}' is defined more than once", t.name)));
+        }
+
+        match &t.kind {
+            CustomTypeKind::Enum { variants } => {
+                if variants.is_empty() {
+                    errors.push(Diagnostic::new(format!("Enum '{}' has no variants", t.name)));
+                }
+                let mut seen = std::collections::HashSet::new();
+                for variant in variants {
+                    if !is_ident(variant) {
+                        errors.push(Diagnostic::new(format!("Variant '{}' of '{}' is not a valid Rust identifier", variant, t.name)));
+                    }
+                    if !seen.insert(variant) {
+                        errors.push(Diagnostic::new(format!("Enum '{}' has variant '{}' more than once", t.name, variant)));
+                    }
+                }
+            }
+            CustomTypeKind::Struct { fields } => {
+                let mut seen = std::collections::HashSet::new();
+                for field in fields {
+                    if !is_ident(&field.name) {
+                        errors.push(Diagnostic::new(format!("Field '{}' of '{}' is not a valid Rust identifier", field.name, t.name)));
+                    }
+                    if !seen.insert(&field.name) {
+                        errors.push(Diagnostic::new(format!("Struct '{}' has field '{}' more than once", t.name, field.name)));
+                    }
+                    if let Some(e) = unresolved_type(&field.v_type, types) {
+                        errors.push(Diagnostic::new(format!("Field '{}.{}': {}", t.name, field.name, e)));
+                    }
+                }
+                if contains_struct(types, &t.name, &t.name, &mut Vec::new()) {
+                    errors.push(Diagnostic::new(format!(
+                        "Struct '{}' contains itself; use a List field to nest it",
+                        t.name
+                    )));
+                }
+            }
+        }
+    }
+
+    let mut keys: Vec<&String> = project_state.variables.keys().collect();
+    keys.sort();
+    for key in keys {
+        let var = &project_state.variables[key];
+        if let Some(e) = unresolved_type(&var.v_type, types) {
+            errors.push(Diagnostic::new(format!("Variable '{}': {}", var.name, e)));
+        } else if let Err(e) = var.v_type.parse_value(&var.value, types) {
+            errors.push(Diagnostic::new(format!("Variable '{}' has an invalid initial value: {}", var.name, e)));
+        }
+    }
+    errors
+}
+
+/// Why a type can't be resolved against the project's definitions, if it can't
+fn unresolved_type(v_type: &crate::model::VariableType, types: &[crate::model::CustomType]) -> Option<String> {
+    use crate::model::{CustomTypeKind, VariableType};
+
+    match v_type {
+        VariableType::List(inner) => unresolved_type(inner, types),
+        VariableType::Enum(name) => match crate::model::find_type(types, name).map(|t| &t.kind) {
+            Some(CustomTypeKind::Enum { .. }) => None,
+            Some(_) => Some(format!("'{}' is not an enum", name)),
+            None => Some(format!("unknown enum '{}'", name)),
+        },
+        VariableType::Struct(name) => match crate::model::find_type(types, name).map(|t| &t.kind) {
+            Some(CustomTypeKind::Struct { .. }) => None,
+            Some(_) => Some(format!("'{}' is not a struct", name)),
+            None => Some(format!("unknown struct '{}'", name)),
+        },
+        _ => None,
+    }
+}
+
+/// Whether struct `current` holds a `target` by value (lists are heap-allocated and fine)
+fn contains_struct<'a>(
+    types: &'a [crate::model::CustomType],
+    target: &str,
+    current: &'a str,
+    visited: &mut Vec<&'a str>,
+) -> bool {
+    use crate::model::{CustomTypeKind, VariableType};
+
+    if visited.contains(&current) {
+        return false;
+    }
+    visited.push(current);
+    let Some(CustomTypeKind::Struct { fields }) = crate::model::find_type(types, current).map(|t| &t.kind) else {
+        return false;
+    };
+    fields.iter().any(|field| match &field.v_type {
+        VariableType::Struct(name) => name == target || contains_struct(types, target, name, visited),
+        _ => false,
+    })
 }
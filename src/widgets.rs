@@ -1,10 +1,69 @@
-use crate::model::WidgetNode;
+use crate::compiler::{CodegenContext, Compiler};
+use crate::model::{
+    ChildLayout, ChildList, ChildSlot, ChildSlotMut, CommonProperties, CrateDependency, PropertyDescriptor, PropertyKind,
+    PropertyValue, WidgetCommon, WidgetNode,
+};
+use crate::preview::PreviewState;
 use egui::Ui;
 use quote::quote;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use uuid::Uuid;
 
+/// Declares a widget struct with the `common` properties every widget has,
+/// and implements `WidgetCommon` for it.
+macro_rules! widget {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty,)*
+        }
+    ) => {
+        $(#[$meta])*
+        pub struct $name {
+            $($(#[$field_meta])* $field_vis $field: $ty,)*
+            #[serde(default)]
+            pub common: CommonProperties,
+        }
+
+        impl WidgetCommon for $name {
+            fn common(&self) -> &CommonProperties {
+                &self.common
+            }
+
+            fn common_mut(&mut self) -> &mut CommonProperties {
+                &mut self.common
+            }
+        }
+    };
+}
+
+/// Implements `properties`, `property` and `set_property` from one list of
+/// `field: descriptor` pairs, so each property is declared once. The descriptors
+/// may use the widget, named by the first argument (`self`).
+macro_rules! widget_properties {
+    ($this:ident; $($field:ident: $descriptor:expr),* $(,)?) => {
+        fn properties(&$this) -> Vec<PropertyDescriptor> {
+            vec![$($descriptor),*]
+        }
+
+        fn property(&self, name: &str) -> Option<PropertyValue> {
+            let getters: &[fn(&Self) -> PropertyValue] = &[$(|widget| widget.$field.clone().into()),*];
+            let index = self.properties().iter().position(|property| property.name == name)?;
+            Some(getters[index](self))
+        }
+
+        fn set_property(&mut self, name: &str, value: PropertyValue) -> bool {
+            let setters: &[fn(&mut Self, PropertyValue) -> bool] =
+                &[$(|widget, value| value.assign(&mut widget.$field)),*];
+            match self.properties().iter().position(|property| property.name == name) {
+                Some(index) => setters[index](self, value),
+                None => false,
+            }
+        }
+    };
+}
+
 // === Drag and Drop Payload Types ===
 
 /// Payload for drag-and-drop operations
@@ -72,6 +131,7 @@ pub fn create_widget_by_name(name: &str) -> Option<Box<dyn WidgetNode>> {
         "Separator" => Box::new(SeparatorWidget::default()),
         "Spinner" => Box::new(SpinnerWidget::default()),
         "Hyperlink" => Box::new(HyperlinkWidget::default()),
+        "Custom Code" => Box::new(CustomCodeWidget::default()),
         "Color Picker" => Box::new(ColorPickerWidget::default()),
         "Table" => Box::new(TableWidget::default()),
         "Plot" => Box::new(PlotWidget::default()),
@@ -208,24 +268,51 @@ fn handle_selection(ui: &egui::Ui, widget_id: Uuid, response_clicked: bool, sele
     });
 }
 
-/// Context menu action for widgets
-#[derive(Debug, Clone, PartialEq)]
-#[allow(dead_code)]
+/// Context menu action for widgets, sent with the widget's id under `"context_action"`
+/// in temp memory for `AetherApp::update` to apply
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum ContextMenuAction {
+    #[default]
     None,
     Delete,
     Duplicate,
     MoveUp,
     MoveDown,
-    WrapInVertical,
-    WrapInHorizontal,
+    /// Put the widget inside a new container of this type
+    WrapIn(&'static str),
+    /// Replace the container with its children
+    Unwrap,
+    /// Turn the container into another type, keeping its children
+    ConvertTo(&'static str),
 }
 
+impl ContextMenuAction {
+    pub fn id() -> egui::Id {
+        egui::Id::new("context_action")
+    }
+
+    fn send(self, ui: &egui::Ui, widget_id: Uuid) {
+        ui.memory_mut(|mem| mem.data.insert_temp(Self::id(), (widget_id, self)));
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Containers that widgets can be wrapped in and converted between
+pub const CONTAINER_TYPES: &[&str] = &[
+    "Vertical Layout",
+    "Horizontal Layout",
+    "Grid Layout",
+    "Freeform Layout",
+    "Scroll Area",
+    "Tab Container",
+    "Window",
+];
+
 /// Create an interaction overlay for more reliable selection hit detection
 /// This adds an expanded clickable area around the widget rect
 /// Returns both the response and any context menu action triggered
 /// NOTE: Uses click-only sense so parent dnd_drag_source can handle dragging
-fn create_selection_overlay(ui: &mut egui::Ui, rect: egui::Rect, widget_id: Uuid) -> egui::Response {
+fn create_selection_overlay(ui: &mut egui::Ui, rect: egui::Rect, widget_id: Uuid, kind: &str) -> egui::Response {
     // Expand the rect slightly for easier clicking
     let expanded_rect = rect.expand(4.0);
 
@@ -236,47 +323,59 @@ fn create_selection_overlay(ui: &mut egui::Ui, rect: egui::Rect, widget_id: Uuid
 
     // Add context menu on right-click
     response.clone().context_menu(|ui| {
-        render_widget_context_menu(ui, widget_id);
+        render_widget_context_menu(ui, widget_id, kind);
     });
 
     response
 }
 
-/// Render context menu items for a widget
-fn render_widget_context_menu(ui: &mut egui::Ui, _widget_id: Uuid) {
+/// Render context menu items for a widget of type `kind`
+fn render_widget_context_menu(ui: &mut egui::Ui, widget_id: Uuid, kind: &str) {
     if ui.button("✕ Delete").clicked() {
-        ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::Delete));
+        ContextMenuAction::Delete.send(ui, widget_id);
         ui.close();
     }
     if ui.button("⎘ Duplicate").clicked() {
-        ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::Duplicate));
+        ContextMenuAction::Duplicate.send(ui, widget_id);
         ui.close();
     }
     ui.separator();
     if ui.button("↑ Move Up").clicked() {
-        ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::MoveUp));
+        ContextMenuAction::MoveUp.send(ui, widget_id);
         ui.close();
     }
     if ui.button("↓ Move Down").clicked() {
-        ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::MoveDown));
+        ContextMenuAction::MoveDown.send(ui, widget_id);
         ui.close();
     }
     ui.separator();
     ui.menu_button("⊞ Wrap in...", |ui| {
-        if ui.button("Vertical Layout").clicked() {
-            ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::WrapInVertical));
-            ui.close();
+        for container in CONTAINER_TYPES {
+            if ui.button(*container).clicked() {
+                ContextMenuAction::WrapIn(container).send(ui, widget_id);
+                ui.close();
+            }
         }
-        if ui.button("Horizontal Layout").clicked() {
-            ui.memory_mut(|mem| mem.data.insert_temp(egui::Id::new("context_action"), ContextMenuAction::WrapInHorizontal));
+    });
+    if CONTAINER_TYPES.contains(&kind) {
+        if ui.button("⊟ Unwrap").clicked() {
+            ContextMenuAction::Unwrap.send(ui, widget_id);
             ui.close();
         }
-    });
+        ui.menu_button("⇄ Convert to...", |ui| {
+            for container in CONTAINER_TYPES.iter().filter(|c| **c != kind) {
+                if ui.button(*container).clicked() {
+                    ContextMenuAction::ConvertTo(container).send(ui, widget_id);
+                    ui.close();
+                }
+            }
+        });
+    }
 }
 
 /// Create a container selection overlay that only responds to clicks in the border area
 /// This allows child widgets to be selected without also selecting the parent
-fn create_container_selection_overlay(ui: &mut egui::Ui, outer_rect: egui::Rect, inner_margin: f32, widget_id: Uuid) -> bool {
+fn create_container_selection_overlay(ui: &mut egui::Ui, outer_rect: egui::Rect, inner_margin: f32, widget_id: Uuid, kind: &str) -> bool {
     // Only respond to clicks in the border area (outer rect minus inner content rect)
     let inner_rect = outer_rect.shrink(inner_margin.max(8.0));
     let id = egui::Id::new("container_select_overlay").with(widget_id);
@@ -289,7 +388,7 @@ fn create_container_selection_overlay(ui: &mut egui::Ui, outer_rect: egui::Rect,
 
             // Add context menu on right-click
             response.clone().context_menu(|ui| {
-                render_widget_context_menu(ui, widget_id);
+                render_widget_context_menu(ui, widget_id, kind);
             });
 
             return response.clicked();
@@ -298,27 +397,194 @@ fn create_container_selection_overlay(ui: &mut egui::Ui, outer_rect: egui::Rect,
     false
 }
 
-/// Render an action editor in the Inspector
-fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, known_variables: &[String]) {
+/// Publish the project's screen names so action editors can offer them as navigation targets
+pub fn set_known_screens(ctx: &egui::Context, screens: Vec<String>) {
+    ctx.data_mut(|d| d.insert_temp(egui::Id::new("known_screens"), screens));
+}
+
+/// Windows and tab containers that actions can open or switch, for the action editor
+pub fn set_known_action_targets(ctx: &egui::Context, targets: Vec<crate::model::ActionTarget>) {
+    ctx.data_mut(|d| d.insert_temp(egui::Id::new("known_action_targets"), targets));
+}
+
+/// Preview a child widget with its tooltip, enabled state and visibility applied,
+/// the way `CodegenContext::widget_code` wraps its generated code
+pub fn preview_widget(ui: &mut Ui, node: &dyn WidgetNode, preview: &mut PreviewState) {
+    let common = node.common();
+    if !common.visible.evaluate(preview) {
+        return;
+    }
+    let enabled = common.enabled.evaluate(preview);
+    let tooltip = preview.interpolate(&common.tooltip);
+    let mut render = |ui: &mut Ui| {
+        if common.enabled.is(true) {
+            node.render_preview(ui, preview);
+        } else {
+            ui.add_enabled_ui(enabled, |ui| node.render_preview(ui, preview));
+        }
+    };
+    if tooltip.is_empty() {
+        render(ui);
+    } else {
+        ui.scope(render).response.on_hover_text(tooltip);
+    }
+}
+
+/// Text of a bindable property in preview mode: the bound variable's live value, or the static text
+fn preview_text(preview: &PreviewState, bindings: &std::collections::HashMap<String, String>, property: &str, text: &str) -> String {
+    match bindings.get(property) {
+        Some(var) => preview.display(var).unwrap_or_default(),
+        None => preview.interpolate(text),
+    }
+}
+
+/// Hover text for inspector fields that take `{variable}` placeholders
+pub const TEXT_PLACEHOLDER_HINT: &str = "Insert variables with {name}, or {name:.2} to format them";
+
+/// Errors for `{variable}` placeholders in a widget's text
+fn validate_text(
+    widget: &str,
+    id: Uuid,
+    text: &str,
+    variables: &std::collections::HashMap<String, crate::model::Variable>,
+) -> Vec<String> {
+    use crate::interpolation::{self, Segment};
+
+    interpolation::parse(text)
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Placeholder { var, spec } => {
+                interpolation::check_placeholder(var, spec, variables.get(var).map(|v| &v.v_type)).err()
+            }
+            Segment::Text(_) => None,
+        })
+        .map(|e| format!("{} '{}' text: {}", widget, id, e))
+        .collect()
+}
+
+/// Render the actions of an event in the Inspector, as a list that runs in order
+fn render_action_editor(ui: &mut egui::Ui, actions: &mut Vec<crate::model::Action>, known_variables: &[String]) {
+    // The list lives in the widget's event map, so its address is stable between frames
+    let id = egui::Id::new("actions").with(actions as *const _ as usize);
+    render_action_list(ui, id, actions, known_variables);
+}
+
+/// Edit a list of actions with add, remove and reorder controls
+fn render_action_list(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    actions: &mut Vec<crate::model::Action>,
+    known_variables: &[String],
+) {
     use crate::model::Action;
 
-    let action_ptr = action as *const _ as usize;
+    let len = actions.len();
+    let mut to_remove = None;
+    let mut to_swap = None;
+
+    for (i, action) in actions.iter_mut().enumerate() {
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("{}.", i + 1)).weak());
+                if ui.add_enabled(i > 0, egui::Button::new("▲").small()).on_hover_text("Run earlier").clicked() {
+                    to_swap = Some((i - 1, i));
+                }
+                if ui.add_enabled(i + 1 < len, egui::Button::new("▼").small()).on_hover_text("Run later").clicked() {
+                    to_swap = Some((i, i + 1));
+                }
+                if ui.small_button("✕").on_hover_text("Remove action").clicked() {
+                    to_remove = Some(i);
+                }
+            });
+            render_single_action(ui, id.with(i), action, known_variables);
+        });
+    }
+
+    if let Some((a, b)) = to_swap {
+        actions.swap(a, b);
+    }
+    if let Some(i) = to_remove {
+        actions.remove(i);
+    }
+    if ui.small_button("+ Add action").clicked() {
+        actions.push(Action::Custom(String::new()));
+    }
+}
+
+/// Action types offered in the editor: (key, label)
+const ACTION_TYPES: &[(&str, &str)] = &[
+    ("Increment", "Increment"),
+    ("Decrement", "Decrement"),
+    ("Add", "Add To"),
+    ("Toggle", "Toggle"),
+    ("Set", "Set"),
+    ("Copy", "Copy Variable"),
+    ("Push", "Push to List"),
+    ("Clear", "Clear List"),
+    ("Window", "Open / Close Window"),
+    ("Tab", "Select Tab"),
+    ("Url", "Open URL"),
+    ("Clipboard", "Copy to Clipboard"),
+    ("Custom", "Custom"),
+    ("Navigate", "Navigate"),
+    ("If", "If / Else"),
+    ("Sequence", "Sequence"),
+];
+
+/// A labelled combo box picking one of the project variables
+fn variable_picker(ui: &mut egui::Ui, id: egui::Id, label: &str, var_name: &mut String, known_variables: &[String]) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(var_name.as_str())
+            .show_ui(ui, |ui| {
+                for var in known_variables {
+                    ui.selectable_value(var_name, var.clone(), var);
+                }
+            });
+    });
+}
+
+/// Edit one action: its type and the type's settings
+fn render_single_action(ui: &mut egui::Ui, id: egui::Id, action: &mut crate::model::Action, known_variables: &[String]) {
+    use crate::model::{Action, ActionTarget, CompareOp, Condition};
 
     let action_type = match action {
         Action::IncrementVariable(_) => "Increment",
+        Action::DecrementVariable(_) => "Decrement",
+        Action::AddToVariable(_, _) => "Add",
+        Action::ToggleVariable(_) => "Toggle",
         Action::SetVariable(_, _) => "Set",
+        Action::CopyVariable { .. } => "Copy",
+        Action::PushToList(_, _) => "Push",
+        Action::ClearList(_) => "Clear",
+        Action::SetWindowOpen { .. } => "Window",
+        Action::SelectTab { .. } => "Tab",
+        Action::OpenUrl(_) => "Url",
+        Action::CopyToClipboard(_) => "Clipboard",
         Action::Custom(_) => "Custom",
+        Action::NavigateTo(_) => "Navigate",
+        Action::If { .. } => "If",
+        Action::Sequence(_) => "Sequence",
     };
+    let known_screens: Vec<String> = ui
+        .data(|d| d.get_temp(egui::Id::new("known_screens")))
+        .unwrap_or_default();
+    let known_targets: Vec<ActionTarget> = ui
+        .data(|d| d.get_temp(egui::Id::new("known_action_targets")))
+        .unwrap_or_default();
+    let first_var = known_variables.first().cloned().unwrap_or_default();
 
     ui.horizontal(|ui| {
         ui.label("Action Type:");
         let mut selected = action_type.to_string();
-        egui::ComboBox::from_id_salt(format!("action_{}", action_ptr))
-            .selected_text(&selected)
+        let label = ACTION_TYPES.iter().find(|(key, _)| *key == action_type).map_or(action_type, |(_, label)| *label);
+        egui::ComboBox::from_id_salt(id.with("action"))
+            .selected_text(label)
             .show_ui(ui, |ui| {
-                ui.selectable_value(&mut selected, "Increment".to_string(), "Increment");
-                ui.selectable_value(&mut selected, "Set".to_string(), "Set");
-                ui.selectable_value(&mut selected, "Custom".to_string(), "Custom");
+                for (key, label) in ACTION_TYPES {
+                    ui.selectable_value(&mut selected, key.to_string(), *label);
+                }
             });
 
         // Change action type if needed
@@ -327,11 +593,50 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
                 "Increment" => *action = Action::IncrementVariable(
                     known_variables.first().cloned().unwrap_or_default()
                 ),
+                "Decrement" => *action = Action::DecrementVariable(first_var.clone()),
+                "Add" => *action = Action::AddToVariable(first_var.clone(), "1".to_string()),
+                "Toggle" => *action = Action::ToggleVariable(first_var.clone()),
+                "Copy" => *action = Action::CopyVariable {
+                    from: first_var.clone(),
+                    to: known_variables.get(1).cloned().unwrap_or_else(|| first_var.clone()),
+                },
+                "Push" => *action = Action::PushToList(first_var.clone(), String::new()),
+                "Clear" => *action = Action::ClearList(first_var.clone()),
+                "Window" => *action = Action::SetWindowOpen {
+                    window: known_targets
+                        .iter()
+                        .find(|t| matches!(t, ActionTarget::Window { .. }))
+                        .map_or(Uuid::nil(), ActionTarget::id),
+                    open: true,
+                },
+                "Tab" => *action = Action::SelectTab {
+                    container: known_targets
+                        .iter()
+                        .find(|t| matches!(t, ActionTarget::Tabs { .. }))
+                        .map_or(Uuid::nil(), ActionTarget::id),
+                    tab: 0,
+                },
+                "Url" => *action = Action::OpenUrl("https://".to_string()),
+                "Clipboard" => *action = Action::CopyToClipboard(String::new()),
                 "Set" => *action = Action::SetVariable(
                     known_variables.first().cloned().unwrap_or_default(),
                     "".to_string(),
                 ),
                 "Custom" => *action = Action::Custom(String::new()),
+                "Navigate" => *action = Action::NavigateTo(
+                    known_screens.first().cloned().unwrap_or_default()
+                ),
+                // The current action becomes the first step
+                "If" => *action = Action::If {
+                    condition: Condition {
+                        variable: known_variables.first().cloned().unwrap_or_default(),
+                        op: CompareOp::IsTrue,
+                        value: String::new(),
+                    },
+                    then: vec![action.clone()],
+                    otherwise: Vec::new(),
+                },
+                "Sequence" => *action = Action::Sequence(vec![action.clone()]),
                 _ => {}
             }
         }
@@ -348,11 +653,89 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
     let mut set_value_new_value = String::new();
 
     match action {
+        Action::DecrementVariable(var_name) | Action::ToggleVariable(var_name) | Action::ClearList(var_name) => {
+            variable_picker(ui, id.with("var"), "Variable:", var_name, known_variables);
+        }
+        Action::AddToVariable(var_name, value) | Action::PushToList(var_name, value) => {
+            variable_picker(ui, id.with("var"), "Variable:", var_name, known_variables);
+            ui.horizontal(|ui| {
+                ui.label(if matches!(action_type, "Add") { "Amount:" } else { "Value:" });
+                ui.text_edit_singleline(value).on_hover_text("Rust expression, e.g. 5 or \"text\".to_string()");
+            });
+        }
+        Action::CopyVariable { from, to } => {
+            variable_picker(ui, id.with("from"), "From:", from, known_variables);
+            variable_picker(ui, id.with("to"), "To:", to, known_variables);
+        }
+        Action::SetWindowOpen { window, open } => {
+            ui.horizontal(|ui| {
+                ui.label("Window:");
+                let title = |id: &Uuid| {
+                    known_targets.iter().find_map(|t| match t {
+                        ActionTarget::Window { id: window_id, title } if window_id == id => Some(title.clone()),
+                        _ => None,
+                    })
+                };
+                egui::ComboBox::from_id_salt(id.with("window"))
+                    .selected_text(title(window).unwrap_or_else(|| "(missing)".to_string()))
+                    .show_ui(ui, |ui| {
+                        for target in &known_targets {
+                            if let ActionTarget::Window { id: window_id, title } = target {
+                                ui.selectable_value(window, *window_id, title);
+                            }
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(open, true, "Open");
+                ui.radio_value(open, false, "Close");
+            });
+        }
+        Action::SelectTab { container, tab } => {
+            let tabs_of = |id: &Uuid| {
+                known_targets.iter().find_map(|t| match t {
+                    ActionTarget::Tabs { id: tabs_id, tabs } if tabs_id == id => Some(tabs.clone()),
+                    _ => None,
+                })
+            };
+            ui.horizontal(|ui| {
+                ui.label("Tabs:");
+                egui::ComboBox::from_id_salt(id.with("tabs"))
+                    .selected_text(tabs_of(container).map_or("(missing)".to_string(), |tabs| tabs.join(" | ")))
+                    .show_ui(ui, |ui| {
+                        for target in &known_targets {
+                            if let ActionTarget::Tabs { id: tabs_id, tabs } = target {
+                                if ui.selectable_label(container == tabs_id, tabs.join(" | ")).clicked() {
+                                    *container = *tabs_id;
+                                    *tab = 0;
+                                }
+                            }
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("Tab:");
+                let tabs = tabs_of(container).unwrap_or_default();
+                egui::ComboBox::from_id_salt(id.with("tab"))
+                    .selected_text(tabs.get(*tab).cloned().unwrap_or_else(|| format!("#{}", *tab + 1)))
+                    .show_ui(ui, |ui| {
+                        for (index, name) in tabs.iter().enumerate() {
+                            ui.selectable_value(tab, index, name);
+                        }
+                    });
+            });
+        }
+        Action::OpenUrl(text) | Action::CopyToClipboard(text) => {
+            ui.horizontal(|ui| {
+                ui.label(if matches!(action_type, "Url") { "URL:" } else { "Text:" });
+                ui.text_edit_singleline(text).on_hover_text(TEXT_PLACEHOLDER_HINT);
+            });
+        }
         Action::IncrementVariable(var_name) => {
             ui.horizontal(|ui| {
                 ui.label("Variable:");
                 let mut selected_var = var_name.clone();
-                egui::ComboBox::from_id_salt(format!("inc_var_{}", action_ptr))
+                egui::ComboBox::from_id_salt(id.with("inc_var"))
                     .selected_text(&selected_var)
                     .show_ui(ui, |ui| {
                         for var in known_variables {
@@ -369,7 +752,7 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
             ui.horizontal(|ui| {
                 ui.label("Variable:");
                 let mut selected_var = var_name.clone();
-                egui::ComboBox::from_id_salt(format!("set_var_{}", action_ptr))
+                egui::ComboBox::from_id_salt(id.with("set_var"))
                     .selected_text(&selected_var)
                     .show_ui(ui, |ui| {
                         for var in known_variables {
@@ -390,6 +773,22 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
                 }
             });
         }
+        Action::NavigateTo(screen) => {
+            ui.horizontal(|ui| {
+                ui.label("Screen:");
+                if known_screens.is_empty() {
+                    ui.text_edit_singleline(screen);
+                } else {
+                    egui::ComboBox::from_id_salt(id.with("nav_screen"))
+                        .selected_text(screen.as_str())
+                        .show_ui(ui, |ui| {
+                            for name in &known_screens {
+                                ui.selectable_value(screen, name.clone(), name);
+                            }
+                        });
+                }
+            });
+        }
         Action::Custom(code) => {
             ui.label("Rust Code:");
             let code_editor = egui::TextEdit::multiline(code)
@@ -406,6 +805,43 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
                 }
             }
         }
+        Action::If { condition, then, otherwise } => {
+            ui.horizontal(|ui| {
+                ui.label("If");
+                egui::ComboBox::from_id_salt(id.with("if_var"))
+                    .selected_text(&condition.variable)
+                    .show_ui(ui, |ui| {
+                        for var in known_variables {
+                            ui.selectable_value(&mut condition.variable, var.clone(), var);
+                        }
+                    });
+                egui::ComboBox::from_id_salt(id.with("if_op"))
+                    .selected_text(condition.op.to_string())
+                    .width(60.0)
+                    .show_ui(ui, |ui| {
+                        for op in CompareOp::ALL {
+                            ui.selectable_value(&mut condition.op, op, op.to_string());
+                        }
+                    });
+                if condition.op.takes_value() {
+                    ui.add(egui::TextEdit::singleline(&mut condition.value).desired_width(60.0))
+                        .on_hover_text("Rust expression, e.g. 10 or \"text\"");
+                }
+            });
+            ui.label("Then:");
+            ui.indent(id.with("if_then"), |ui| {
+                render_action_list(ui, id.with("then_list"), then, known_variables);
+            });
+            ui.label("Else:");
+            ui.indent(id.with("if_else"), |ui| {
+                render_action_list(ui, id.with("else_list"), otherwise, known_variables);
+            });
+        }
+        Action::Sequence(actions) => {
+            ui.indent(id.with("sequence"), |ui| {
+                render_action_list(ui, id.with("sequence_list"), actions, known_variables);
+            });
+        }
     }
 
     // Apply updates after the match to avoid borrow conflicts
@@ -422,33 +858,52 @@ fn render_action_editor(ui: &mut egui::Ui, action: &mut crate::model::Action, kn
 }
 
 /// Alignment options for layout containers
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum LayoutAlignment {
+    #[default]
     Start,
     Center,
     End,
 }
 
-impl Default for LayoutAlignment {
-    fn default() -> Self {
-        Self::Start
+impl LayoutAlignment {
+    /// Inspector names, in variant order
+    pub const OPTIONS: &'static [&'static str] = &["Left", "Center", "Right"];
+    const ALL: [LayoutAlignment; 3] = [LayoutAlignment::Start, LayoutAlignment::Center, LayoutAlignment::End];
+}
+
+impl From<LayoutAlignment> for PropertyValue {
+    fn from(value: LayoutAlignment) -> Self {
+        PropertyValue::Choice(value as usize)
     }
 }
 
-/// A container that arranges children vertically.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct VerticalLayout {
-    pub id: Uuid,
-    pub children: Vec<Box<dyn WidgetNode>>,
-    pub spacing: f32,
-    #[serde(default)]
-    pub padding: f32,
-    #[serde(default)]
-    pub min_width: Option<f32>,
-    #[serde(default)]
-    pub max_width: Option<f32>,
-    #[serde(default)]
-    pub alignment: LayoutAlignment,
+impl TryFrom<PropertyValue> for LayoutAlignment {
+    type Error = PropertyValue;
+    fn try_from(value: PropertyValue) -> Result<Self, Self::Error> {
+        match value {
+            PropertyValue::Choice(index) if index < Self::ALL.len() => Ok(Self::ALL[index]),
+            other => Err(other),
+        }
+    }
+}
+
+widget! {
+    /// A container that arranges children vertically.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct VerticalLayout {
+        pub id: Uuid,
+        pub children: Vec<Box<dyn WidgetNode>>,
+        pub spacing: f32,
+        #[serde(default)]
+        pub padding: f32,
+        #[serde(default)]
+        pub min_width: Option<f32>,
+        #[serde(default)]
+        pub max_width: Option<f32>,
+        #[serde(default)]
+        pub alignment: LayoutAlignment,
+    }
 }
 
 impl Default for VerticalLayout {
@@ -461,6 +916,7 @@ impl Default for VerticalLayout {
             min_width: None,
             max_width: None,
             alignment: LayoutAlignment::Start,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -476,6 +932,7 @@ impl WidgetNode for VerticalLayout {
             min_width: self.min_width,
             max_width: self.max_width,
             alignment: self.alignment,
+            common: self.common.clone(),
         })
     }
 
@@ -483,6 +940,44 @@ impl WidgetNode for VerticalLayout {
         self.id
     }
 
+    widget_properties! { self;
+        spacing: PropertyDescriptor::new(
+            "spacing",
+            "Spacing",
+            PropertyKind::Float { min: 0.0, max: 50.0, speed: 0.5 },
+            5.0,
+        )
+        .in_group("Layout Options"),
+        padding: PropertyDescriptor::new(
+            "padding",
+            "Padding",
+            PropertyKind::Float { min: 0.0, max: 50.0, speed: 0.5 },
+            0.0,
+        )
+        .in_group("Layout Options"),
+        alignment: PropertyDescriptor::new(
+            "alignment",
+            "Alignment",
+            PropertyKind::Choice(LayoutAlignment::OPTIONS),
+            LayoutAlignment::Start,
+        )
+        .in_group("Layout Options"),
+        min_width: PropertyDescriptor::new(
+            "min_width",
+            "Min Width",
+            PropertyKind::OptionalFloat { min: 0.0, max: 1000.0, initial: 100.0 },
+            PropertyValue::OptionalFloat(None),
+        )
+        .in_group("Size Constraints"),
+        max_width: PropertyDescriptor::new(
+            "max_width",
+            "Max Width",
+            PropertyKind::OptionalFloat { min: 0.0, max: 2000.0, initial: 500.0 },
+            PropertyValue::OptionalFloat(None),
+        )
+        .in_group("Size Constraints"),
+    }
+
     fn name(&self) -> &str {
         "Vertical Layout"
     }
@@ -535,7 +1030,7 @@ impl WidgetNode for VerticalLayout {
         }
 
         // Handle container selection via border
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, self.padding.max(8.0), self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, self.padding.max(8.0), self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -549,73 +1044,20 @@ impl WidgetNode for VerticalLayout {
         }
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Vertical Layout Settings");
-        ui.label(format!("ID: {}", self.id));
-
-        ui.separator();
-        ui.label("Layout Options:");
-
-        ui.horizontal(|ui| {
-            ui.label("Spacing:");
-            ui.add(egui::DragValue::new(&mut self.spacing).speed(0.5).range(0.0..=50.0));
-            reset_button(ui, &mut self.spacing, 5.0);
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Padding:");
-            ui.add(egui::DragValue::new(&mut self.padding).speed(0.5).range(0.0..=50.0));
-            reset_button(ui, &mut self.padding, 0.0);
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Alignment:");
-            egui::ComboBox::from_id_salt("vlayout_align")
-                .selected_text(match self.alignment {
-                    LayoutAlignment::Start => "Left",
-                    LayoutAlignment::Center => "Center",
-                    LayoutAlignment::End => "Right",
-                })
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.alignment, LayoutAlignment::Start, "Left");
-                    ui.selectable_value(&mut self.alignment, LayoutAlignment::Center, "Center");
-                    ui.selectable_value(&mut self.alignment, LayoutAlignment::End, "Right");
-                });
-        });
-
-        ui.separator();
-        ui.label("Size Constraints:");
-
-        ui.horizontal(|ui| {
-            let mut has_min = self.min_width.is_some();
-            if ui.checkbox(&mut has_min, "Min Width:").changed() {
-                self.min_width = if has_min { Some(100.0) } else { None };
-            }
-            if let Some(ref mut min_w) = self.min_width {
-                ui.add(egui::DragValue::new(min_w).speed(1.0).range(0.0..=1000.0));
-            }
-        });
+    // ... VerticalLayout ...
 
-        ui.horizontal(|ui| {
-            let mut has_max = self.max_width.is_some();
-            if ui.checkbox(&mut has_max, "Max Width:").changed() {
-                self.max_width = if has_max { Some(500.0) } else { None };
-            }
-            if let Some(ref mut max_w) = self.max_width {
-                ui.add(egui::DragValue::new(max_w).speed(1.0).range(0.0..=2000.0));
+    // RECURSION: Generate code for the layout and all children
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        ui.vertical(|ui| {
+            for child in &self.children {
+                preview_widget(ui, child.as_ref(), preview);
             }
         });
-
-        ui.separator();
-        ui.label(format!("Children count: {}", self.children.len()));
     }
 
-    // ... VerticalLayout ...
-
-    // RECURSION: Generate code for the layout and all children
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         // 1. Generate token streams for all children
-        let child_streams: Vec<_> = self.children.iter().map(|c| c.codegen()).collect();
+        let child_streams: Vec<_> = self.children.iter().map(|c| ctx.widget_code(c.as_ref())).collect();
 
         // 2. Wrap them in the egui vertical builder
         quote! {
@@ -635,12 +1077,14 @@ impl WidgetNode for VerticalLayout {
     }
 }
 
-/// A container that arranges children horizontally.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct HorizontalLayout {
-    pub id: Uuid,
-    pub children: Vec<Box<dyn WidgetNode>>,
-    pub spacing: f32,
+widget! {
+    /// A container that arranges children horizontally.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct HorizontalLayout {
+        pub id: Uuid,
+        pub children: Vec<Box<dyn WidgetNode>>,
+        pub spacing: f32,
+    }
 }
 
 impl Default for HorizontalLayout {
@@ -649,6 +1093,7 @@ impl Default for HorizontalLayout {
             id: Uuid::new_v4(),
             children: Vec::new(),
             spacing: 5.0,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -660,12 +1105,23 @@ impl WidgetNode for HorizontalLayout {
             id: self.id,
             children: self.children.iter().map(|c| c.clone_box()).collect(),
             spacing: self.spacing,
+            common: self.common.clone(),
         })
     }
 
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        spacing: PropertyDescriptor::new(
+            "spacing",
+            "Spacing",
+            PropertyKind::Float { min: f64::NEG_INFINITY, max: f64::INFINITY, speed: 0.1 },
+            5.0,
+        ),
+    }
+
     fn name(&self) -> &str {
         "Horizontal Layout"
     }
@@ -698,7 +1154,7 @@ impl WidgetNode for HorizontalLayout {
         }
 
         // Handle container selection via border
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -711,19 +1167,16 @@ impl WidgetNode for HorizontalLayout {
         }
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Horizontal Layout Settings");
-        ui.label(format!("ID: {}", self.id));
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
         ui.horizontal(|ui| {
-            ui.label("Spacing:");
-            ui.add(egui::DragValue::new(&mut self.spacing).speed(0.1));
-            reset_button(ui, &mut self.spacing, 5.0);
+            for child in &self.children {
+                preview_widget(ui, child.as_ref(), preview);
+            }
         });
-        ui.label(format!("Children count: {}", self.children.len()));
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        let child_streams: Vec<_> = self.children.iter().map(|c| c.codegen()).collect();
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        let child_streams: Vec<_> = self.children.iter().map(|c| ctx.widget_code(c.as_ref())).collect();
         quote! {
             ui.horizontal(|ui| {
                 #(#child_streams)*
@@ -739,13 +1192,15 @@ impl WidgetNode for HorizontalLayout {
     }
 }
 
-/// A container that arranges children in a grid with a specified number of columns.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GridLayout {
-    pub id: Uuid,
-    pub children: Vec<Box<dyn WidgetNode>>,
-    pub columns: usize,
-    pub spacing: f32,
+widget! {
+    /// A container that arranges children in a grid with a specified number of columns.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct GridLayout {
+        pub id: Uuid,
+        pub children: Vec<Box<dyn WidgetNode>>,
+        pub columns: usize,
+        pub spacing: f32,
+    }
 }
 
 impl Default for GridLayout {
@@ -755,6 +1210,7 @@ impl Default for GridLayout {
             children: Vec::new(),
             columns: 2, // Default to 2 columns
             spacing: 5.0,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -767,6 +1223,7 @@ impl WidgetNode for GridLayout {
             children: self.children.iter().map(|c| c.clone_box()).collect(),
             columns: self.columns,
             spacing: self.spacing,
+            common: self.common.clone(),
         })
     }
 
@@ -774,6 +1231,21 @@ impl WidgetNode for GridLayout {
         self.id
     }
 
+    widget_properties! { self;
+        columns: PropertyDescriptor::new(
+            "columns",
+            "Columns",
+            PropertyKind::Integer { min: 1, max: 10 },
+            PropertyValue::Integer(2),
+        ),
+        spacing: PropertyDescriptor::new(
+            "spacing",
+            "Spacing",
+            PropertyKind::Float { min: f64::NEG_INFINITY, max: f64::INFINITY, speed: 0.1 },
+            5.0,
+        ),
+    }
+
     fn name(&self) -> &str {
         "Grid Layout"
     }
@@ -816,7 +1288,7 @@ impl WidgetNode for GridLayout {
         }
 
         // Handle container selection via border
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -829,23 +1301,19 @@ impl WidgetNode for GridLayout {
         }
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Grid Layout Settings");
-        ui.label(format!("ID: {}", self.id));
-        ui.horizontal(|ui| {
-            ui.label("Columns:");
-            ui.add(egui::DragValue::new(&mut self.columns).speed(1.0).range(1..=10));
-            reset_button(ui, &mut self.columns, 2);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Spacing:");
-            ui.add(egui::DragValue::new(&mut self.spacing).speed(0.1));
-            reset_button(ui, &mut self.spacing, 5.0);
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        ui.vertical(|ui| {
+            for row in self.children.chunks(self.columns.max(1)) {
+                ui.horizontal(|ui| {
+                    for child in row {
+                        preview_widget(ui, child.as_ref(), preview);
+                    }
+                });
+            }
         });
-        ui.label(format!("Children count: {}", self.children.len()));
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let columns = self.columns;
 
         // Group children into rows
@@ -853,11 +1321,11 @@ impl WidgetNode for GridLayout {
         let mut current_row = Vec::new();
 
         for (idx, child) in self.children.iter().enumerate() {
-            current_row.push(child.codegen());
+            current_row.push(ctx.widget_code(child.as_ref()));
 
             // When we reach the column count or the last child, complete the row
             if (idx + 1) % columns == 0 || idx == self.children.len() - 1 {
-                let row_widgets = current_row.drain(..).collect::<Vec<_>>();
+                let row_widgets = std::mem::take(&mut current_row);
                 row_streams.push(quote! {
                     ui.horizontal(|ui| {
                         #(#row_widgets)*
@@ -882,19 +1350,21 @@ impl WidgetNode for GridLayout {
     }
 }
 
-/// A concrete implementation of a Button.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ButtonWidget {
-    pub id: Uuid,
-    pub text: String,
+widget! {
+    /// A concrete implementation of a Button.
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ButtonWidget {
+        pub id: Uuid,
+        pub text: String,
 
-    // Maps event type to action
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
+        // Maps event type to action
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
 
-    // Maps property name (e.g. "text") to variable name (e.g. "counter")
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+        // Maps property name (e.g. "text") to variable name (e.g. "counter")
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for ButtonWidget {
@@ -904,6 +1374,7 @@ impl Default for ButtonWidget {
             text: "Click Me".to_string(),
             events: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -918,10 +1389,37 @@ impl WidgetNode for ButtonWidget {
         self.id
     }
 
+    widget_properties! { self;
+        text: PropertyDescriptor::new(
+            "text",
+            "Label Text",
+            PropertyKind::Text { placeholders: true },
+            "Click Me",
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Button"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     // Render logic for the Editor Canvas
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let is_selected = selection.contains(&self.id);
@@ -959,7 +1457,7 @@ impl WidgetNode for ButtonWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -973,50 +1471,7 @@ impl WidgetNode for ButtonWidget {
     // The "Inspectable" pattern: The widget defines its own property UI.
     // [cite: 137]
     fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Button Properties");
-        ui.label(format!("ID: {}", self.id));
-
-        ui.horizontal(|ui| {
-            ui.label("Label Text:");
-
-            // Check binding status
-            let is_bound = self.bindings.contains_key("text");
-            let mut bound_mode = is_bound;
-
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    // Switch to bound: set default if empty
-                    if !known_variables.is_empty() {
-                        self.bindings
-                            .insert("text".to_string(), known_variables[0].clone());
-                    } else {
-                        self.bindings.insert("text".to_string(), "".to_string());
-                    }
-                } else {
-                    self.bindings.remove("text");
-                }
-            }
-
-            if bound_mode {
-                let current_var = self.bindings.get("text").cloned().unwrap_or_default();
-                let mut selected_var = current_var.clone();
-
-                egui::ComboBox::from_id_salt("btn_txt_bind")
-                    .selected_text(&selected_var)
-                    .show_ui(ui, |ui| {
-                        for var in known_variables {
-                            ui.selectable_value(&mut selected_var, var.clone(), var);
-                        }
-                    });
-
-                if selected_var != current_var {
-                    self.bindings.insert("text".to_string(), selected_var);
-                }
-            } else {
-                ui.text_edit_singleline(&mut self.text);
-                reset_button(ui, &mut self.text, "Click Me".to_string());
-            }
-        });
+        inspect_properties(self, ui, known_variables);
 
         ui.separator();
         ui.heading("Events");
@@ -1034,8 +1489,8 @@ impl WidgetNode for ButtonWidget {
         for event in &possible_events {
             if self.events.contains_key(event) {
                 ui.collapsing(format!("{}", event), |ui| {
-                    if let Some(action) = self.events.get_mut(event) {
-                        render_action_editor(ui, action, known_variables);
+                    if let Some(actions) = self.events.get_mut(event) {
+                        render_action_editor(ui, actions, known_variables);
                     }
                     if ui.button("Remove Event").clicked() {
                         events_to_remove = Some(*event);
@@ -1049,7 +1504,7 @@ impl WidgetNode for ButtonWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -1057,29 +1512,38 @@ impl WidgetNode for ButtonWidget {
         }
     }
 
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        use crate::model::WidgetEvent;
+
+        let response = ui.button(preview_text(preview, &self.bindings, "text", &self.text));
+        if response.clicked() {
+            preview.fire(self.name(), &self.events, WidgetEvent::Clicked);
+        }
+        if response.hovered() {
+            preview.fire(self.name(), &self.events, WidgetEvent::Hovered);
+        }
+    }
+
     // Generating the AST for the final Rust application.
-    // [cite: 184]
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         use crate::model::WidgetEvent;
 
         let label_tokens = if let Some(var_name) = self.bindings.get("text") {
-            let ident = quote::format_ident!("{}", var_name);
-            quote! { &self.#ident }
+            ctx.display_expr(var_name)
         } else {
-            let text = &self.text;
-            quote! { #text }
+            ctx.text_expr(&self.text)
         };
 
         // Generate code for the clicked event if present
-        let clicked_code = if let Some(action) = self.events.get(&WidgetEvent::Clicked) {
-            action.to_code()
+        let clicked_code = if let Some(actions) = self.events.get(&WidgetEvent::Clicked) {
+            ctx.event_code(self.name(), self.id, WidgetEvent::Clicked, actions)
         } else {
             quote! {}
         };
 
         // Generate code for the hovered event if present
-        let hovered_code = if let Some(action) = self.events.get(&WidgetEvent::Hovered) {
-            let action_code = action.to_code();
+        let hovered_code = if let Some(actions) = self.events.get(&WidgetEvent::Hovered) {
+            let action_code = ctx.event_code(self.name(), self.id, WidgetEvent::Hovered, actions);
             quote! {
                 if response.hovered() {
                     #action_code
@@ -1097,17 +1561,169 @@ impl WidgetNode for ButtonWidget {
             #hovered_code
         }
     }
+
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        let mut errors = Vec::new();
+        // Any variable type can be displayed; it only has to exist
+        if let Some(var_name) = self.bindings.get("text") {
+            if !variables.contains_key(var_name) {
+                errors.push(format!("Button '{}' bound to missing variable '{}'", self.id, var_name));
+            }
+        } else {
+            errors.extend(validate_text("Button", self.id, &self.text, variables));
+        }
+        errors
+    }
 }
 
 // ===================== HELPERS =====================
 
 /// Helper to render a small reset button if the value differs from default
 pub fn reset_button<T: PartialEq + Clone>(ui: &mut Ui, value: &mut T, default: T) {
-    if *value != default {
-        if ui.small_button("↺").on_hover_text("Reset to default").clicked() {
-            *value = default;
+    if *value != default && ui.small_button("↺").on_hover_text("Reset to default").clicked() {
+        *value = default;
+    }
+}
+
+/// The Inspector's editor for any widget, built from its property descriptors
+pub fn inspect_properties<W: WidgetNode + ?Sized>(node: &mut W, ui: &mut Ui, known_variables: &[String]) {
+    ui.heading(format!("{} Properties", node.name()));
+    ui.label(format!("ID: {}", node.id()));
+
+    let mut group = None;
+    for descriptor in node.properties() {
+        if let Some(heading) = descriptor.group.filter(|heading| group != Some(*heading)) {
+            group = Some(heading);
+            ui.separator();
+            ui.label(format!("{}:", heading));
+        }
+        ui.push_id(descriptor.name, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", descriptor.label));
+                if descriptor.bindable && binding_editor(ui, node, descriptor.name, known_variables) {
+                    return;
+                }
+                let Some(mut value) = descriptor.get(node) else {
+                    return;
+                };
+                let before = value.clone();
+                property_editor(ui, &descriptor.kind, &mut value, false);
+                reset_button(ui, &mut value, descriptor.default.clone());
+                if value != before {
+                    descriptor.set(node, value);
+                }
+            });
+        });
+    }
+
+    if !node.slots().is_empty() {
+        if group.is_some() {
+            ui.separator();
+        }
+        ui.label(format!("Children count: {}", node.child_widgets().len()));
+    }
+}
+
+/// "Bind" toggle and variable picker for a bindable property. Returns whether it is bound.
+fn binding_editor<W: WidgetNode + ?Sized>(ui: &mut Ui, node: &mut W, property: &str, known_variables: &[String]) -> bool {
+    let Some(bindings) = node.bindings_mut() else {
+        return false;
+    };
+    let mut bound = bindings.contains_key(property);
+    if ui.checkbox(&mut bound, "Bind").changed() {
+        if bound {
+            let first = known_variables.first().cloned().unwrap_or_default();
+            bindings.insert(property.to_string(), first);
+        } else {
+            bindings.remove(property);
+        }
+    }
+    let Some(var) = bindings.get_mut(property) else {
+        return false;
+    };
+    egui::ComboBox::from_id_salt("binding")
+        .selected_text(var.as_str())
+        .show_ui(ui, |ui| {
+            for v in known_variables {
+                ui.selectable_value(var, v.clone(), v);
+            }
+        });
+    true
+}
+
+/// Edit a property value with the control that suits its kind. Returns whether it changed.
+/// `mixed` shows the value as indeterminate, for several widgets whose values differ.
+pub fn property_editor(ui: &mut Ui, kind: &PropertyKind, value: &mut PropertyValue, mixed: bool) -> bool {
+    let changed = match (kind, value) {
+        (PropertyKind::Text { placeholders }, PropertyValue::Text(text)) => {
+            if mixed {
+                text.clear();
+            }
+            let response = ui.add(egui::TextEdit::singleline(text).hint_text(if mixed { "Mixed" } else { "" }));
+            let changed = response.changed();
+            if *placeholders {
+                response.on_hover_text(TEXT_PLACEHOLDER_HINT);
+            }
+            changed
+        }
+        (PropertyKind::Code, PropertyValue::Text(code)) => ui
+            .add(
+                egui::TextEdit::multiline(code)
+                    .code_editor()
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed(),
+        (PropertyKind::Bool, PropertyValue::Bool(value)) => {
+            if mixed {
+                *value = false;
+            }
+            ui.add(egui::Checkbox::new(value, "").indeterminate(mixed)).changed()
+        }
+        (PropertyKind::Integer { min, max }, PropertyValue::Integer(n)) => {
+            ui.add(egui::DragValue::new(n).range(*min..=*max)).changed()
+        }
+        (PropertyKind::Float { min, max, speed }, PropertyValue::Float(n)) => {
+            ui.add(egui::DragValue::new(n).speed(*speed).range(*min..=*max)).changed()
+        }
+        (PropertyKind::OptionalFloat { min, max, initial }, PropertyValue::OptionalFloat(n)) => {
+            let mut is_set = n.is_some();
+            let mut changed = ui.checkbox(&mut is_set, "").on_hover_text("Unset leaves the size to egui").changed();
+            if changed {
+                *n = is_set.then_some(*initial);
+            }
+            if let Some(n) = n {
+                changed |= ui.add(egui::DragValue::new(n).speed(1.0).range(*min..=*max)).changed();
+            }
+            changed
+        }
+        (PropertyKind::Color, PropertyValue::Color(rgba)) => {
+            let hex = PropertyValue::Color(*rgba).to_string();
+            ui.color_edit_button_rgba_unmultiplied(rgba).on_hover_text(hex).changed()
+        }
+        (PropertyKind::Choice(options), PropertyValue::Choice(index)) => {
+            let mut changed = false;
+            let selected = if mixed { "Mixed" } else { options.get(*index).copied().unwrap_or_default() };
+            egui::ComboBox::from_id_salt("choice")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (i, option) in options.iter().enumerate() {
+                        changed |= ui.selectable_value(index, i, *option).changed();
+                    }
+                });
+            changed
         }
+        _ => {
+            ui.label("(mismatched value)");
+            false
+        }
+    };
+    // Controls without an indeterminate look show one widget's value, flagged as mixed
+    if mixed && !matches!(kind, PropertyKind::Text { .. } | PropertyKind::Bool | PropertyKind::Choice(_)) {
+        ui.label(egui::RichText::new("mixed").italics().weak())
+            .on_hover_text("The selected widgets have different values");
     }
+    changed
 }
 
 /// Render a preview of a widget for drag-and-drop visualization
@@ -1170,6 +1786,9 @@ pub fn render_widget_preview(ui: &mut Ui, widget_type: &str, accent_color: egui:
         "Hyperlink" => {
             ui.hyperlink_to("Link", "");
         }
+        "Custom Code" => {
+            ui.monospace("{ } ui.label(..);");
+        }
         "Color Picker" => {
             let mut color = [0.3f32, 0.6, 0.9];
             ui.color_edit_button_rgb(&mut color);
@@ -1214,12 +1833,14 @@ pub fn render_widget_preview(ui: &mut Ui, widget_type: &str, accent_color: egui:
 }
 
 // --- Label ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct LabelWidget {
-    pub id: Uuid,
-    pub text: String,
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct LabelWidget {
+        pub id: Uuid,
+        pub text: String,
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for LabelWidget {
@@ -1228,6 +1849,7 @@ impl Default for LabelWidget {
             id: Uuid::new_v4(),
             text: "Label".to_string(),
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -1241,6 +1863,24 @@ impl WidgetNode for LabelWidget {
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        text: PropertyDescriptor::new(
+            "text",
+            "Text",
+            PropertyKind::Text { placeholders: true },
+            "Label",
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Label"
     }
@@ -1276,7 +1916,7 @@ impl WidgetNode for LabelWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -1287,62 +1927,44 @@ impl WidgetNode for LabelWidget {
         response.on_hover_text(format!("Label: {}\nID: {}", self.text, self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Label Properties");
-        ui.horizontal(|ui| {
-            ui.label("Text:");
-            // Simplified binding logic for prototype
-            let is_bound = self.bindings.contains_key("text");
-            let mut bound_mode = is_bound;
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    let first = known_variables.first().cloned().unwrap_or_default();
-                    self.bindings.insert("text".to_string(), first);
-                } else {
-                    self.bindings.remove("text");
-                }
-            }
-            if bound_mode {
-                let current = self.bindings.get("text").cloned().unwrap_or_default();
-                let mut selected = current.clone();
-                egui::ComboBox::from_id_salt("lbl_bind")
-                    .selected_text(&selected)
-                    .show_ui(ui, |ui| {
-                        for v in known_variables {
-                            ui.selectable_value(&mut selected, v.clone(), v);
-                        }
-                    });
-                if selected != current {
-                    self.bindings.insert("text".to_string(), selected);
-                }
-            } else {
-                ui.text_edit_singleline(&mut self.text);
-                reset_button(ui, &mut self.text, "Label".to_string());
-            }
-        });
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        ui.label(preview_text(preview, &self.bindings, "text", &self.text));
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let content = if let Some(var) = self.bindings.get("text") {
-            let ident = quote::format_ident!("{}", var);
-            quote! { &self.#ident }
+            ctx.display_expr(var)
         } else {
-            let t = &self.text;
-            quote! { #t }
+            ctx.text_expr(&self.text)
         };
         quote! { ui.label(#content); }
     }
+
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        let mut errors = Vec::new();
+        // Any variable type can be displayed; it only has to exist
+        if let Some(var_name) = self.bindings.get("text") {
+            if !variables.contains_key(var_name) {
+                errors.push(format!("Label '{}' bound to missing variable '{}'", self.id, var_name));
+            }
+        } else {
+            errors.extend(validate_text("Label", self.id, &self.text, variables));
+        }
+        errors
+    }
 }
 
 // --- TextEdit ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct TextEditWidget {
-    pub id: Uuid,
-    pub text: String, // Fallback if not bound
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct TextEditWidget {
+        pub id: Uuid,
+        pub text: String, // Fallback if not bound
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for TextEditWidget {
@@ -1352,6 +1974,7 @@ impl Default for TextEditWidget {
             text: "".to_string(),
             events: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -1365,10 +1988,38 @@ impl WidgetNode for TextEditWidget {
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        text: PropertyDescriptor::new(
+            "value",
+            "Text",
+            PropertyKind::Text { placeholders: false },
+            "",
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Text Edit"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let is_selected = selection.contains(&self.id);
 
@@ -1406,7 +2057,7 @@ impl WidgetNode for TextEditWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -1418,35 +2069,8 @@ impl WidgetNode for TextEditWidget {
     }
 
     fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Text Edit Properties");
-        ui.label("Note: Binds to String variables.");
-        ui.horizontal(|ui| {
-            ui.label("Bind Value:");
-            let is_bound = self.bindings.contains_key("value");
-            let mut bound_mode = is_bound;
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    let first = known_variables.first().cloned().unwrap_or_default();
-                    self.bindings.insert("value".to_string(), first);
-                } else {
-                    self.bindings.remove("value");
-                }
-            }
-            if bound_mode {
-                let current = self.bindings.get("value").cloned().unwrap_or_default();
-                let mut selected = current.clone();
-                egui::ComboBox::from_id_salt("txt_bind")
-                    .selected_text(&selected)
-                    .show_ui(ui, |ui| {
-                        for v in known_variables {
-                            ui.selectable_value(&mut selected, v.clone(), v);
-                        }
-                    });
-                if selected != current {
-                    self.bindings.insert("value".to_string(), selected);
-                }
-            }
-        });
+        inspect_properties(self, ui, known_variables);
+        ui.label("Note: Non-string variables are parsed from the text.");
 
         ui.separator();
         ui.heading("Events");
@@ -1464,8 +2088,8 @@ impl WidgetNode for TextEditWidget {
         for event in &possible_events {
             if self.events.contains_key(event) {
                 ui.collapsing(format!("{}", event), |ui| {
-                    if let Some(action) = self.events.get_mut(event) {
-                        render_action_editor(ui, action, known_variables);
+                    if let Some(actions) = self.events.get_mut(event) {
+                        render_action_editor(ui, actions, known_variables);
                     }
                     if ui.button("Remove Event").clicked() {
                         events_to_remove = Some(*event);
@@ -1479,7 +2103,7 @@ impl WidgetNode for TextEditWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -1487,20 +2111,61 @@ impl WidgetNode for TextEditWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        use crate::model::WidgetEvent;
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        use crate::model::{VariableType, WidgetEvent};
+
+        let Some(var) = self.bindings.get("value") else {
+            ui.label("Unbound TextEdit");
+            return;
+        };
+
+        let response = match preview.variables.get(var).map(|v| v.v_type.clone()) {
+            Some(VariableType::String) | None => {
+                let mut text = preview.display(var).unwrap_or_default();
+                let response = ui.text_edit_singleline(&mut text);
+                if response.changed() {
+                    preview.set_value(var, text);
+                    preview.fire(self.name(), &self.events, WidgetEvent::Changed);
+                }
+                response
+            }
+            Some(_) => {
+                // Same buffer behaviour as the generated code for non-string variables
+                let mut buffer = preview.widget_value(self.id, preview.display(var).unwrap_or_default());
+                let response = ui.text_edit_singleline(&mut buffer);
+                if response.changed() && preview.set_parsed(var, &buffer) {
+                    preview.fire(self.name(), &self.events, WidgetEvent::Changed);
+                }
+                if !response.has_focus() {
+                    buffer = preview.display(var).unwrap_or_default();
+                }
+                preview.set_widget_value(self.id, buffer);
+                response
+            }
+        };
+
+        if response.gained_focus() {
+            preview.fire(self.name(), &self.events, WidgetEvent::Focused);
+        }
+        if response.lost_focus() {
+            preview.fire(self.name(), &self.events, WidgetEvent::LostFocus);
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        use crate::model::{VariableType, WidgetEvent};
 
         if let Some(var) = self.bindings.get("value") {
             let ident = quote::format_ident!("{}", var);
 
-            let changed_code = if let Some(action) = self.events.get(&WidgetEvent::Changed) {
-                action.to_code()
+            let changed_code = if let Some(actions) = self.events.get(&WidgetEvent::Changed) {
+                ctx.event_code(self.name(), self.id, WidgetEvent::Changed, actions)
             } else {
                 quote! {}
             };
 
-            let focused_code = if let Some(action) = self.events.get(&WidgetEvent::Focused) {
-                let action_code = action.to_code();
+            let focused_code = if let Some(actions) = self.events.get(&WidgetEvent::Focused) {
+                let action_code = ctx.event_code(self.name(), self.id, WidgetEvent::Focused, actions);
                 quote! {
                     if response.gained_focus() {
                         #action_code
@@ -1510,8 +2175,8 @@ impl WidgetNode for TextEditWidget {
                 quote! {}
             };
 
-            let lost_focus_code = if let Some(action) = self.events.get(&WidgetEvent::LostFocus) {
-                let action_code = action.to_code();
+            let lost_focus_code = if let Some(actions) = self.events.get(&WidgetEvent::LostFocus) {
+                let action_code = ctx.event_code(self.name(), self.id, WidgetEvent::LostFocus, actions);
                 quote! {
                     if response.lost_focus() {
                         #action_code
@@ -1521,13 +2186,39 @@ impl WidgetNode for TextEditWidget {
                 quote! {}
             };
 
-            quote! {
-                let response = ui.text_edit_singleline(&mut self.#ident);
-                if response.changed() {
-                    #changed_code
+            match ctx.variable_type(var) {
+                Some(VariableType::String) | None => quote! {
+                    let response = ui.text_edit_singleline(&mut self.#ident);
+                    if response.changed() {
+                        #changed_code
+                    }
+                    #focused_code
+                    #lost_focus_code
+                },
+                Some(_) => {
+                    // Non-string variables are edited through a text buffer. The value only
+                    // updates when the text parses, and the buffer resyncs once focus is lost.
+                    let initial = ctx.variables.get(var).map(|v| v.value.clone()).unwrap_or_default();
+                    let buffer = ctx.register_field(
+                        &format!("{}_text_{}", var, self.id.simple()),
+                        quote! { String },
+                        quote! { #initial.to_string() },
+                    );
+                    quote! {
+                        let response = ui.text_edit_singleline(&mut self.#buffer);
+                        if response.changed() {
+                            if let Ok(value) = self.#buffer.trim().parse() {
+                                self.#ident = value;
+                                #changed_code
+                            }
+                        }
+                        if !response.has_focus() {
+                            self.#buffer = self.#ident.to_string();
+                        }
+                        #focused_code
+                        #lost_focus_code
+                    }
                 }
-                #focused_code
-                #lost_focus_code
             }
         } else {
             // If not bound, it's just a placeholder or needs local state we don't track well in codegen yet
@@ -1538,12 +2229,14 @@ impl WidgetNode for TextEditWidget {
     fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
         let mut errors = Vec::new();
         if let Some(var_name) = self.bindings.get("value") {
-            if let Some(var) = variables.get(var_name) {
-                if var.v_type != crate::model::VariableType::String {
-                    errors.push(format!("TextEdit '{}' bound to non-string variable '{}' ({})", self.id, var_name, var.v_type));
-                }
-            } else {
-                errors.push(format!("TextEdit '{}' bound to missing variable '{}'", self.id, var_name));
+            // Numbers and booleans are parsed from the text; other types can't be typed in
+            match variables.get(var_name) {
+                Some(var) if !var.v_type.is_scalar() => errors.push(format!(
+                    "TextEdit '{}' bound to non-text variable '{}' ({})",
+                    self.id, var_name, var.v_type
+                )),
+                Some(_) => {}
+                None => errors.push(format!("TextEdit '{}' bound to missing variable '{}'", self.id, var_name)),
             }
         }
         errors
@@ -1551,15 +2244,17 @@ impl WidgetNode for TextEditWidget {
 }
 
 // --- Checkbox ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct CheckboxWidget {
-    pub id: Uuid,
-    pub label: String,
-    pub checked: bool,
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct CheckboxWidget {
+        pub id: Uuid,
+        pub label: String,
+        pub checked: bool,
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for CheckboxWidget {
@@ -1570,6 +2265,7 @@ impl Default for CheckboxWidget {
             checked: false,
             events: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -1583,10 +2279,34 @@ impl WidgetNode for CheckboxWidget {
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        label: PropertyDescriptor::new("label", "Label", PropertyKind::Text { placeholders: true }, "Check me"),
+        checked: PropertyDescriptor::new("checked", "Checked", PropertyKind::Bool, false).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Checkbox"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let is_selected = selection.contains(&self.id);
 
@@ -1645,7 +2365,7 @@ impl WidgetNode for CheckboxWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -1657,38 +2377,7 @@ impl WidgetNode for CheckboxWidget {
     }
 
     fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Checkbox Properties");
-        ui.horizontal(|ui| {
-            ui.label("Label:");
-            ui.text_edit_singleline(&mut self.label);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Bind Checked (Bool):");
-            let is_bound = self.bindings.contains_key("checked");
-            let mut bound_mode = is_bound;
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    let first = known_variables.first().cloned().unwrap_or_default();
-                    self.bindings.insert("checked".to_string(), first);
-                } else {
-                    self.bindings.remove("checked");
-                }
-            }
-            if bound_mode {
-                let current = self.bindings.get("checked").cloned().unwrap_or_default();
-                let mut selected = current.clone();
-                egui::ComboBox::from_id_salt("chk_bind")
-                    .selected_text(&selected)
-                    .show_ui(ui, |ui| {
-                        for v in known_variables {
-                            ui.selectable_value(&mut selected, v.clone(), v);
-                        }
-                    });
-                if selected != current {
-                    self.bindings.insert("checked".to_string(), selected);
-                }
-            }
-        });
+        inspect_properties(self, ui, known_variables);
 
         ui.separator();
         ui.heading("Events");
@@ -1699,8 +2388,8 @@ impl WidgetNode for CheckboxWidget {
         let event = crate::model::WidgetEvent::Changed;
         if self.events.contains_key(&event) {
             ui.collapsing(format!("{}", event), |ui| {
-                if let Some(action) = self.events.get_mut(&event) {
-                    render_action_editor(ui, action, known_variables);
+                if let Some(actions) = self.events.get_mut(&event) {
+                    render_action_editor(ui, actions, known_variables);
                 }
                 if ui.button("Remove Event").clicked() {
                     events_to_remove = Some(event);
@@ -1713,7 +2402,7 @@ impl WidgetNode for CheckboxWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -1721,14 +2410,30 @@ impl WidgetNode for CheckboxWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        use crate::model::WidgetEvent;
+
+        if let Some(var) = self.bindings.get("checked") {
+            let mut checked = preview.boolean(var).unwrap_or_default();
+            if ui.checkbox(&mut checked, preview.interpolate(&self.label)).changed() {
+                preview.set_value(var, checked.to_string());
+                preview.fire(self.name(), &self.events, WidgetEvent::Changed);
+            }
+        } else {
+            // Unbound checkboxes reset every frame in the generated app
+            let mut temp = self.checked;
+            ui.checkbox(&mut temp, preview.interpolate(&self.label));
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         use crate::model::WidgetEvent;
 
-        let label = &self.label;
+        let label = ctx.text_expr(&self.label);
         if let Some(var) = self.bindings.get("checked") {
             let ident = quote::format_ident!("{}", var);
-            let changed_code = if let Some(action) = self.events.get(&WidgetEvent::Changed) {
-                action.to_code()
+            let changed_code = if let Some(actions) = self.events.get(&WidgetEvent::Changed) {
+                ctx.event_code(self.name(), self.id, WidgetEvent::Changed, actions)
             } else {
                 quote! {}
             };
@@ -1757,21 +2462,24 @@ impl WidgetNode for CheckboxWidget {
                 errors.push(format!("Checkbox '{}' bound to missing variable '{}'", self.id, var_name));
             }
         }
+        errors.extend(validate_text("Checkbox", self.id, &self.label, variables));
         errors
     }
 }
 
 // --- Slider ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SliderWidget {
-    pub id: Uuid,
-    pub min: f64,
-    pub max: f64,
-    pub value: f64,
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct SliderWidget {
+        pub id: Uuid,
+        pub min: f64,
+        pub max: f64,
+        pub value: f64,
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for SliderWidget {
@@ -1783,6 +2491,7 @@ impl Default for SliderWidget {
             value: 50.0,
             events: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -1796,10 +2505,50 @@ impl WidgetNode for SliderWidget {
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        min: PropertyDescriptor::new(
+            "min",
+            "Min",
+            PropertyKind::Float { min: f64::NEG_INFINITY, max: f64::INFINITY, speed: 1.0 },
+            0.0,
+        ),
+        max: PropertyDescriptor::new(
+            "max",
+            "Max",
+            PropertyKind::Float { min: f64::NEG_INFINITY, max: f64::INFINITY, speed: 1.0 },
+            100.0,
+        ),
+        value: PropertyDescriptor::new(
+            "value",
+            "Value",
+            PropertyKind::Float { min: self.min.min(self.max), max: self.max.max(self.min), speed: 1.0 },
+            50.0,
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Slider"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let is_selected = selection.contains(&self.id);
 
@@ -1854,7 +2603,7 @@ impl WidgetNode for SliderWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -1866,42 +2615,7 @@ impl WidgetNode for SliderWidget {
     }
 
     fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Slider Properties");
-        ui.horizontal(|ui| {
-            ui.label("Min:");
-            ui.add(egui::DragValue::new(&mut self.min));
-            reset_button(ui, &mut self.min, 0.0);
-            ui.label("Max:");
-            ui.add(egui::DragValue::new(&mut self.max));
-            reset_button(ui, &mut self.max, 100.0);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Bind Value (Num):");
-            let is_bound = self.bindings.contains_key("value");
-            let mut bound_mode = is_bound;
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    let first = known_variables.first().cloned().unwrap_or_default();
-                    self.bindings.insert("value".to_string(), first);
-                } else {
-                    self.bindings.remove("value");
-                }
-            }
-            if bound_mode {
-                let current = self.bindings.get("value").cloned().unwrap_or_default();
-                let mut selected = current.clone();
-                egui::ComboBox::from_id_salt("sld_bind")
-                    .selected_text(&selected)
-                    .show_ui(ui, |ui| {
-                        for v in known_variables {
-                            ui.selectable_value(&mut selected, v.clone(), v);
-                        }
-                    });
-                if selected != current {
-                    self.bindings.insert("value".to_string(), selected);
-                }
-            }
-        });
+        inspect_properties(self, ui, known_variables);
 
         ui.separator();
         ui.heading("Events");
@@ -1912,8 +2626,8 @@ impl WidgetNode for SliderWidget {
         let event = crate::model::WidgetEvent::Changed;
         if self.events.contains_key(&event) {
             ui.collapsing(format!("{}", event), |ui| {
-                if let Some(action) = self.events.get_mut(&event) {
-                    render_action_editor(ui, action, known_variables);
+                if let Some(actions) = self.events.get_mut(&event) {
+                    render_action_editor(ui, actions, known_variables);
                 }
                 if ui.button("Remove Event").clicked() {
                     events_to_remove = Some(event);
@@ -1926,7 +2640,7 @@ impl WidgetNode for SliderWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -1934,24 +2648,55 @@ impl WidgetNode for SliderWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        use crate::model::WidgetEvent;
+
+        if let Some(var) = self.bindings.get("value") {
+            let mut value = preview.number(var).unwrap_or_default();
+            let mut slider = egui::Slider::new(&mut value, self.min..=self.max);
+            if preview.variables.get(var).is_some_and(|v| v.v_type.is_integer()) {
+                slider = slider.integer();
+            }
+            if ui.add(slider).changed() {
+                preview.set_number(var, value);
+                preview.fire(self.name(), &self.events, WidgetEvent::Changed);
+            }
+        } else {
+            let mut temp = self.value;
+            ui.add(egui::Slider::new(&mut temp, self.min..=self.max));
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         use crate::model::WidgetEvent;
 
         let min = self.min;
         let max = self.max;
         if let Some(var) = self.bindings.get("value") {
             let ident = quote::format_ident!("{}", var);
-            let changed_code = if let Some(action) = self.events.get(&WidgetEvent::Changed) {
-                action.to_code()
+            let changed_code = if let Some(actions) = self.events.get(&WidgetEvent::Changed) {
+                ctx.event_code(self.name(), self.id, WidgetEvent::Changed, actions)
             } else {
                 quote! {}
             };
-            // Use `as _` to allow the compiler to infer the correct numeric type (f64, f32, i32, etc)
-            // for the range limits based on the variable's type.
-            quote! {
-                if ui.add(egui::Slider::new(&mut self.#ident, (#min as _)..=(#max as _))).changed() {
-                    #changed_code
+            match ctx.variable_type(var).filter(|t| t.is_numeric()) {
+                // Name the slider's numeric type and write the range in it
+                Some(v_type) => {
+                    let ty = Compiler::rust_type(v_type);
+                    let min = Compiler::numeric_literal(v_type, min);
+                    let max = Compiler::numeric_literal(v_type, max);
+                    quote! {
+                        if ui.add(egui::Slider::new::<#ty>(&mut self.#ident, #min..=#max)).changed() {
+                            #changed_code
+                        }
+                    }
                 }
+                // Let the compiler infer the type; `validate` reports the bad binding
+                None => quote! {
+                    if ui.add(egui::Slider::new(&mut self.#ident, (#min as _)..=(#max as _))).changed() {
+                        #changed_code
+                    }
+                },
             }
         } else {
             let val = self.value;
@@ -1966,9 +2711,8 @@ impl WidgetNode for SliderWidget {
         let mut errors = Vec::new();
         if let Some(var_name) = self.bindings.get("value") {
             if let Some(var) = variables.get(var_name) {
-                match var.v_type {
-                    crate::model::VariableType::Integer | crate::model::VariableType::Float => {},
-                    _ => errors.push(format!("Slider '{}' bound to non-numeric variable '{}' ({})", self.id, var_name, var.v_type)),
+                if !var.v_type.is_numeric() {
+                    errors.push(format!("Slider '{}' bound to non-numeric variable '{}' ({})", self.id, var_name, var.v_type));
                 }
             } else {
                 errors.push(format!("Slider '{}' bound to missing variable '{}'", self.id, var_name));
@@ -1979,12 +2723,14 @@ impl WidgetNode for SliderWidget {
 }
 
 // --- ProgressBar ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ProgressBarWidget {
-    pub id: Uuid,
-    pub value: f32, // 0.0 to 1.0
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ProgressBarWidget {
+        pub id: Uuid,
+        pub value: f32, // 0.0 to 1.0
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for ProgressBarWidget {
@@ -1993,6 +2739,7 @@ impl Default for ProgressBarWidget {
             id: Uuid::new_v4(),
             value: 0.5,
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2006,6 +2753,24 @@ impl WidgetNode for ProgressBarWidget {
     fn id(&self) -> Uuid {
         self.id
     }
+
+    widget_properties! { self;
+        value: PropertyDescriptor::new(
+            "value",
+            "Progress",
+            PropertyKind::Float { min: 0.0, max: 1.0, speed: 0.01 },
+            0.5,
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Progress Bar"
     }
@@ -2046,7 +2811,7 @@ impl WidgetNode for ProgressBarWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -2057,45 +2822,20 @@ impl WidgetNode for ProgressBarWidget {
         response.on_hover_text(format!("Progress Bar: {:.0}%\nID: {}", self.value * 100.0, self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Progress Bar Properties");
-        ui.horizontal(|ui| {
-            ui.label("Progress (0.0 - 1.0):");
-            ui.add(egui::Slider::new(&mut self.value, 0.0..=1.0));
-            reset_button(ui, &mut self.value, 0.5);
-        });
-        ui.horizontal(|ui| {
-            ui.label("Bind Value:");
-            let is_bound = self.bindings.contains_key("value");
-            let mut bound_mode = is_bound;
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    let first = known_variables.first().cloned().unwrap_or_default();
-                    self.bindings.insert("value".to_string(), first);
-                } else {
-                    self.bindings.remove("value");
-                }
-            }
-            if bound_mode {
-                let current = self.bindings.get("value").cloned().unwrap_or_default();
-                let mut selected = current.clone();
-                egui::ComboBox::from_id_salt("progress_bind")
-                    .selected_text(&selected)
-                    .show_ui(ui, |ui| {
-                        for v in known_variables {
-                            ui.selectable_value(&mut selected, v.clone(), v);
-                        }
-                    });
-                if selected != current {
-                    self.bindings.insert("value".to_string(), selected);
-                }
-            }
-        });
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        let value = match self.bindings.get("value") {
+            Some(var) => preview.number(var).unwrap_or_default() as f32,
+            None => self.value,
+        };
+        ui.add(egui::ProgressBar::new(value).show_percentage());
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         if let Some(var) = self.bindings.get("value") {
             let ident = quote::format_ident!("{}", var);
+            if ctx.variable_type(var) == Some(&crate::model::VariableType::F32) {
+                return quote! { ui.add(egui::ProgressBar::new(self.#ident).show_percentage()); };
+            }
             quote! { ui.add(egui::ProgressBar::new(self.#ident as f32).show_percentage()); }
         } else {
             let val = self.value;
@@ -2107,9 +2847,8 @@ impl WidgetNode for ProgressBarWidget {
         let mut errors = Vec::new();
         if let Some(var_name) = self.bindings.get("value") {
             if let Some(var) = variables.get(var_name) {
-                match var.v_type {
-                    crate::model::VariableType::Integer | crate::model::VariableType::Float => {},
-                    _ => errors.push(format!("ProgressBar '{}' bound to non-numeric variable '{}' ({})", self.id, var_name, var.v_type)),
+                if !var.v_type.is_numeric() {
+                    errors.push(format!("ProgressBar '{}' bound to non-numeric variable '{}' ({})", self.id, var_name, var.v_type));
                 }
             } else {
                 errors.push(format!("ProgressBar '{}' bound to missing variable '{}'", self.id, var_name));
@@ -2120,16 +2859,18 @@ impl WidgetNode for ProgressBarWidget {
 }
 
 // --- ComboBox ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ComboBoxWidget {
-    pub id: Uuid,
-    pub label: String,
-    pub options: Vec<String>,
-    pub selected: usize,
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ComboBoxWidget {
+        pub id: Uuid,
+        pub label: String,
+        pub options: Vec<String>,
+        pub selected: usize,
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
+    }
 }
 
 impl Default for ComboBoxWidget {
@@ -2141,6 +2882,7 @@ impl Default for ComboBoxWidget {
             selected: 0,
             bindings: std::collections::HashMap::new(),
             events: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2155,10 +2897,38 @@ impl WidgetNode for ComboBoxWidget {
         self.id
     }
 
+    widget_properties! { self;
+        label: PropertyDescriptor::new("label", "Label", PropertyKind::Text { placeholders: true }, "Select:"),
+        selected: PropertyDescriptor::new(
+            "selected",
+            "Selected",
+            PropertyKind::Integer { min: 0, max: self.options.len().saturating_sub(1) as i64 },
+            PropertyValue::Integer(0),
+        ).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "ComboBox"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let is_selected = selection.contains(&self.id);
 
@@ -2225,7 +2995,7 @@ impl WidgetNode for ComboBoxWidget {
 
         // Add context menu
         response.context_menu(|ui| {
-            render_widget_context_menu(ui, self.id);
+            render_widget_context_menu(ui, self.id, self.name());
         });
 
         if is_selected {
@@ -2241,7 +3011,7 @@ impl WidgetNode for ComboBoxWidget {
 
         ui.horizontal(|ui| {
             ui.label("Label:");
-            ui.text_edit_singleline(&mut self.label);
+            ui.text_edit_singleline(&mut self.label).on_hover_text(TEXT_PLACEHOLDER_HINT);
             reset_button(ui, &mut self.label, "Select...".to_string());
         });
 
@@ -2314,8 +3084,8 @@ impl WidgetNode for ComboBoxWidget {
         for event in &possible_events {
             if self.events.contains_key(event) {
                 ui.collapsing(format!("{}", event), |ui| {
-                    if let Some(action) = self.events.get_mut(event) {
-                        render_action_editor(ui, action, known_variables);
+                    if let Some(actions) = self.events.get_mut(event) {
+                        render_action_editor(ui, actions, known_variables);
                     }
                     if ui.button("Remove Event").clicked() {
                         events_to_remove = Some(*event);
@@ -2329,7 +3099,7 @@ impl WidgetNode for ComboBoxWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -2337,22 +3107,68 @@ impl WidgetNode for ComboBoxWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        let label = &self.label;
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        let var = self.bindings.get("selected");
+        let mut selected = match var {
+            Some(var) => preview.number(var).unwrap_or_default().max(0.0) as usize,
+            None => self.selected,
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(preview.interpolate(&self.label));
+            egui::ComboBox::from_id_salt(egui::Id::new("preview_combo").with(self.id))
+                .selected_text(self.options.get(selected).map(String::as_str).unwrap_or(""))
+                .show_ui(ui, |ui| {
+                    for (idx, opt) in self.options.iter().enumerate() {
+                        ui.selectable_value(&mut selected, idx, opt);
+                    }
+                });
+        });
+
+        if let Some(var) = var {
+            preview.set_number(var, selected as f64);
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        let label = ctx.text_expr(&self.label);
         let options: Vec<_> = self.options.iter().map(|s| s.as_str()).collect();
 
         if let Some(var) = self.bindings.get("selected") {
             let ident = quote::format_ident!("{}", var);
+            let var_type = ctx.variable_type(var).filter(|t| t.is_integer());
+            if let Some(v_type) = var_type.filter(|t| **t != crate::model::VariableType::Usize) {
+                // Other integer types select through a usize copy of the index
+                let ty = Compiler::rust_type(v_type);
+                return quote! {
+                    ui.horizontal(|ui| {
+                        ui.label(#label);
+                        let options = vec![#(#options),*];
+                        let mut selected = usize::try_from(self.#ident).unwrap_or(usize::MAX);
+                        let selected_text = options.get(selected).copied().unwrap_or("");
+                        egui::ComboBox::from_label("")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                for (idx, opt) in options.iter().enumerate() {
+                                    ui.selectable_value(&mut selected, idx, *opt);
+                                }
+                            });
+                        if let Ok(value) = #ty::try_from(selected) {
+                            self.#ident = value;
+                        }
+                    });
+                };
+            }
             quote! {
                 ui.horizontal(|ui| {
                     ui.label(#label);
                     let options = vec![#(#options),*];
-                    let selected_text = options.get(self.#ident).unwrap_or(&"");
+                    let selected_text = options.get(self.#ident).copied().unwrap_or("");
                     egui::ComboBox::from_label("")
                         .selected_text(selected_text)
                         .show_ui(ui, |ui| {
                             for (idx, opt) in options.iter().enumerate() {
-                                ui.selectable_value(&mut self.#ident, idx, opt);
+                                ui.selectable_value(&mut self.#ident, idx, *opt);
                             }
                         });
                 });
@@ -2364,35 +3180,52 @@ impl WidgetNode for ComboBoxWidget {
                     ui.label(#label);
                     let mut selected = #selected;
                     let options = vec![#(#options),*];
-                    let selected_text = options.get(selected).unwrap_or(&"");
+                    let selected_text = options.get(selected).copied().unwrap_or("");
                     egui::ComboBox::from_label("")
                         .selected_text(selected_text)
                         .show_ui(ui, |ui| {
                             for (idx, opt) in options.iter().enumerate() {
-                                ui.selectable_value(&mut selected, idx, opt);
+                                ui.selectable_value(&mut selected, idx, *opt);
                             }
                         });
                 });
             }
         }
     }
+
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(var_name) = self.bindings.get("selected") {
+            if let Some(var) = variables.get(var_name) {
+                if !var.v_type.is_integer() {
+                    errors.push(format!("ComboBox '{}' bound to non-integer variable '{}' ({})", self.id, var_name, var.v_type));
+                }
+            } else {
+                errors.push(format!("ComboBox '{}' bound to missing variable '{}'", self.id, var_name));
+            }
+        }
+        errors.extend(validate_text("ComboBox", self.id, &self.label, variables));
+        errors
+    }
 }
 
 // --- Image ---
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ImageWidget {
-    pub id: Uuid,
-    pub path: String,
-    pub width: Option<f32>,
-    pub height: Option<f32>,
-    /// Optional reference to an asset by name (from AssetManager)
-    #[serde(default)]
-    pub asset_name: Option<String>,
-    /// Cached filename from the asset (for codegen)
-    #[serde(default)]
-    pub asset_filename: Option<String>,
-    #[serde(default)]
-    pub events: std::collections::HashMap<crate::model::WidgetEvent, crate::model::Action>,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ImageWidget {
+        pub id: Uuid,
+        pub path: String,
+        pub width: Option<f32>,
+        pub height: Option<f32>,
+        /// Optional reference to an asset by name (from AssetManager)
+        #[serde(default)]
+        pub asset_name: Option<String>,
+        /// Cached filename from the asset (for codegen)
+        #[serde(default)]
+        pub asset_filename: Option<String>,
+        #[serde(default, deserialize_with = "crate::model::deserialize_events")]
+        pub events: std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>,
+    }
 }
 
 impl Default for ImageWidget {
@@ -2405,6 +3238,7 @@ impl Default for ImageWidget {
             asset_name: None,
             asset_filename: None,
             events: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2419,20 +3253,45 @@ impl WidgetNode for ImageWidget {
         self.id
     }
 
+    widget_properties! { self;
+        width: PropertyDescriptor::new(
+            "width",
+            "Width",
+            PropertyKind::OptionalFloat { min: 10.0, max: 1000.0, initial: 100.0 },
+            Some(100.0),
+        ),
+        height: PropertyDescriptor::new(
+            "height",
+            "Height",
+            PropertyKind::OptionalFloat { min: 10.0, max: 1000.0, initial: 100.0 },
+            PropertyValue::OptionalFloat(None),
+        ),
+    }
+
     fn name(&self) -> &str {
         "Image"
     }
 
+    fn events(&self) -> Option<&std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&self.events)
+    }
+
+    fn events_mut(
+        &mut self,
+    ) -> Option<&mut std::collections::HashMap<crate::model::WidgetEvent, Vec<crate::model::Action>>> {
+        Some(&mut self.events)
+    }
+
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
         let response = if self.path.is_empty() {
             ui.label("[No Image]")
         } else {
-            ui.label(format!("🖼 {}", self.path.split('/').last().unwrap_or(&self.path)))
+            ui.label(format!("🖼 {}", self.path.split('/').next_back().unwrap_or(&self.path)))
         };
         let widget_rect = response.rect;
 
         // Create selection overlay for better hit detection
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
 
         let is_selected = selection.contains(&self.id);
@@ -2583,8 +3442,8 @@ impl WidgetNode for ImageWidget {
         for event in &possible_events {
             if self.events.contains_key(event) {
                 ui.collapsing(format!("{}", event), |ui| {
-                    if let Some(action) = self.events.get_mut(event) {
-                        render_action_editor(ui, action, &[]);
+                    if let Some(actions) = self.events.get_mut(event) {
+                        render_action_editor(ui, actions, &[]);
                     }
                     if ui.button("Remove Event").clicked() {
                         events_to_remove = Some(*event);
@@ -2598,7 +3457,7 @@ impl WidgetNode for ImageWidget {
         }
 
         if let Some(event) = events_to_add {
-            self.events.insert(event, crate::model::Action::Custom(String::new()));
+            self.events.insert(event, Vec::new());
         }
 
         if let Some(event) = events_to_remove {
@@ -2606,14 +3465,47 @@ impl WidgetNode for ImageWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
         use crate::model::WidgetEvent;
 
-        // Use asset path if an asset is selected, otherwise use manual path
-        let path = if let Some(ref filename) = self.asset_filename {
-            format!("assets/{}", filename)
+        // Assets are loaded from where they were imported from rather than the exported folder
+        let uri = if let Some(ref filename) = self.asset_filename {
+            match preview.asset_path(filename) {
+                Some(path) => format!("file://{}", path.display()),
+                None => format!("file://assets/{}", filename),
+            }
+        } else if self.path.contains("://") {
+            self.path.clone()
         } else {
+            format!("file://{}", self.path)
+        };
+
+        let mut image = egui::Image::new(uri);
+        image = match (self.width, self.height) {
+            (Some(w), Some(h)) => image.max_size(egui::vec2(w, h)),
+            (Some(w), None) => image.max_width(w),
+            (None, Some(h)) => image.max_height(h),
+            (None, None) => image,
+        };
+
+        egui_extras::install_image_loaders(ui.ctx());
+        let response = ui.add(image);
+        if response.hovered() {
+            preview.fire(self.name(), &self.events, WidgetEvent::Hovered);
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        use crate::model::WidgetEvent;
+
+        // Use asset path if an asset is selected, otherwise use manual path.
+        // Plain paths need the file:// scheme for egui_extras' file loader.
+        let path = if let Some(ref filename) = self.asset_filename {
+            format!("file://assets/{}", filename)
+        } else if self.path.contains("://") {
             self.path.clone()
+        } else {
+            format!("file://{}", self.path)
         };
 
         let size_tokens = match (self.width, self.height) {
@@ -2624,8 +3516,8 @@ impl WidgetNode for ImageWidget {
         };
 
         // Generate code for the hovered event if present
-        let hovered_code = if let Some(action) = self.events.get(&WidgetEvent::Hovered) {
-            let action_code = action.to_code();
+        let hovered_code = if let Some(actions) = self.events.get(&WidgetEvent::Hovered) {
+            let action_code = ctx.event_code(self.name(), self.id, WidgetEvent::Hovered, actions);
             quote! {
                 if response.hovered() {
                     #action_code
@@ -2636,6 +3528,7 @@ impl WidgetNode for ImageWidget {
         };
 
         quote! {
+            egui_extras::install_image_loaders(ui.ctx());
             let response = ui.add(
                 egui::Image::new(#path)
                     #size_tokens
@@ -2643,21 +3536,33 @@ impl WidgetNode for ImageWidget {
             #hovered_code
         }
     }
+
+    fn dependencies(&self) -> Vec<CrateDependency> {
+        vec![
+            CrateDependency::new("egui_extras", crate::compiler::EGUI_EXTRAS_VERSION)
+                .with_features(&["file", "image"]),
+            CrateDependency::new("image", crate::compiler::IMAGE_VERSION)
+                .with_features(&["png", "jpeg", "gif", "bmp"]),
+        ]
+    }
 }
 
 // === NEW SIMPLE WIDGETS ===
 
 // --- Separator ---
-/// A visual separator line for layouts
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SeparatorWidget {
-    pub id: Uuid,
+widget! {
+    /// A visual separator line for layouts
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct SeparatorWidget {
+        pub id: Uuid,
+    }
 }
 
 impl Default for SeparatorWidget {
     fn default() -> Self {
         Self {
             id: Uuid::new_v4(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2681,7 +3586,7 @@ impl WidgetNode for SeparatorWidget {
         let widget_rect = response.rect;
 
         // Create selection overlay for better hit detection
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
 
         if selection.contains(&self.id) {
@@ -2691,23 +3596,23 @@ impl WidgetNode for SeparatorWidget {
         overlay.on_hover_text(format!("Separator\nID: {}", self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Separator");
-        ui.label("A visual divider between widgets.");
-        ui.label(format!("ID: {}", self.id));
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut PreviewState) {
+        ui.separator();
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         quote! { ui.separator(); }
     }
 }
 
 // --- Spinner ---
-/// A loading spinner indicator
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SpinnerWidget {
-    pub id: Uuid,
-    pub size: f32,
+widget! {
+    /// A loading spinner indicator
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct SpinnerWidget {
+        pub id: Uuid,
+        pub size: f32,
+    }
 }
 
 impl Default for SpinnerWidget {
@@ -2715,6 +3620,7 @@ impl Default for SpinnerWidget {
         Self {
             id: Uuid::new_v4(),
             size: 20.0,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2729,6 +3635,15 @@ impl WidgetNode for SpinnerWidget {
         self.id
     }
 
+    widget_properties! { self;
+        size: PropertyDescriptor::new(
+            "size",
+            "Size",
+            PropertyKind::Float { min: 10.0, max: 100.0, speed: 1.0 },
+            20.0,
+        ),
+    }
+
     fn name(&self) -> &str {
         "Spinner"
     }
@@ -2738,7 +3653,7 @@ impl WidgetNode for SpinnerWidget {
         let widget_rect = response.rect;
 
         // Create selection overlay for better hit detection
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
 
         if selection.contains(&self.id) {
@@ -2748,29 +3663,25 @@ impl WidgetNode for SpinnerWidget {
         overlay.on_hover_text(format!("Spinner (size: {})\nID: {}", self.size, self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Spinner Properties");
-        ui.horizontal(|ui| {
-            ui.label("Size:");
-            ui.add(egui::DragValue::new(&mut self.size).speed(1.0).range(10.0..=100.0));
-            reset_button(ui, &mut self.size, 20.0);
-        });
-        ui.label(format!("ID: {}", self.id));
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut PreviewState) {
+        ui.add(egui::Spinner::new().size(self.size));
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let size = self.size;
         quote! { ui.add(egui::Spinner::new().size(#size)); }
     }
 }
 
 // --- Hyperlink ---
-/// A clickable hyperlink
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct HyperlinkWidget {
-    pub id: Uuid,
-    pub text: String,
-    pub url: String,
+widget! {
+    /// A clickable hyperlink
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct HyperlinkWidget {
+        pub id: Uuid,
+        pub text: String,
+        pub url: String,
+    }
 }
 
 impl Default for HyperlinkWidget {
@@ -2779,6 +3690,7 @@ impl Default for HyperlinkWidget {
             id: Uuid::new_v4(),
             text: "Click here".to_string(),
             url: "https://example.com".to_string(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -2793,6 +3705,16 @@ impl WidgetNode for HyperlinkWidget {
         self.id
     }
 
+    widget_properties! { self;
+        text: PropertyDescriptor::new("text", "Text", PropertyKind::Text { placeholders: true }, "Click here"),
+        url: PropertyDescriptor::new(
+            "url",
+            "URL",
+            PropertyKind::Text { placeholders: false },
+            "https://example.com",
+        ),
+    }
+
     fn name(&self) -> &str {
         "Hyperlink"
     }
@@ -2802,7 +3724,7 @@ impl WidgetNode for HyperlinkWidget {
         let widget_rect = response.rect;
 
         // Create selection overlay for better hit detection
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
 
         if selection.contains(&self.id) {
@@ -2812,40 +3734,129 @@ impl WidgetNode for HyperlinkWidget {
         overlay.on_hover_text(format!("Hyperlink: {}\nURL: {}\nID: {}", self.text, self.url, self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Hyperlink Properties");
-        ui.horizontal(|ui| {
-            ui.label("Text:");
-            ui.text_edit_singleline(&mut self.text);
-            reset_button(ui, &mut self.text, "Click here".to_string());
-        });
-        ui.horizontal(|ui| {
-            ui.label("URL:");
-            ui.text_edit_singleline(&mut self.url);
-            reset_button(ui, &mut self.url, "https://example.com".to_string());
-        });
-        ui.label(format!("ID: {}", self.id));
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        ui.hyperlink_to(preview.interpolate(&self.text), &self.url);
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        let text = ctx.text_expr(&self.text);
+        let url = &self.url;
+        quote! { ui.hyperlink_to(#text, #url); }
+    }
+
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        validate_text("Hyperlink", self.id, &self.text, variables)
+    }
+}
+
+// --- Custom Code ---
+widget! {
+    /// Opaque Rust code emitted verbatim, e.g. statements the importer could not recognize
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct CustomCodeWidget {
+        pub id: Uuid,
+        pub code: String,
+    }
+}
+
+impl Default for CustomCodeWidget {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            code: "ui.label(\"Custom code\");".to_string(),
+            common: CommonProperties::default(),
+        }
+    }
+}
+
+#[typetag::serde]
+impl WidgetNode for CustomCodeWidget {
+    fn clone_box(&self) -> Box<dyn WidgetNode> {
+        Box::new(self.clone())
+    }
+
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    widget_properties! { self;
+        code: PropertyDescriptor::new("code", "Code", PropertyKind::Code, "ui.label(\"Custom code\");"),
+    }
+
+    fn name(&self) -> &str {
+        "Custom Code"
+    }
+
+    fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
+        // Show the first line of code as a placeholder; it can't be rendered in the editor
+        let first_line = self.code.lines().next().unwrap_or_default();
+        let response = ui.add(
+            egui::Label::new(egui::RichText::new(format!("{{ }} {}", first_line)).monospace().weak())
+                .truncate()
+                .sense(egui::Sense::click()),
+        );
+        let widget_rect = response.rect;
+
+        handle_selection(ui, self.id, response.clicked(), selection);
+
+        response.context_menu(|ui| {
+            render_widget_context_menu(ui, self.id, self.name());
+        });
+
+        if selection.contains(&self.id) {
+            draw_gizmo(ui, widget_rect);
+        }
+
+        response.on_hover_text(format!("Custom Code\n{}\nID: {}", self.code, self.id));
+    }
+
+    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
+        ui.heading("Custom Code");
+        ui.label("Emitted as-is inside the parent layout. `ui` and `self` are in scope.");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.code)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY),
+        );
+        ui.label(format!("ID: {}", self.id));
+    }
+
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut PreviewState) {
+        ui.label(egui::RichText::new("⚙ Custom code (not simulated)").italics().weak())
+            .on_hover_text(&self.code);
+    }
+
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        match self.code.parse::<proc_macro2::TokenStream>() {
+            Ok(tokens) => tokens,
+            Err(_) => quote! { /* Invalid Rust code */ },
+        }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        let text = &self.text;
-        let url = &self.url;
-        quote! { ui.hyperlink_to(#text, #url); }
+    fn validate(&self, _variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.code.parse::<proc_macro2::TokenStream>().is_err() {
+            errors.push(format!("Custom Code '{}' is not valid Rust", self.id));
+        }
+        errors
     }
 }
 
 // --- Window Container ---
-/// A window container widget that represents an egui::Window
-#[derive(Debug, Serialize, Deserialize)]
-pub struct WindowWidget {
-    pub id: Uuid,
-    pub title: String,
-    pub children: Vec<Box<dyn WidgetNode>>,
-    pub closeable: bool,
-    pub collapsible: bool,
-    pub resizable: bool,
-    pub default_width: f32,
-    pub default_height: Option<f32>,
+widget! {
+    /// A window container widget that represents an egui::Window
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct WindowWidget {
+        pub id: Uuid,
+        pub title: String,
+        pub children: Vec<Box<dyn WidgetNode>>,
+        pub closeable: bool,
+        pub collapsible: bool,
+        pub resizable: bool,
+        pub default_width: f32,
+        pub default_height: Option<f32>,
+    }
 }
 
 impl Default for WindowWidget {
@@ -2859,10 +3870,18 @@ impl Default for WindowWidget {
             resizable: true,
             default_width: 300.0,
             default_height: Some(200.0),
+            common: CommonProperties::default(),
         }
     }
 }
 
+impl WindowWidget {
+    /// Name of the app struct field holding the window's open flag
+    pub fn open_field_name(id: Uuid) -> String {
+        format!("window_{}_open", id.simple())
+    }
+}
+
 #[typetag::serde]
 impl WidgetNode for WindowWidget {
     fn clone_box(&self) -> Box<dyn WidgetNode> {
@@ -2875,6 +3894,7 @@ impl WidgetNode for WindowWidget {
             resizable: self.resizable,
             default_width: self.default_width,
             default_height: self.default_height,
+            common: self.common.clone(),
         })
     }
 
@@ -2882,6 +3902,25 @@ impl WidgetNode for WindowWidget {
         self.id
     }
 
+    widget_properties! { self;
+        title: PropertyDescriptor::new("title", "Title", PropertyKind::Text { placeholders: true }, "Window"),
+        closeable: PropertyDescriptor::new("closeable", "Closeable", PropertyKind::Bool, true),
+        collapsible: PropertyDescriptor::new("collapsible", "Collapsible", PropertyKind::Bool, true),
+        resizable: PropertyDescriptor::new("resizable", "Resizable", PropertyKind::Bool, true),
+        default_width: PropertyDescriptor::new(
+            "default_width",
+            "Default Width",
+            PropertyKind::Float { min: 100.0, max: 1000.0, speed: 1.0 },
+            300.0,
+        ),
+        default_height: PropertyDescriptor::new(
+            "default_height",
+            "Default Height",
+            PropertyKind::OptionalFloat { min: 50.0, max: 1000.0, initial: 200.0 },
+            Some(200.0),
+        ),
+    }
+
     fn name(&self) -> &str {
         "Window"
     }
@@ -2938,7 +3977,7 @@ impl WidgetNode for WindowWidget {
 
         // Make the window container selectable only via border (not content area)
         let widget_rect = response.rect;
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -2951,54 +3990,35 @@ impl WidgetNode for WindowWidget {
         }
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Window Properties");
-        ui.label(format!("ID: {}", self.id));
-
-        ui.separator();
-
-        ui.horizontal(|ui| {
-            ui.label("Title:");
-            ui.text_edit_singleline(&mut self.title);
-            reset_button(ui, &mut self.title, "Window".to_string());
-        });
-
-        ui.separator();
-        ui.label("Window Options:");
-
-        ui.checkbox(&mut self.closeable, "Closeable");
-        ui.checkbox(&mut self.collapsible, "Collapsible");
-        ui.checkbox(&mut self.resizable, "Resizable");
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        let mut open = preview.widget_value(self.id, true);
 
-        ui.separator();
-        ui.label("Size:");
-
-        ui.horizontal(|ui| {
-            ui.label("Default Width:");
-            ui.add(egui::DragValue::new(&mut self.default_width).speed(1.0).range(100.0..=1000.0));
-            reset_button(ui, &mut self.default_width, 300.0);
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Default Height:");
-            let mut has_height = self.default_height.is_some();
-            if ui.checkbox(&mut has_height, "").changed() {
-                self.default_height = if has_height { Some(200.0) } else { None };
-            }
-            if let Some(ref mut h) = self.default_height {
-                ui.add(egui::DragValue::new(h).speed(1.0).range(50.0..=1000.0));
+        // Keep the window on the canvas instead of floating over the editor panels
+        let mut window = egui::Window::new(preview.interpolate(&self.title))
+            .id(egui::Id::new("preview_window").with(self.id))
+            .open(&mut open)
+            .collapsible(self.collapsible)
+            .resizable(self.resizable)
+            .default_width(self.default_width)
+            .constrain_to(ui.clip_rect());
+        if let Some(h) = self.default_height {
+            window = window.default_height(h);
+        }
+        window.show(ui.ctx(), |ui| {
+            for child in &self.children {
+                preview_widget(ui, child.as_ref(), preview);
             }
         });
 
-        ui.label(format!("Children count: {}", self.children.len()));
+        preview.set_widget_value(self.id, open);
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        let title = &self.title;
-        let child_streams: Vec<_> = self.children.iter().map(|c| c.codegen()).collect();
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        let title = ctx.text_expr(&self.title);
+        let child_streams: Vec<_> = self.children.iter().map(|c| ctx.widget_code(c.as_ref())).collect();
 
-        // Generate a state variable name from the window title
-        let state_var = quote::format_ident!("window_{}_open", self.id.to_string().replace("-", "_"));
+        // Each window gets its own open flag on the app struct
+        let state_var = ctx.register_field(&Self::open_field_name(self.id), quote! { bool }, quote! { true });
 
         let collapsible = self.collapsible;
         let resizable = self.resizable;
@@ -3009,22 +4029,40 @@ impl WidgetNode for WindowWidget {
         } else {
             quote! {}
         };
+        // egui identifies windows by title, which changes when it shows variables
+        let id_token = if crate::interpolation::has_placeholders(&self.title) {
+            let id = state_var.to_string();
+            quote! { .id(egui::Id::new(#id)) }
+        } else {
+            quote! {}
+        };
 
-        // Note: The window state variable needs to be added to the app struct
-        // This codegen assumes the app will have `window_XXX_open: bool` field
+        // The flag is copied out so handler calls inside the window can borrow self;
+        // a handler that closed the window keeps it closed
         quote! {
+            let mut window_open = self.#state_var;
             egui::Window::new(#title)
-                .open(&mut self.#state_var)
+                #id_token
+                .open(&mut window_open)
                 .collapsible(#collapsible)
                 .resizable(#resizable)
                 .default_width(#default_width)
                 #height_token
-                .show(ctx, |ui| {
+                .show(ui.ctx(), |ui| {
                     #(#child_streams)*
                 });
+            self.#state_var &= window_open;
         }
     }
 
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        validate_text("Window", self.id, &self.title, variables)
+    }
+
+    fn action_target(&self) -> Option<crate::model::ActionTarget> {
+        Some(crate::model::ActionTarget::Window { id: self.id, title: self.title.clone() })
+    }
+
     fn children(&self) -> Option<&Vec<Box<dyn WidgetNode>>> {
         Some(&self.children)
     }
@@ -3035,12 +4073,14 @@ impl WidgetNode for WindowWidget {
 }
 
 // --- TabContainer ---
-/// A tabbed container for organizing content
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TabContainerWidget {
-    pub id: Uuid,
-    pub tabs: Vec<TabItem>,
-    pub selected_tab: usize,
+widget! {
+    /// A tabbed container for organizing content
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct TabContainerWidget {
+        pub id: Uuid,
+        pub tabs: Vec<TabItem>,
+        pub selected_tab: usize,
+    }
 }
 
 /// A single tab with a name and children
@@ -3074,10 +4114,18 @@ impl Default for TabContainerWidget {
                 },
             ],
             selected_tab: 0,
+            common: CommonProperties::default(),
         }
     }
 }
 
+impl TabContainerWidget {
+    /// Name of the app struct field holding the selected tab's index
+    pub fn selected_field_name(id: Uuid) -> String {
+        format!("tab_{}_selected", id.simple())
+    }
+}
+
 #[typetag::serde]
 impl WidgetNode for TabContainerWidget {
     fn clone_box(&self) -> Box<dyn WidgetNode> {
@@ -3085,6 +4133,7 @@ impl WidgetNode for TabContainerWidget {
             id: self.id,
             tabs: self.tabs.clone(),
             selected_tab: self.selected_tab,
+            common: self.common.clone(),
         })
     }
 
@@ -3092,6 +4141,15 @@ impl WidgetNode for TabContainerWidget {
         self.id
     }
 
+    widget_properties! { self;
+        selected_tab: PropertyDescriptor::new(
+            "selected_tab",
+            "Selected Tab",
+            PropertyKind::Integer { min: 0, max: self.tabs.len().saturating_sub(1) as i64 },
+            PropertyValue::Integer(0),
+        ),
+    }
+
     fn name(&self) -> &str {
         "Tab Container"
     }
@@ -3145,7 +4203,7 @@ impl WidgetNode for TabContainerWidget {
 
         // Make the tab container selectable only via border (not content area)
         let widget_rect = response.rect;
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -3208,12 +4266,36 @@ impl WidgetNode for TabContainerWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        let mut selected = preview.widget_value(self.id, self.selected_tab);
+
+        ui.horizontal(|ui| {
+            for (idx, tab) in self.tabs.iter().enumerate() {
+                if ui.selectable_label(selected == idx, &tab.name).clicked() {
+                    selected = idx;
+                }
+            }
+        });
+        ui.separator();
+        if let Some(tab) = self.tabs.get(selected) {
+            for child in &tab.children {
+                preview_widget(ui, child.as_ref(), preview);
+            }
+        }
+
+        preview.set_widget_value(self.id, selected);
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let tab_count = self.tabs.len();
 
+        // Each tab container tracks its own selected index on the app struct
+        let initial_tab = self.selected_tab;
+        let selected = ctx.register_field(&Self::selected_field_name(self.id), quote! { usize }, quote! { #initial_tab });
+
         let tab_names: Vec<_> = self.tabs.iter().map(|t| &t.name).collect();
         let tab_contents: Vec<_> = self.tabs.iter().map(|tab| {
-            let child_streams: Vec<_> = tab.children.iter().map(|c| c.codegen()).collect();
+            let child_streams: Vec<_> = tab.children.iter().map(|c| ctx.widget_code(c.as_ref())).collect();
             quote! {
                 #(#child_streams)*
             }
@@ -3224,13 +4306,13 @@ impl WidgetNode for TabContainerWidget {
         quote! {
             ui.horizontal(|ui| {
                 #(
-                    if ui.selectable_label(self.selected_tab == #tab_indices, #tab_names).clicked() {
-                        self.selected_tab = #tab_indices;
+                    if ui.selectable_label(self.#selected == #tab_indices, #tab_names).clicked() {
+                        self.#selected = #tab_indices;
                     }
                 )*
             });
             ui.separator();
-            match self.selected_tab {
+            match self.#selected {
                 #(
                     #tab_indices => {
                         #tab_contents
@@ -3241,27 +4323,52 @@ impl WidgetNode for TabContainerWidget {
         }
     }
 
+    fn action_target(&self) -> Option<crate::model::ActionTarget> {
+        Some(crate::model::ActionTarget::Tabs {
+            id: self.id,
+            tabs: self.tabs.iter().map(|tab| tab.name.clone()).collect(),
+        })
+    }
+
     fn children(&self) -> Option<&Vec<Box<dyn WidgetNode>>> {
-        // Return children of the currently selected tab
-        self.tabs.get(self.selected_tab).map(|tab| &tab.children)
+        None // Children are per tab; see `slots`
     }
 
     fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn WidgetNode>>> {
-        // Return children of the currently selected tab
-        self.tabs.get_mut(self.selected_tab).map(|tab| &mut tab.children)
+        None
+    }
+
+    fn slots(&self) -> Vec<ChildSlot<'_>> {
+        self.tabs
+            .iter()
+            .map(|tab| ChildSlot { name: tab.name.clone(), children: &tab.children })
+            .collect()
+    }
+
+    fn slots_mut(&mut self) -> Vec<ChildSlotMut<'_>> {
+        self.tabs
+            .iter_mut()
+            .map(|tab| ChildSlotMut { name: tab.name.clone(), children: &mut tab.children })
+            .collect()
+    }
+
+    fn default_slot(&self) -> usize {
+        self.selected_tab
     }
 }
 
 // --- ScrollArea ---
-/// A scrollable container widget
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ScrollAreaWidget {
-    pub id: Uuid,
-    pub children: Vec<Box<dyn WidgetNode>>,
-    pub scroll_horizontal: bool,
-    pub scroll_vertical: bool,
-    pub max_height: Option<f32>,
-    pub max_width: Option<f32>,
+widget! {
+    /// A scrollable container widget
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct ScrollAreaWidget {
+        pub id: Uuid,
+        pub children: Vec<Box<dyn WidgetNode>>,
+        pub scroll_horizontal: bool,
+        pub scroll_vertical: bool,
+        pub max_height: Option<f32>,
+        pub max_width: Option<f32>,
+    }
 }
 
 impl Default for ScrollAreaWidget {
@@ -3273,6 +4380,7 @@ impl Default for ScrollAreaWidget {
             scroll_vertical: true,
             max_height: Some(200.0),
             max_width: None,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -3287,6 +4395,7 @@ impl WidgetNode for ScrollAreaWidget {
             scroll_vertical: self.scroll_vertical,
             max_height: self.max_height,
             max_width: self.max_width,
+            common: self.common.clone(),
         })
     }
 
@@ -3294,6 +4403,23 @@ impl WidgetNode for ScrollAreaWidget {
         self.id
     }
 
+    widget_properties! { self;
+        scroll_horizontal: PropertyDescriptor::new("scroll_horizontal", "Horizontal Scroll", PropertyKind::Bool, false),
+        scroll_vertical: PropertyDescriptor::new("scroll_vertical", "Vertical Scroll", PropertyKind::Bool, true),
+        max_height: PropertyDescriptor::new(
+            "max_height",
+            "Max Height",
+            PropertyKind::OptionalFloat { min: 50.0, max: 1000.0, initial: 200.0 },
+            Some(200.0),
+        ),
+        max_width: PropertyDescriptor::new(
+            "max_width",
+            "Max Width",
+            PropertyKind::OptionalFloat { min: 50.0, max: 1000.0, initial: 300.0 },
+            PropertyValue::OptionalFloat(None),
+        ),
+    }
+
     fn name(&self) -> &str {
         "Scroll Area"
     }
@@ -3333,7 +4459,7 @@ impl WidgetNode for ScrollAreaWidget {
 
         // Make the scroll area selectable only via border (not content area)
         let widget_rect = response;
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 8.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -3346,44 +4472,24 @@ impl WidgetNode for ScrollAreaWidget {
         }
     }
 
-    fn inspect(&mut self, ui: &mut Ui, _known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Scroll Area Settings");
-        ui.label(format!("ID: {}", self.id));
-
-        ui.separator();
-
-        ui.checkbox(&mut self.scroll_horizontal, "Horizontal Scroll");
-        ui.checkbox(&mut self.scroll_vertical, "Vertical Scroll");
-
-        ui.separator();
-
-        ui.horizontal(|ui| {
-            ui.label("Max Height:");
-            let mut has_max_height = self.max_height.is_some();
-            if ui.checkbox(&mut has_max_height, "").changed() {
-                self.max_height = if has_max_height { Some(200.0) } else { None };
-            }
-            if let Some(ref mut h) = self.max_height {
-                ui.add(egui::DragValue::new(h).speed(1.0).range(50.0..=1000.0));
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Max Width:");
-            let mut has_max_width = self.max_width.is_some();
-            if ui.checkbox(&mut has_max_width, "").changed() {
-                self.max_width = if has_max_width { Some(300.0) } else { None };
-            }
-            if let Some(ref mut w) = self.max_width {
-                ui.add(egui::DragValue::new(w).speed(1.0).range(50.0..=1000.0));
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        let mut scroll = egui::ScrollArea::new([self.scroll_horizontal, self.scroll_vertical])
+            .id_salt(egui::Id::new("preview_scroll").with(self.id));
+        if let Some(h) = self.max_height {
+            scroll = scroll.max_height(h);
+        }
+        if let Some(w) = self.max_width {
+            scroll = scroll.max_width(w);
+        }
+        scroll.show(ui, |ui| {
+            for child in &self.children {
+                preview_widget(ui, child.as_ref(), preview);
             }
         });
-
-        ui.label(format!("Children count: {}", self.children.len()));
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
-        let child_streams: Vec<_> = self.children.iter().map(|c| c.codegen()).collect();
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
+        let child_streams: Vec<_> = self.children.iter().map(|c| ctx.widget_code(c.as_ref())).collect();
 
         let h_scroll = self.scroll_horizontal;
         let v_scroll = self.scroll_vertical;
@@ -3420,13 +4526,15 @@ impl WidgetNode for ScrollAreaWidget {
 }
 
 // --- ColorPicker ---
-/// A color selection widget
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ColorPickerWidget {
-    pub id: Uuid,
-    pub color: [f32; 4],
-    #[serde(default)]
-    pub bindings: std::collections::HashMap<String, String>,
+widget! {
+    /// A color selection widget
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ColorPickerWidget {
+        pub id: Uuid,
+        pub color: [f32; 4],
+        #[serde(default)]
+        pub bindings: std::collections::HashMap<String, String>,
+    }
 }
 
 impl Default for ColorPickerWidget {
@@ -3435,6 +4543,7 @@ impl Default for ColorPickerWidget {
             id: Uuid::new_v4(),
             color: [1.0, 1.0, 1.0, 1.0], // White by default
             bindings: std::collections::HashMap::new(),
+            common: CommonProperties::default(),
         }
     }
 }
@@ -3449,6 +4558,18 @@ impl WidgetNode for ColorPickerWidget {
         self.id
     }
 
+    widget_properties! { self;
+        color: PropertyDescriptor::new("color", "Color", PropertyKind::Color, [1.0, 1.0, 1.0, 1.0]).bindable(),
+    }
+
+    fn bindings(&self) -> Option<&std::collections::HashMap<String, String>> {
+        Some(&self.bindings)
+    }
+
+    fn bindings_mut(&mut self) -> Option<&mut std::collections::HashMap<String, String>> {
+        Some(&mut self.bindings)
+    }
+
     fn name(&self) -> &str {
         "Color Picker"
     }
@@ -3458,7 +4579,7 @@ impl WidgetNode for ColorPickerWidget {
         let widget_rect = response.rect;
 
         // Create selection overlay for better hit detection
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
 
         if selection.contains(&self.id) {
@@ -3475,62 +4596,19 @@ impl WidgetNode for ColorPickerWidget {
         overlay.on_hover_text(format!("Color: {}\nID: {}", hex, self.id));
     }
 
-    fn inspect(&mut self, ui: &mut Ui, known_variables: &[String], _known_assets: &[(String, String)]) {
-        ui.heading("Color Picker Properties");
-        ui.label(format!("ID: {}", self.id));
-
-        ui.separator();
-
-        ui.horizontal(|ui| {
-            ui.label("Color:");
-            let is_bound = self.bindings.contains_key("color");
-            let mut bound_mode = is_bound;
-
-            if ui.checkbox(&mut bound_mode, "Bind").changed() {
-                if bound_mode {
-                    if !known_variables.is_empty() {
-                        self.bindings
-                            .insert("color".to_string(), known_variables[0].clone());
-                    } else {
-                        self.bindings.insert("color".to_string(), "".to_string());
-                    }
-                } else {
-                    self.bindings.remove("color");
-                }
-            }
-
-            if bound_mode {
-                let current_var = self.bindings.get("color").cloned().unwrap_or_default();
-                let mut selected_var = current_var.clone();
-
-                egui::ComboBox::from_id_salt("color_picker_bind")
-                    .selected_text(&selected_var)
-                    .show_ui(ui, |ui| {
-                        for var in known_variables {
-                            ui.selectable_value(&mut selected_var, var.clone(), var);
-                        }
-                    });
-
-                if selected_var != current_var {
-                    self.bindings.insert("color".to_string(), selected_var);
-                }
-            } else {
-                ui.color_edit_button_rgba_unmultiplied(&mut self.color);
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        if let Some(var) = self.bindings.get("color") {
+            let mut color = preview.color(var).unwrap_or(self.color);
+            if ui.color_edit_button_rgba_unmultiplied(&mut color).changed() {
+                preview.set_color(var, color);
             }
-        });
-
-        // Display hex color for reference
-        let hex = format!(
-            "#{:02x}{:02x}{:02x}{:02x}",
-            (self.color[0] * 255.0) as u8,
-            (self.color[1] * 255.0) as u8,
-            (self.color[2] * 255.0) as u8,
-            (self.color[3] * 255.0) as u8,
-        );
-        ui.label(format!("Hex: {}", hex));
+        } else {
+            let mut color = self.color;
+            ui.color_edit_button_rgba_unmultiplied(&mut color);
+        }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let r = (self.color[0] * 255.0) as u8;
         let g = (self.color[1] * 255.0) as u8;
         let b = (self.color[2] * 255.0) as u8;
@@ -3548,20 +4626,36 @@ impl WidgetNode for ColorPickerWidget {
             }
         }
     }
+
+    fn validate(&self, variables: &std::collections::HashMap<String, crate::model::Variable>) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(var_name) = self.bindings.get("color") {
+            if let Some(var) = variables.get(var_name) {
+                if var.v_type != crate::model::VariableType::Color {
+                    errors.push(format!("ColorPicker '{}' bound to non-color variable '{}' ({})", self.id, var_name, var.v_type));
+                }
+            } else {
+                errors.push(format!("ColorPicker '{}' bound to missing variable '{}'", self.id, var_name));
+            }
+        }
+        errors
+    }
 }
 
 // --- FreeformLayout ---
-/// A container with absolute positioning for children
-/// Each child can have its own x, y position within the container
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FreeformLayout {
-    pub id: Uuid,
-    pub children: Vec<FreeformChild>,
-    pub width: f32,
-    pub height: f32,
-    pub show_grid: bool,
-    pub snap_to_grid: bool,
-    pub grid_size: f32,
+widget! {
+    /// A container with absolute positioning for children
+    /// Each child can have its own x, y position within the container
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct FreeformLayout {
+        pub id: Uuid,
+        pub children: Vec<FreeformChild>,
+        pub width: f32,
+        pub height: f32,
+        pub show_grid: bool,
+        pub snap_to_grid: bool,
+        pub grid_size: f32,
+    }
 }
 
 /// A child widget with position data
@@ -3586,6 +4680,46 @@ impl Clone for FreeformChild {
     }
 }
 
+impl ChildList for Vec<FreeformChild> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn widgets(&self) -> Vec<&dyn WidgetNode> {
+        self.iter().map(|child| child.widget.as_ref()).collect()
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn WidgetNode> {
+        self.iter_mut().map(|child| child.widget.as_mut() as &mut dyn WidgetNode).collect()
+    }
+
+    fn layout(&self, index: usize) -> ChildLayout {
+        let child = &self[index];
+        ChildLayout::Placed { x: child.x, y: child.y, width: child.width, height: child.height }
+    }
+
+    fn set_layout(&mut self, index: usize, layout: ChildLayout) {
+        if let ChildLayout::Placed { x, y, width, height } = layout {
+            let child = &mut self[index];
+            (child.x, child.y, child.width, child.height) = (x, y, width, height);
+        }
+    }
+
+    fn insert(&mut self, index: usize, widget: Box<dyn WidgetNode>, layout: ChildLayout) {
+        // Widgets moved in from a flow container start at the top left
+        let (x, y, width, height) = match layout {
+            ChildLayout::Placed { x, y, width, height } => (x, y, width, height),
+            ChildLayout::Flow => (0.0, 0.0, None, None),
+        };
+        Vec::insert(self, index.min(Vec::len(self)), FreeformChild { widget, x, y, width, height });
+    }
+
+    fn remove(&mut self, index: usize) -> (Box<dyn WidgetNode>, ChildLayout) {
+        let layout = ChildList::layout(self, index);
+        (Vec::remove(self, index).widget, layout)
+    }
+}
+
 impl Default for FreeformLayout {
     fn default() -> Self {
         Self {
@@ -3596,6 +4730,7 @@ impl Default for FreeformLayout {
             show_grid: true,
             snap_to_grid: true,
             grid_size: 10.0,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -3617,6 +4752,7 @@ impl WidgetNode for FreeformLayout {
             show_grid: self.show_grid,
             snap_to_grid: self.snap_to_grid,
             grid_size: self.grid_size,
+            common: self.common.clone(),
         })
     }
 
@@ -3624,6 +4760,29 @@ impl WidgetNode for FreeformLayout {
         self.id
     }
 
+    widget_properties! { self;
+        width: PropertyDescriptor::new(
+            "width",
+            "Width",
+            PropertyKind::Float { min: 100.0, max: 2000.0, speed: 1.0 },
+            400.0,
+        ),
+        height: PropertyDescriptor::new(
+            "height",
+            "Height",
+            PropertyKind::Float { min: 100.0, max: 2000.0, speed: 1.0 },
+            300.0,
+        ),
+        show_grid: PropertyDescriptor::new("show_grid", "Show Grid", PropertyKind::Bool, true),
+        snap_to_grid: PropertyDescriptor::new("snap_to_grid", "Snap to Grid", PropertyKind::Bool, true),
+        grid_size: PropertyDescriptor::new(
+            "grid_size",
+            "Grid Size",
+            PropertyKind::Float { min: 5.0, max: 50.0, speed: 1.0 },
+            10.0,
+        ),
+    }
+
     fn name(&self) -> &str {
         "Freeform Layout"
     }
@@ -3756,7 +4915,7 @@ impl WidgetNode for FreeformLayout {
 
         // Handle selection of the container itself only via border (not content area)
         let widget_rect = response.rect;
-        let border_clicked = create_container_selection_overlay(ui, widget_rect, 10.0, self.id);
+        let border_clicked = create_container_selection_overlay(ui, widget_rect, 10.0, self.id, self.name());
         handle_selection(ui, self.id, border_clicked, selection);
 
         // Draw drop zone indicator when any payload is hovering
@@ -3839,7 +4998,7 @@ impl WidgetNode for FreeformLayout {
         // Show child positions
         if !self.children.is_empty() {
             ui.collapsing("Child Positions", |ui| {
-                for (_idx, child) in self.children.iter_mut().enumerate() {
+                for child in self.children.iter_mut() {
                     ui.horizontal(|ui| {
                         ui.label(format!("{}:", child.widget.name()));
                         ui.label("x:");
@@ -3852,10 +5011,23 @@ impl WidgetNode for FreeformLayout {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, preview: &mut PreviewState) {
+        // The generated app positions children on the screen; here they are placed
+        // relative to the container so the preview stays on the canvas
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(self.width, self.height), egui::Sense::hover());
+        for child in &self.children {
+            egui::Area::new(egui::Id::new("preview_freeform_child").with(child.widget.id()))
+                .fixed_pos(rect.min + egui::vec2(child.x, child.y))
+                .show(ui.ctx(), |ui| {
+                    preview_widget(ui, child.widget.as_ref(), preview);
+                });
+        }
+    }
+
+    fn codegen(&self, ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         // For code generation, we use egui::Area for absolute positioning
         let child_streams: Vec<_> = self.children.iter().map(|child| {
-            let child_code = child.widget.codegen();
+            let child_code = ctx.widget_code(child.widget.as_ref());
             let x = child.x;
             let y = child.y;
             let child_id = format!("freeform_child_{}", child.widget.id());
@@ -3863,7 +5035,7 @@ impl WidgetNode for FreeformLayout {
             quote! {
                 egui::Area::new(egui::Id::new(#child_id))
                     .fixed_pos(egui::pos2(#x, #y))
-                    .show(ctx, |ui| {
+                    .show(ui.ctx(), |ui| {
                         #child_code
                     });
             }
@@ -3886,6 +5058,14 @@ impl WidgetNode for FreeformLayout {
     fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn WidgetNode>>> {
         None
     }
+
+    fn slots(&self) -> Vec<ChildSlot<'_>> {
+        vec![ChildSlot { name: String::new(), children: &self.children }]
+    }
+
+    fn slots_mut(&mut self) -> Vec<ChildSlotMut<'_>> {
+        vec![ChildSlotMut { name: String::new(), children: &mut self.children }]
+    }
 }
 
 // --- Table ---
@@ -3895,13 +5075,15 @@ pub struct TableColumn {
     pub width: Option<f32>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct TableWidget {
-    pub id: Uuid,
-    pub columns: Vec<TableColumn>,
-    pub row_count: usize,
-    pub striped: bool,
-    pub resizable: bool,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct TableWidget {
+        pub id: Uuid,
+        pub columns: Vec<TableColumn>,
+        pub row_count: usize,
+        pub striped: bool,
+        pub resizable: bool,
+    }
 }
 
 impl Default for TableWidget {
@@ -3916,6 +5098,7 @@ impl Default for TableWidget {
             row_count: 5,
             striped: true,
             resizable: true,
+            common: CommonProperties::default(),
         }
     }
 }
@@ -3930,6 +5113,17 @@ impl WidgetNode for TableWidget {
         self.id
     }
 
+    widget_properties! { self;
+        striped: PropertyDescriptor::new("striped", "Striped Rows", PropertyKind::Bool, true),
+        resizable: PropertyDescriptor::new("resizable", "Resizable Columns", PropertyKind::Bool, true),
+        row_count: PropertyDescriptor::new(
+            "row_count",
+            "Rows",
+            PropertyKind::Integer { min: 0, max: 100 },
+            PropertyValue::Integer(5),
+        ),
+    }
+
     fn name(&self) -> &str {
         "Table"
     }
@@ -3975,7 +5169,7 @@ impl WidgetNode for TableWidget {
         });
 
         let widget_rect = response.response.rect;
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
         
         if selection.contains(&self.id) {
@@ -4032,7 +5226,43 @@ impl WidgetNode for TableWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut PreviewState) {
+        let mut table = egui_extras::TableBuilder::new(ui)
+            .id_salt(egui::Id::new("preview_table").with(self.id))
+            .striped(self.striped)
+            .resizable(self.resizable)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .min_scrolled_height(0.0);
+        for col in &self.columns {
+            table = match col.width {
+                Some(w) => table.column(egui_extras::Column::exact(w)),
+                None => table.column(egui_extras::Column::initial(100.0).at_least(40.0)),
+            };
+        }
+
+        // The generated table always shows 5 placeholder rows
+        table
+            .header(20.0, |mut header| {
+                for col in &self.columns {
+                    header.col(|ui| {
+                        ui.strong(&col.header);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for i in 0..5 {
+                    body.row(18.0, |mut row| {
+                        for j in 0..self.columns.len() {
+                            row.col(|ui| {
+                                ui.label(format!("Cell {},{}", i, j));
+                            });
+                        }
+                    });
+                }
+            });
+    }
+
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let striped = self.striped;
         let resizable = self.resizable;
         let col_defs: Vec<_> = self.columns.iter().map(|col| {
@@ -4075,6 +5305,10 @@ impl WidgetNode for TableWidget {
                 });
         }
     }
+
+    fn dependencies(&self) -> Vec<CrateDependency> {
+        vec![CrateDependency::new("egui_extras", crate::compiler::EGUI_EXTRAS_VERSION)]
+    }
 }
 
 // --- Plot ---
@@ -4092,15 +5326,17 @@ pub struct PlotSeries {
     pub color: Option<[f32; 3]>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct PlotWidget {
-    pub id: Uuid,
-    pub title: String,
-    pub show_x_axis: bool,
-    pub show_y_axis: bool,
-    pub show_legend: bool,
-    pub series: Vec<PlotSeries>,
-    pub height: f32,
+widget! {
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct PlotWidget {
+        pub id: Uuid,
+        pub title: String,
+        pub show_x_axis: bool,
+        pub show_y_axis: bool,
+        pub show_legend: bool,
+        pub series: Vec<PlotSeries>,
+        pub height: f32,
+    }
 }
 
 impl Default for PlotWidget {
@@ -4115,6 +5351,45 @@ impl Default for PlotWidget {
                 PlotSeries { name: "Series A".to_string(), plot_type: PlotType::Line, color: Some([0.0, 0.5, 1.0]) },
             ],
             height: 200.0,
+            common: CommonProperties::default(),
+        }
+    }
+}
+
+impl PlotWidget {
+    /// Draw the sample data of every series; series without a color use `default_color`
+    fn show_series(&self, plot_ui: &mut egui_plot::PlotUi, default_color: egui::Color32) {
+        use egui_plot::{Line, PlotPoints};
+
+        for (idx, s) in self.series.iter().enumerate() {
+            let color = s.color.map(|c| egui::Color32::from_rgb(
+                (c[0] * 255.0) as u8,
+                (c[1] * 255.0) as u8,
+                (c[2] * 255.0) as u8
+            )).unwrap_or(default_color);
+
+            match s.plot_type {
+                PlotType::Line => {
+                    let sin: PlotPoints = (0..1000).map(|i| {
+                        let x = i as f64 * 0.01;
+                        [x, (x + (idx as f64)).sin()]
+                    }).collect();
+                    plot_ui.line(Line::new(&s.name, sin).color(color));
+                }
+                PlotType::Bar => {
+                    let bars: Vec<egui_plot::Bar> = (0..10).map(|i| {
+                        egui_plot::Bar::new(i as f64, (i as f64 + (idx as f64)).cos().abs() * 5.0)
+                    }).collect();
+                    plot_ui.bar_chart(egui_plot::BarChart::new(&s.name, bars).color(color));
+                }
+                PlotType::Points => {
+                    let points: PlotPoints = (0..50).map(|i| {
+                        let x = i as f64 * 0.2;
+                        [x, (x * (idx as f64 + 1.0)).cos() * 2.0]
+                    }).collect();
+                    plot_ui.points(egui_plot::Points::new(&s.name, points).color(color));
+                }
+            }
         }
     }
 }
@@ -4129,12 +5404,30 @@ impl WidgetNode for PlotWidget {
         self.id
     }
 
+    widget_properties! { self;
+        title: PropertyDescriptor::new(
+            "title",
+            "Title",
+            PropertyKind::Text { placeholders: false },
+            "Performance",
+        ),
+        height: PropertyDescriptor::new(
+            "height",
+            "Height",
+            PropertyKind::Float { min: 50.0, max: 1000.0, speed: 1.0 },
+            200.0,
+        ),
+        show_x_axis: PropertyDescriptor::new("show_x_axis", "Show X Axis", PropertyKind::Bool, true),
+        show_y_axis: PropertyDescriptor::new("show_y_axis", "Show Y Axis", PropertyKind::Bool, true),
+        show_legend: PropertyDescriptor::new("show_legend", "Show Legend", PropertyKind::Bool, true),
+    }
+
     fn name(&self) -> &str {
         "Plot"
     }
 
     fn render_editor(&mut self, ui: &mut Ui, selection: &mut HashSet<Uuid>) {
-        use egui_plot::{Line, Plot, PlotPoints};
+        use egui_plot::Plot;
 
         let mut plot = Plot::new(format!("plot_{}", self.id))
             .height(self.height)
@@ -4145,41 +5438,10 @@ impl WidgetNode for PlotWidget {
             plot = plot.legend(egui_plot::Legend::default());
         }
 
-        let response = plot.show(ui, |plot_ui| {
-            for (idx, s) in self.series.iter().enumerate() {
-                let color = s.color.map(|c| egui::Color32::from_rgb(
-                    (c[0] * 255.0) as u8,
-                    (c[1] * 255.0) as u8,
-                    (c[2] * 255.0) as u8
-                )).unwrap_or(egui::Color32::from_gray(150));
-
-                match s.plot_type {
-                    PlotType::Line => {
-                        let sin: PlotPoints = (0..1000).map(|i| {
-                            let x = i as f64 * 0.01;
-                            [x, (x + (idx as f64)).sin()]
-                        }).collect();
-                        plot_ui.line(Line::new(&s.name, sin).color(color));
-                    }
-                    PlotType::Bar => {
-                        let bars: Vec<egui_plot::Bar> = (0..10).map(|i| {
-                            egui_plot::Bar::new(i as f64, (i as f64 + (idx as f64)).cos().abs() * 5.0)
-                        }).collect();
-                        plot_ui.bar_chart(egui_plot::BarChart::new(&s.name, bars).color(color));
-                    }
-                    PlotType::Points => {
-                        let points: PlotPoints = (0..50).map(|i| {
-                            let x = i as f64 * 0.2;
-                            [x, (x * (idx as f64 + 1.0)).cos() * 2.0]
-                        }).collect();
-                        plot_ui.points(egui_plot::Points::new(&s.name, points).color(color));
-                    }
-                }
-            }
-        });
+        let response = plot.show(ui, |plot_ui| self.show_series(plot_ui, egui::Color32::from_gray(150)));
 
         let widget_rect = response.response.rect;
-        let overlay = create_selection_overlay(ui, widget_rect, self.id);
+        let overlay = create_selection_overlay(ui, widget_rect, self.id, self.name());
         handle_selection(ui, self.id, overlay.clicked(), selection);
         
         if selection.contains(&self.id) {
@@ -4259,7 +5521,18 @@ impl WidgetNode for PlotWidget {
         }
     }
 
-    fn codegen(&self) -> proc_macro2::TokenStream {
+    fn render_preview(&self, ui: &mut Ui, _preview: &mut PreviewState) {
+        let mut plot = egui_plot::Plot::new(("preview_plot", self.id))
+            .height(self.height)
+            .show_x(self.show_x_axis)
+            .show_y(self.show_y_axis);
+        if self.show_legend {
+            plot = plot.legend(egui_plot::Legend::default());
+        }
+        plot.show(ui, |plot_ui| self.show_series(plot_ui, egui::Color32::WHITE));
+    }
+
+    fn codegen(&self, _ctx: &mut CodegenContext) -> proc_macro2::TokenStream {
         let title = &self.title;
         let height = self.height;
         let show_x = self.show_x_axis;
@@ -4270,9 +5543,9 @@ impl WidgetNode for PlotWidget {
         for (idx, s) in self.series.iter().enumerate() {
             let name = &s.name;
             let color = s.color.unwrap_or([1.0, 1.0, 1.0]);
-            let r = color[0];
-            let g = color[1];
-            let b = color[2];
+            let r = (color[0] * 255.0) as u8;
+            let g = (color[1] * 255.0) as u8;
+            let b = (color[2] * 255.0) as u8;
 
             match s.plot_type {
                 PlotType::Line => {
@@ -4281,7 +5554,7 @@ impl WidgetNode for PlotWidget {
                             let x = i as f64 * 0.01;
                             [x, (x + (#idx as f64)).sin()]
                         }).collect();
-                        plot_ui.line(egui_plot::Line::new(#name, sin).color(egui::Color32::from_rgb_f32(#r, #g, #b)));
+                        plot_ui.line(egui_plot::Line::new(#name, sin).color(egui::Color32::from_rgb(#r, #g, #b)));
                     });
                 }
                 PlotType::Bar => {
@@ -4289,7 +5562,7 @@ impl WidgetNode for PlotWidget {
                         let bars: Vec<egui_plot::Bar> = (0..10).map(|i| {
                             egui_plot::Bar::new(i as f64, (i as f64 + (#idx as f64)).cos().abs() * 5.0)
                         }).collect();
-                        plot_ui.bar_chart(egui_plot::BarChart::new(#name, bars).color(egui::Color32::from_rgb_f32(#r, #g, #b)));
+                        plot_ui.bar_chart(egui_plot::BarChart::new(#name, bars).color(egui::Color32::from_rgb(#r, #g, #b)));
                     });
                 }
                 PlotType::Points => {
@@ -4298,7 +5571,7 @@ impl WidgetNode for PlotWidget {
                             let x = i as f64 * 0.2;
                             [x, (x * (#idx as f64 + 1.0)).cos() * 2.0]
                         }).collect();
-                        plot_ui.points(egui_plot::Points::new(#name, points).color(egui::Color32::from_rgb_f32(#r, #g, #b)));
+                        plot_ui.points(egui_plot::Points::new(#name, points).color(egui::Color32::from_rgb(#r, #g, #b)));
                     });
                 }
             }
@@ -4321,4 +5594,8 @@ impl WidgetNode for PlotWidget {
             });
         }
     }
+
+    fn dependencies(&self) -> Vec<CrateDependency> {
+        vec![CrateDependency::new("egui_plot", crate::compiler::EGUI_PLOT_VERSION)]
+    }
 }
@@ -1,5 +1,5 @@
-use aether_rad::model::{ProjectState, Variable, VariableType, WidgetEvent, Action};
-use aether_rad::widgets::{ButtonWidget, LabelWidget, VerticalLayout, HorizontalLayout, GridLayout, CheckboxWidget, SliderWidget};
+use aether_rad::model::{CommonProperties, CompareOp, Condition, CustomType, Flag, StructField, ExportMode, ProjectState, Screen, Variable, VariableType, WidgetEvent, WidgetNode, Action};
+use aether_rad::widgets::{ButtonWidget, LabelWidget, VerticalLayout, HorizontalLayout, GridLayout, CheckboxWidget, ColorPickerWidget, ComboBoxWidget, ProgressBarWidget, SliderWidget, TabContainerWidget, TextEditWidget, WindowWidget};
 use aether_rad::compiler::Compiler;
 
 #[test]
@@ -12,16 +12,18 @@ fn test_save_load_round_trip() {
         text: "Test Button".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
     button.events.insert(
         WidgetEvent::Clicked,
-        Action::Custom("self.counter += 1;".to_string()),
+        vec![Action::Custom("self.counter += 1;".to_string())],
     );
 
     let label = LabelWidget {
         id: uuid::Uuid::new_v4(),
         text: "Test Label".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     root.children.push(Box::new(button));
@@ -53,10 +55,10 @@ fn test_save_load_round_trip() {
     );
 
     // Verify widget structure
-    assert_eq!(project.root_node.name(), loaded.root_node.name());
+    assert_eq!(project.root().name(), loaded.root().name());
     assert_eq!(
-        project.root_node.children().unwrap().len(),
-        loaded.root_node.children().unwrap().len()
+        project.root().children().unwrap().len(),
+        loaded.root().children().unwrap().len()
     );
 }
 
@@ -70,10 +72,11 @@ fn test_code_generation_valid_rust() {
         text: "Click Me".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
     button.events.insert(
         WidgetEvent::Clicked,
-        Action::Custom("println!(\"Button clicked!\");".to_string()),
+        vec![Action::Custom("println!(\"Button clicked!\");".to_string())],
     );
 
     root.children.push(Box::new(button));
@@ -82,9 +85,9 @@ fn test_code_generation_valid_rust() {
     project.project_name = "test_app".to_string();
 
     // Generate code
-    let app_rs = Compiler::generate_app_rs(&project);
+    let app_rs = Compiler::generate_app_generated_rs(&project);
     let main_rs = Compiler::generate_main_rs();
-    let cargo_toml = Compiler::generate_cargo_toml("test_app");
+    let cargo_toml = Compiler::generate_cargo_toml("test_app", &Compiler::collect_dependencies(&project));
 
     // Verify code contains expected elements
     assert!(app_rs.contains("struct MyApp"));
@@ -110,18 +113,20 @@ fn test_widget_tree_manipulation() {
     let button_id = uuid::Uuid::new_v4();
     let label_id = uuid::Uuid::new_v4();
 
-    if let Some(children) = project.root_node.children_mut() {
+    if let Some(children) = project.root_mut().children_mut() {
         children.push(Box::new(ButtonWidget {
             id: button_id,
             text: "Button 1".to_string(),
             events: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
+            ..Default::default()
         }));
 
         children.push(Box::new(LabelWidget {
             id: label_id,
             text: "Label 1".to_string(),
             bindings: std::collections::HashMap::new(),
+            ..Default::default()
         }));
     }
 
@@ -153,23 +158,26 @@ fn test_widget_reordering() {
     let id2 = uuid::Uuid::new_v4();
     let id3 = uuid::Uuid::new_v4();
 
-    if let Some(children) = project.root_node.children_mut() {
+    if let Some(children) = project.root_mut().children_mut() {
         children.push(Box::new(LabelWidget {
             id: id1,
             text: "First".to_string(),
             bindings: std::collections::HashMap::new(),
+            ..Default::default()
         }));
 
         children.push(Box::new(LabelWidget {
             id: id2,
             text: "Second".to_string(),
             bindings: std::collections::HashMap::new(),
+            ..Default::default()
         }));
 
         children.push(Box::new(LabelWidget {
             id: id3,
             text: "Third".to_string(),
             bindings: std::collections::HashMap::new(),
+            ..Default::default()
         }));
     }
 
@@ -178,7 +186,7 @@ fn test_widget_reordering() {
     assert!(move_result);
 
     // Verify order: First, Third, Second
-    if let Some(children) = project.root_node.children() {
+    if let Some(children) = project.root().children() {
         assert_eq!(children[0].id(), id1);
         assert_eq!(children[1].id(), id3);
         assert_eq!(children[2].id(), id2);
@@ -189,7 +197,7 @@ fn test_widget_reordering() {
     assert!(move_result);
 
     // Verify order: Third, First, Second
-    if let Some(children) = project.root_node.children() {
+    if let Some(children) = project.root().children() {
         assert_eq!(children[0].id(), id3);
         assert_eq!(children[1].id(), id1);
         assert_eq!(children[2].id(), id2);
@@ -201,30 +209,42 @@ fn test_root_layout_type_switching() {
     let mut project = ProjectState::new(Box::new(VerticalLayout::default()));
 
     // Add some children
-    if let Some(children) = project.root_node.children_mut() {
+    if let Some(children) = project.root_mut().children_mut() {
         children.push(Box::new(ButtonWidget::default()));
         children.push(Box::new(LabelWidget::default()));
     }
 
-    let root_id = project.root_node.id();
+    let root_id = project.root().id();
 
     // Switch to horizontal layout
     project.set_root_layout_type("Horizontal Layout");
     assert_eq!(project.root_layout_type(), "Horizontal Layout");
-    assert_eq!(project.root_node.id(), root_id); // ID should be preserved
-    assert_eq!(project.root_node.children().unwrap().len(), 2); // Children preserved
+    assert_eq!(project.root().id(), root_id); // ID should be preserved
+    assert_eq!(project.root().children().unwrap().len(), 2); // Children preserved
 
     // Switch to grid layout
     project.set_root_layout_type("Grid Layout");
     assert_eq!(project.root_layout_type(), "Grid Layout");
-    assert_eq!(project.root_node.id(), root_id);
-    assert_eq!(project.root_node.children().unwrap().len(), 2);
+    assert_eq!(project.root().id(), root_id);
+    assert_eq!(project.root().children().unwrap().len(), 2);
 
     // Switch back to vertical
     project.set_root_layout_type("Vertical Layout");
     assert_eq!(project.root_layout_type(), "Vertical Layout");
-    assert_eq!(project.root_node.id(), root_id);
-    assert_eq!(project.root_node.children().unwrap().len(), 2);
+    assert_eq!(project.root().id(), root_id);
+    assert_eq!(project.root().children().unwrap().len(), 2);
+
+    // Not a layout: nothing changes
+    project.set_root_layout_type("Window");
+    assert_eq!(project.root_layout_type(), "Vertical Layout");
+}
+
+#[test]
+fn test_tab_container_has_no_single_child_list() {
+    // Its children are in tabs, reached through slots
+    let tabs = TabContainerWidget::default();
+    assert!(tabs.children().is_none());
+    assert_eq!(tabs.slots().len(), 2);
 }
 
 #[test]
@@ -267,6 +287,7 @@ fn test_variable_management() {
 }
 
 #[test]
+#[allow(clippy::get_first)]
 fn test_deeply_nested_widget_structure() {
     // Create a deeply nested structure:
     // VerticalLayout -> HorizontalLayout -> GridLayout -> Button/Label/Checkbox
@@ -277,12 +298,14 @@ fn test_deeply_nested_widget_structure() {
         text: "Nested Button".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     let label = LabelWidget {
         id: uuid::Uuid::new_v4(),
         text: "Nested Label".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     let checkbox = CheckboxWidget {
@@ -291,6 +314,7 @@ fn test_deeply_nested_widget_structure() {
         checked: false,
         bindings: std::collections::HashMap::new(),
         events: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     let slider = SliderWidget {
@@ -300,6 +324,7 @@ fn test_deeply_nested_widget_structure() {
         value: 50.0,
         bindings: std::collections::HashMap::new(),
         events: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     // Create Grid containing the widgets
@@ -308,6 +333,7 @@ fn test_deeply_nested_widget_structure() {
         children: Vec::new(),
         columns: 2,
         spacing: 8.0,
+        ..Default::default()
     };
     grid.children.push(Box::new(button));
     grid.children.push(Box::new(label));
@@ -319,6 +345,7 @@ fn test_deeply_nested_widget_structure() {
         id: uuid::Uuid::new_v4(),
         children: Vec::new(),
         spacing: 10.0,
+        ..Default::default()
     };
     horizontal.children.push(Box::new(grid));
 
@@ -327,6 +354,7 @@ fn test_deeply_nested_widget_structure() {
         id: uuid::Uuid::new_v4(),
         text: "Sibling Label".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     }));
 
     // Create Vertical (root) containing the Horizontal
@@ -339,17 +367,18 @@ fn test_deeply_nested_widget_structure() {
         text: "Top Level Button".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     }));
 
     // Create project with nested structure
     let project = ProjectState::new(Box::new(vertical));
 
     // Verify structure before serialization
-    assert_eq!(project.root_node.name(), "Vertical Layout");
-    assert_eq!(project.root_node.children().unwrap().len(), 2);
+    assert_eq!(project.root().name(), "Vertical Layout");
+    assert_eq!(project.root().children().unwrap().len(), 2);
 
     // Get the horizontal layout
-    let horizontal_ref = project.root_node.children().unwrap().get(0).unwrap();
+    let horizontal_ref = project.root().children().unwrap().get(0).unwrap();
     assert_eq!(horizontal_ref.name(), "Horizontal Layout");
     assert_eq!(horizontal_ref.children().unwrap().len(), 2);
 
@@ -363,10 +392,10 @@ fn test_deeply_nested_widget_structure() {
     let loaded: ProjectState = serde_json::from_str(&json).expect("Failed to deserialize nested structure");
 
     // Verify structure after deserialization
-    assert_eq!(loaded.root_node.name(), "Vertical Layout");
-    assert_eq!(loaded.root_node.children().unwrap().len(), 2);
+    assert_eq!(loaded.root().name(), "Vertical Layout");
+    assert_eq!(loaded.root().children().unwrap().len(), 2);
 
-    let loaded_horizontal = loaded.root_node.children().unwrap().get(0).unwrap();
+    let loaded_horizontal = loaded.root().children().unwrap().get(0).unwrap();
     assert_eq!(loaded_horizontal.name(), "Horizontal Layout");
     assert_eq!(loaded_horizontal.children().unwrap().len(), 2);
 
@@ -390,6 +419,7 @@ fn test_nested_codegen_produces_valid_code() {
         children: Vec::new(),
         columns: 2,
         spacing: 8.0,
+        ..Default::default()
     };
     grid.children.push(Box::new(ButtonWidget::default()));
     grid.children.push(Box::new(LabelWidget::default()));
@@ -398,6 +428,7 @@ fn test_nested_codegen_produces_valid_code() {
         id: uuid::Uuid::new_v4(),
         children: Vec::new(),
         spacing: 10.0,
+        ..Default::default()
     };
     horizontal.children.push(Box::new(grid));
 
@@ -408,7 +439,7 @@ fn test_nested_codegen_produces_valid_code() {
     project.project_name = "nested_test".to_string();
 
     // Generate code
-    let app_rs = Compiler::generate_app_rs(&project);
+    let app_rs = Compiler::generate_app_generated_rs(&project);
 
     // Verify nested structure is present in generated code
     // GridLayout generates nested vertical/horizontal layouts, not egui::Grid
@@ -419,6 +450,7 @@ fn test_nested_codegen_produces_valid_code() {
 }
 
 #[test]
+#[allow(clippy::get_first)]
 fn test_three_level_nesting_serialization() {
     // Create a three-level nesting: Vertical -> Horizontal -> Vertical -> Button
     let button = ButtonWidget {
@@ -426,6 +458,7 @@ fn test_three_level_nesting_serialization() {
         text: "Deep Button".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     let mut inner_vertical = VerticalLayout::default();
@@ -435,6 +468,7 @@ fn test_three_level_nesting_serialization() {
         id: uuid::Uuid::new_v4(),
         children: Vec::new(),
         spacing: 10.0,
+        ..Default::default()
     };
     horizontal.children.push(Box::new(inner_vertical));
 
@@ -450,7 +484,7 @@ fn test_three_level_nesting_serialization() {
     let loaded: ProjectState = serde_json::from_str(&json).expect("Failed to deserialize");
 
     // Navigate to the deeply nested button
-    let loaded_horizontal = loaded.root_node.children().unwrap().get(0).unwrap();
+    let loaded_horizontal = loaded.root().children().unwrap().get(0).unwrap();
     let loaded_inner_vertical = loaded_horizontal.children().unwrap().get(0).unwrap();
     let loaded_button = loaded_inner_vertical.children().unwrap().get(0).unwrap();
 
@@ -470,14 +504,23 @@ fn test_codegen_compiles_successfully() {
         id: uuid::Uuid::new_v4(),
         text: "Hello World".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     }));
 
-    root.children.push(Box::new(ButtonWidget {
+    // Button whose click action calls into a hand-written handler
+    let mut click_me = ButtonWidget {
         id: uuid::Uuid::new_v4(),
         text: "Click Me".to_string(),
         events: std::collections::HashMap::new(),
         bindings: std::collections::HashMap::new(),
-    }));
+        ..Default::default()
+    };
+    click_me.events.insert(
+        WidgetEvent::Clicked,
+        vec![Action::Custom("self.reset_counter();".to_string())],
+    );
+    let click_hook = format!("on_button_{}_clicked", click_me.id.simple());
+    root.children.push(Box::new(click_me));
 
     root.children.push(Box::new(CheckboxWidget {
         id: uuid::Uuid::new_v4(),
@@ -485,6 +528,7 @@ fn test_codegen_compiles_successfully() {
         checked: false,
         bindings: std::collections::HashMap::new(),
         events: std::collections::HashMap::new(),
+        ..Default::default()
     }));
 
     root.children.push(Box::new(SliderWidget {
@@ -494,48 +538,282 @@ fn test_codegen_compiles_successfully() {
         value: 50.0,
         bindings: std::collections::HashMap::new(),
         events: std::collections::HashMap::new(),
+        ..Default::default()
     }));
 
+    // Widgets that pull in egui_extras, egui_plot and image
+    {
+        use aether_rad::widgets::{ImageWidget, PlotWidget, TableWidget};
+        root.children.push(Box::new(TableWidget::default()));
+        root.children.push(Box::new(PlotWidget::default()));
+        let mut image = ImageWidget::default();
+        image.events.insert(WidgetEvent::Hovered, vec![Action::IncrementVariable("counter".to_string())]);
+        root.children.push(Box::new(image));
+    }
+
     // Add a nested horizontal layout
     let mut horizontal = HorizontalLayout {
         id: uuid::Uuid::new_v4(),
         children: Vec::new(),
         spacing: 10.0,
+        ..Default::default()
     };
     horizontal.children.push(Box::new(LabelWidget {
         id: uuid::Uuid::new_v4(),
         text: "Left".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     }));
     horizontal.children.push(Box::new(LabelWidget {
         id: uuid::Uuid::new_v4(),
         text: "Right".to_string(),
         bindings: std::collections::HashMap::new(),
+        ..Default::default()
     }));
     root.children.push(Box::new(horizontal));
 
+    // Containers that keep their own state on the app struct, and buttons that change it
+    let mut window = WindowWidget::default();
+    window.children.push(Box::new(LabelWidget::default()));
+    // A handler call inside the window must not clash with its open flag borrow
+    let mut window_button = ButtonWidget::default();
+    window_button
+        .events
+        .insert(WidgetEvent::Clicked, vec![Action::Custom(String::new())]);
+    window.children.push(Box::new(window_button));
+    let mut close = ButtonWidget::default();
+    close.events.insert(WidgetEvent::Clicked, vec![Action::SetWindowOpen { window: window.id, open: false }]);
+    window.children.push(Box::new(close));
+    let mut tabs = TabContainerWidget::default();
+    tabs.tabs[1].children.push(Box::new(ButtonWidget::default()));
+    // Refers to the tabs before their code is generated
+    let mut first_tab = ButtonWidget::default();
+    first_tab.events.insert(WidgetEvent::Clicked, vec![Action::SelectTab { container: tabs.id, tab: 0 }]);
+    root.children.push(Box::new(first_tab));
+    root.children.push(Box::new(window));
+    root.children.push(Box::new(tabs));
+
+    // Widgets bound to a non-string variable
+    let mut counter_label = LabelWidget::default();
+    counter_label.bindings.insert("text".to_string(), "counter".to_string());
+    root.children.push(Box::new(counter_label));
+    let mut counter_edit = TextEditWidget::default();
+    counter_edit.bindings.insert("value".to_string(), "counter".to_string());
+    root.children.push(Box::new(counter_edit));
+
+    // Navigation to a second screen and back again
+    let mut open_settings = ButtonWidget::default();
+    open_settings
+        .events
+        .insert(WidgetEvent::Clicked, vec![Action::NavigateTo("Settings Page".to_string())]);
+    root.children.push(Box::new(open_settings));
+    let mut settings = VerticalLayout::default();
+    let mut back = ButtonWidget::default();
+    back.events.insert(WidgetEvent::Clicked, vec![Action::NavigateTo("Main".to_string())]);
+    settings.children.push(Box::new(back));
+
+    // Project-defined types, lists and colors
+    let mut mode_label = LabelWidget::default();
+    mode_label.bindings.insert("text".to_string(), "mode".to_string());
+    root.children.push(Box::new(mode_label));
+    let mut todos_label = LabelWidget::default();
+    todos_label.bindings.insert("text".to_string(), "todos".to_string());
+    root.children.push(Box::new(todos_label));
+    root.children.push(Box::new(LabelWidget {
+        text: "Count: {counter:>4} ({ratio:.2}) {todos} {mode:?} {accent} {{braces}}".to_string(),
+        ..Default::default()
+    }));
+    root.children.push(Box::new(WindowWidget {
+        title: "Mode: {mode}".to_string(),
+        ..Default::default()
+    }));
+    let mut accent_picker = ColorPickerWidget::default();
+    accent_picker.bindings.insert("color".to_string(), "accent".to_string());
+    root.children.push(Box::new(accent_picker));
+
+    // Widgets bound to concrete numeric types
+    let bind = |bindings: &mut std::collections::HashMap<String, String>, property: &str, var: &str| {
+        bindings.insert(property.to_string(), var.to_string());
+    };
+    for var in ["volume", "ratio"] {
+        let mut slider = SliderWidget { min: -10.0, max: 300.0, ..Default::default() };
+        bind(&mut slider.bindings, "value", var);
+        root.children.push(Box::new(slider));
+    }
+    for var in ["ratio", "total"] {
+        let mut progress = ProgressBarWidget::default();
+        bind(&mut progress.bindings, "value", var);
+        root.children.push(Box::new(progress));
+    }
+    for var in ["counter", "volume", "index"] {
+        let mut combo = ComboBoxWidget::default();
+        bind(&mut combo.bindings, "selected", var);
+        root.children.push(Box::new(combo));
+    }
+    let mut total_edit = TextEditWidget::default();
+    bind(&mut total_edit.bindings, "value", "total");
+    root.children.push(Box::new(total_edit));
+
+    // Several actions with a condition on one event
+    let mut step = ButtonWidget::default();
+    step.events.insert(
+        WidgetEvent::Clicked,
+        vec![
+            Action::If {
+                condition: Condition { variable: "volume".to_string(), op: CompareOp::Lt, value: "255".to_string() },
+                then: vec![Action::IncrementVariable("volume".to_string())],
+                otherwise: vec![Action::SetVariable("volume".to_string(), "0".to_string())],
+            },
+            Action::If {
+                condition: Condition { variable: "mode".to_string(), op: CompareOp::Eq, value: "Mode::Dark".to_string() },
+                then: vec![Action::SetVariable("mode".to_string(), "Mode::Light".to_string())],
+                otherwise: Vec::new(),
+            },
+            Action::NavigateTo("Settings Page".to_string()),
+        ],
+    );
+    root.children.push(Box::new(step));
+
+    // The built-in actions on variables, URLs and the clipboard
+    let mut more = ButtonWidget::default();
+    more.events.insert(
+        WidgetEvent::Clicked,
+        vec![
+            Action::DecrementVariable("counter".to_string()),
+            Action::AddToVariable("ratio".to_string(), "0.5".to_string()),
+            Action::ToggleVariable("enabled".to_string()),
+            Action::CopyVariable { from: "counter".to_string(), to: "best".to_string() },
+            Action::CopyVariable { from: "todos".to_string(), to: "archive".to_string() },
+            Action::PushToList(
+                "todos".to_string(),
+                "Todo { title: \"New\".to_string(), ..Default::default() }".to_string(),
+            ),
+            Action::ClearList("archive".to_string()),
+            Action::OpenUrl("https://example.com/{counter}".to_string()),
+            Action::CopyToClipboard("Count: {counter}".to_string()),
+            Action::CopyToClipboard("plain".to_string()),
+        ],
+    );
+    root.children.push(Box::new(more));
+
+    // Tooltips, enabled state and visibility, fixed and bound
+    let mut hint = LabelWidget { text: "Hover me".to_string(), ..Default::default() };
+    hint.common = CommonProperties {
+        tooltip: "Counter is {counter}".to_string(),
+        enabled: Flag::Bound("enabled".to_string()),
+        visible: Flag::Bound("enabled".to_string()),
+    };
+    root.children.push(Box::new(hint));
+    let mut disabled_window = WindowWidget::default();
+    disabled_window.common.enabled = Flag::Static(false);
+    disabled_window.common.tooltip = "Not yet".to_string();
+    root.children.push(Box::new(disabled_window));
+    let mut hidden = ButtonWidget::default();
+    hidden.common.visible = Flag::Static(false);
+    hidden.events.insert(WidgetEvent::Clicked, vec![Action::IncrementVariable("counter".to_string())]);
+    root.children.push(Box::new(hidden));
+
     let mut project = ProjectState::new(Box::new(root));
     project.project_name = "codegen_test_project".to_string();
-
-    // Generate code
-    let app_rs = Compiler::generate_app_rs(&project);
-    let main_rs = Compiler::generate_main_rs();
-    let cargo_toml = Compiler::generate_cargo_toml(&project.project_name);
+    for (name, v_type, value) in [
+        ("volume", VariableType::U8, "200"),
+        ("ratio", VariableType::F32, "0.25"),
+        ("total", VariableType::I64, "-5000000000"),
+        ("index", VariableType::Usize, "1"),
+    ] {
+        project.variables.insert(
+            name.to_string(),
+            Variable { name: name.to_string(), v_type, value: value.to_string() },
+        );
+    }
+    project.screens.push(Screen::new("Settings Page", Box::new(settings)));
+    project.variables.insert(
+        "counter".to_string(),
+        Variable {
+            name: "counter".to_string(),
+            v_type: VariableType::Integer,
+            value: "0".to_string(),
+        },
+    );
+    project.types.push(CustomType::new_enum("Mode", &["Light", "Dark"]));
+    project.types.push(CustomType::new_struct(
+        "Todo",
+        vec![
+            StructField { name: "title".to_string(), v_type: VariableType::String },
+            StructField { name: "done".to_string(), v_type: VariableType::Boolean },
+            StructField { name: "mode".to_string(), v_type: VariableType::Enum("Mode".to_string()) },
+        ],
+    ));
+    for (name, v_type, value) in [
+        ("mode", VariableType::Enum("Mode".to_string()), "Dark"),
+        ("todos", VariableType::List(Box::new(VariableType::Struct("Todo".to_string()))), r#"[{"title": "Write tests", "done": true}]"#),
+        ("accent", VariableType::Color, "#3366ff"),
+        ("archive", VariableType::List(Box::new(VariableType::Struct("Todo".to_string()))), "[]"),
+        ("enabled", VariableType::Boolean, "true"),
+        ("best", VariableType::Integer, "0"),
+    ] {
+        project.variables.insert(
+            name.to_string(),
+            Variable { name: name.to_string(), v_type, value: value.to_string() },
+        );
+    }
+    assert!(aether_rad::validator::CodeValidator::validate_logic(&project).is_empty());
 
     // Create temp directory
     let temp_dir = std::env::temp_dir().join("aether_rad_codegen_test");
-    let src_dir = temp_dir.join("src");
 
     // Clean up any previous test run
     let _ = fs::remove_dir_all(&temp_dir);
 
-    // Create directories
-    fs::create_dir_all(&src_dir).expect("Failed to create temp src directory");
+    // Export, hand-edit the user-owned handlers, then export again
+    Compiler::export_project(&project, &temp_dir).expect("Failed to export project");
+    let handlers = format!(
+        r#"use crate::app_generated::{{AppEvents, MyApp}};
+
+impl MyApp {{
+    pub fn reset_counter(&mut self) {{
+        self.counter = 0;
+    }}
+}}
+
+impl AppEvents for MyApp {{
+    fn {}(&mut self) {{
+        self.counter += 1;
+    }}
+}}
+"#,
+        click_hook
+    );
+    fs::write(temp_dir.join("src/handlers.rs"), &handlers).expect("Failed to write handlers.rs");
+    Compiler::export_project(&project, &temp_dir).expect("Failed to re-export project");
+    assert_eq!(
+        fs::read_to_string(temp_dir.join("src/handlers.rs")).unwrap(),
+        handlers,
+        "Re-export must not overwrite handlers.rs"
+    );
+
+    // The same design exported as an embeddable component must compile too
+    let mut component = project.clone();
+    component.export.mode = ExportMode::ComponentWidget;
+    component.export.struct_name = "CounterPanel".to_string();
+    component.export.module_path = "component".to_string();
+    Compiler::export_project(&component, &temp_dir).expect("Failed to export component");
+    fs::write(
+        temp_dir.join("src/component/handlers.rs"),
+        r#"use super::{CounterPanel, CounterPanelEvents};
+
+impl CounterPanel {
+    pub fn reset_counter(&mut self) {
+        self.counter = 0;
+    }
+}
 
-    // Write files
-    fs::write(temp_dir.join("Cargo.toml"), cargo_toml).expect("Failed to write Cargo.toml");
-    fs::write(src_dir.join("main.rs"), main_rs).expect("Failed to write main.rs");
-    fs::write(src_dir.join("app.rs"), app_rs).expect("Failed to write app.rs");
+impl CounterPanelEvents for CounterPanel {}
+"#,
+    )
+    .expect("Failed to write component handlers.rs");
+    let main_rs = fs::read_to_string(temp_dir.join("src/main.rs")).unwrap();
+    fs::write(temp_dir.join("src/main.rs"), format!("{}\nmod component;\n", main_rs)).unwrap();
 
     // Run cargo check
     let output = Command::new("cargo")
@@ -556,3 +834,1879 @@ fn test_codegen_compiles_successfully() {
     // Assert success
     assert!(output.status.success(), "Generated code should compile successfully");
 }
+
+#[test]
+fn test_cargo_toml_includes_widget_dependencies() {
+    use aether_rad::widgets::{ImageWidget, PlotWidget, TableWidget};
+
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(TableWidget::default()));
+
+    let mut nested = HorizontalLayout::default();
+    nested.children.push(Box::new(PlotWidget::default()));
+    nested.children.push(Box::new(ImageWidget::default()));
+    root.children.push(Box::new(nested));
+
+    let project = ProjectState::new(Box::new(root));
+    let deps = Compiler::collect_dependencies(&project);
+
+    // egui_extras is declared by both Table and Image and must be merged once
+    let extras: Vec<_> = deps.iter().filter(|d| d.name == "egui_extras").collect();
+    assert_eq!(extras.len(), 1);
+    assert!(extras[0].features.contains(&"image".to_string()));
+
+    let cargo_toml = Compiler::generate_cargo_toml("deps_test", &deps);
+    assert!(cargo_toml.contains("egui_plot = \"0.34.0\""));
+    assert!(cargo_toml.contains("egui_extras = { version = \"0.33.3\", features = [\"file\", \"image\"] }"));
+    assert!(cargo_toml.contains("image = {"));
+
+    // A project without those widgets only needs egui and eframe
+    let plain = ProjectState::new(Box::new(VerticalLayout::default()));
+    assert!(Compiler::collect_dependencies(&plain).is_empty());
+}
+
+#[test]
+fn test_widget_state_fields_are_generated() {
+    let window = WindowWidget::default();
+    let window_field = format!("window_{}_open", window.id.simple());
+    let tabs = TabContainerWidget {
+        selected_tab: 1,
+        ..Default::default()
+    };
+    let tab_field = format!("tab_{}_selected", tabs.id.simple());
+
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(window));
+    root.children.push(Box::new(tabs));
+
+    let mut project = ProjectState::new(Box::new(root));
+    // A user variable that happens to use the window's field name
+    project.variables.insert(
+        window_field.clone(),
+        Variable {
+            name: window_field.clone(),
+            v_type: VariableType::Boolean,
+            value: "false".to_string(),
+        },
+    );
+
+    let app_rs = Compiler::generate_app_generated_rs(&project);
+
+    // The tab index is private state initialised from the designer selection
+    assert!(app_rs.contains(&format!("{}: usize", tab_field)));
+    assert!(app_rs.contains(&format!("{}: 1usize", tab_field)));
+    assert!(app_rs.contains(&format!("self.{}", tab_field)));
+
+    // The window flag is renamed instead of clashing with the variable
+    assert!(app_rs.contains(&format!("pub {}: bool", window_field)));
+    assert!(app_rs.contains(&format!("{}_2: bool", window_field)));
+    assert!(app_rs.contains(&format!("self.{}_2", window_field)));
+}
+
+#[test]
+fn test_counter_template_binds_integer_label() {
+    let project = ProjectState::template_counter_app();
+    let app_rs = Compiler::generate_app_generated_rs(&project);
+
+    // Integer bindings are converted for display instead of borrowed as &str
+    assert!(app_rs.contains("ui.label(self.counter.to_string())"));
+    assert!(!app_rs.contains("ui.label(&self.counter)"));
+
+    // Every widget in the template passes logical validation
+    for child in project.root().children().unwrap() {
+        assert!(child.validate(&project.variables).is_empty(), "{} failed validation", child.name());
+    }
+}
+
+#[test]
+fn test_text_edit_bound_to_number_parses_input() {
+    let mut edit = TextEditWidget::default();
+    edit.bindings.insert("value".to_string(), "amount".to_string());
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(edit.clone()));
+
+    let mut project = ProjectState::new(Box::new(root));
+    project.variables.insert(
+        "amount".to_string(),
+        Variable {
+            name: "amount".to_string(),
+            v_type: VariableType::Float,
+            value: "1.5".to_string(),
+        },
+    );
+
+    assert!(edit.validate(&project.variables).is_empty());
+
+    let app_rs = Compiler::generate_app_generated_rs(&project);
+    let buffer = format!("amount_text_{}", edit.id.simple());
+    assert!(app_rs.contains(&format!("{}: String", buffer)));
+    assert!(app_rs.contains(".parse()"));
+    assert!(app_rs.contains("self.amount = value"));
+}
+
+#[test]
+fn test_event_hooks_are_generated() {
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, vec![Action::Custom(String::new())]);
+    let mut edit = TextEditWidget::default();
+    edit.bindings.insert("value".to_string(), "name".to_string());
+    edit.events.insert(WidgetEvent::LostFocus, vec![Action::Custom(String::new())]);
+
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(button.clone()));
+    root.children.push(Box::new(edit.clone()));
+    let mut project = ProjectState::new(Box::new(root));
+    project.variables.insert(
+        "name".to_string(),
+        Variable {
+            name: "name".to_string(),
+            v_type: VariableType::String,
+            value: String::new(),
+        },
+    );
+
+    let app_rs = Compiler::generate_app_generated_rs(&project);
+    let click_hook = format!("on_button_{}_clicked", button.id.simple());
+    let blur_hook = format!("on_text_edit_{}_lost_focus", edit.id.simple());
+
+    // One default trait method per configured event, called from the layout
+    assert!(app_rs.contains("pub trait AppEvents"));
+    assert!(app_rs.contains(&format!("fn {}(&mut self) {{}}", click_hook)));
+    assert!(app_rs.contains(&format!("fn {}(&mut self) {{}}", blur_hook)));
+    assert!(app_rs.contains(&format!("self.{}()", click_hook)));
+    assert!(app_rs.contains(&format!("self.{}()", blur_hook)));
+
+    // main.rs wires both modules, and the user file implements the trait
+    let main_rs = Compiler::generate_main_rs();
+    assert!(main_rs.contains("mod app_generated;"));
+    assert!(main_rs.contains("mod handlers;"));
+    assert!(Compiler::generate_handlers_rs(&project).contains("impl AppEvents for MyApp"));
+
+    let files = Compiler::generate_project_files(&project);
+    let handlers = files.iter().find(|f| f.path == "src/handlers.rs").unwrap();
+    assert!(handlers.user_owned);
+    assert!(files.iter().filter(|f| f.path != "src/handlers.rs").all(|f| !f.user_owned));
+}
+
+#[test]
+fn test_import_generated_code_round_trip() {
+    use aether_rad::importer::Importer;
+    use aether_rad::widgets::{ScrollAreaWidget, TableWidget};
+
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, vec![Action::IncrementVariable("counter".to_string())]);
+    let mut label = LabelWidget::default();
+    label.bindings.insert("text".to_string(), "counter".to_string());
+    let mut slider = SliderWidget::default();
+    slider.bindings.insert("value".to_string(), "volume".to_string());
+    let mut window = WindowWidget {
+        title: "Settings ({counter})".to_string(),
+        ..Default::default()
+    };
+    window.children.push(Box::new(slider));
+    window.children.push(Box::new(LabelWidget {
+        text: "Volume: {volume:>6.2} {{dB}}".to_string(),
+        ..Default::default()
+    }));
+    let mut scroll = ScrollAreaWidget::default();
+    scroll.children.push(Box::new(TableWidget::default()));
+    let mut row = HorizontalLayout::default();
+    row.children.push(Box::new(button.clone()));
+    row.children.push(Box::new(label));
+
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(row));
+    root.children.push(Box::new(window));
+    root.children.push(Box::new(scroll));
+    let mut project = ProjectState::new(Box::new(root));
+    for (name, v_type, value) in [("counter", VariableType::Integer, "3"), ("volume", VariableType::Float, "0.5")] {
+        project.variables.insert(
+            name.to_string(),
+            Variable {
+                name: name.to_string(),
+                v_type,
+                value: value.to_string(),
+            },
+        );
+    }
+
+    let source = Compiler::generate_app_generated_rs(&project);
+    let imported = Importer::import_source(&source).expect("Generated code should import");
+
+    // Variables come from the struct; the window's open flag is widget state
+    assert_eq!(imported.variables.len(), 2);
+    assert_eq!(imported.variables["counter"].v_type, VariableType::Integer);
+    assert_eq!(imported.variables["counter"].value, "3");
+    assert_eq!(imported.variables["volume"].v_type, VariableType::Float);
+
+    let children = imported.root().children().unwrap();
+    let names: Vec<&str> = children.iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["Horizontal Layout", "Window", "Scroll Area"]);
+
+    // The button keeps its id (recovered from the hook name) so handlers still match
+    let row = children[0].children().unwrap();
+    assert_eq!(row[0].id(), button.id);
+    assert_eq!(row[1].name(), "Label");
+    assert_eq!(children[1].children().unwrap()[0].name(), "Slider");
+    let window = serde_json::to_value(&children[1]).unwrap();
+    assert_eq!(window["title"], "Settings ({counter})");
+    let volume = serde_json::to_value(&children[1].children().unwrap()[1]).unwrap();
+    assert_eq!(volume["text"], "Volume: {volume:>6.2} {{dB}}");
+    assert_eq!(children[2].children().unwrap()[0].name(), "Table");
+
+    // Re-generating gives the same code
+    let mut imported = imported;
+    imported.project_name = project.project_name.clone();
+    let regenerated = Compiler::generate_app_generated_rs(&imported);
+    assert!(regenerated.contains(&format!("on_button_{}_clicked", button.id.simple())));
+    assert!(regenerated.contains("self.counter += 1"));
+    assert!(regenerated.contains("egui::Window::new(format!(\"Settings ({})\", self.counter))"));
+    assert!(regenerated.contains("format!(\"Volume: {:>6.2} {{dB}}\", self.volume)"));
+    assert!(regenerated.contains("egui_extras::TableBuilder::new(ui)"));
+}
+
+#[test]
+fn test_import_keeps_unrecognized_code() {
+    use aether_rad::importer::Importer;
+
+    let source = r#"
+        pub struct MyApp {
+            name: String,
+            enabled: bool,
+            history: Vec<String>,
+        }
+
+        impl Default for MyApp {
+            fn default() -> Self {
+                Self {
+                    name: "World".to_owned(),
+                    enabled: true,
+                    history: Vec::new(),
+                }
+            }
+        }
+
+        impl eframe::App for MyApp {
+            fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.heading("My App");
+                    ui.text_edit_singleline(&mut self.name);
+                    if ui.button("Greet").clicked() {
+                        self.history.push(format!("Hello {}", self.name));
+                    }
+                    ui.checkbox(&mut self.enabled, "Enabled");
+                });
+            }
+        }
+    "#;
+
+    let project = Importer::import_source(source).expect("Source should import");
+
+    // Only representable field types become variables
+    assert_eq!(project.variables.len(), 2);
+    assert_eq!(project.variables["name"].value, "World");
+    assert_eq!(project.variables["enabled"].value, "true");
+
+    let children = project.root().children().unwrap();
+    let names: Vec<&str> = children.iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["Custom Code", "Text Edit", "Button", "Checkbox"]);
+
+    let app_rs = Compiler::generate_app_generated_rs(&project);
+    assert!(app_rs.contains("ui.heading(\"My App\")"));
+    assert!(app_rs.contains("self.history.push"));
+
+    assert!(Importer::import_source("fn main() {").is_err());
+}
+
+#[test]
+fn test_component_export_mode() {
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, vec![Action::IncrementVariable("count".to_string())]);
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(button));
+    root.children.push(Box::new(WindowWidget::default()));
+
+    let mut project = ProjectState::new(Box::new(root));
+    project.variables.insert(
+        "count".to_string(),
+        Variable {
+            name: "count".to_string(),
+            v_type: VariableType::Integer,
+            value: "0".to_string(),
+        },
+    );
+    project.export.mode = ExportMode::ComponentUi;
+    project.export.struct_name = "SettingsPanel".to_string();
+    project.export.module_path = "panels::settings".to_string();
+
+    // Only the component and its handlers; no manifest or main.rs
+    let files = Compiler::generate_project_files(&project);
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, ["src/panels/settings.rs", "src/panels/settings/handlers.rs"]);
+    assert!(files[1].user_owned);
+    assert!(files[1].contents.contains("impl SettingsPanelEvents for SettingsPanel {}"));
+
+    let code = &files[0].contents;
+    assert!(code.contains("mod handlers;"));
+    assert!(code.contains("pub trait SettingsPanelEvents"));
+    assert!(code.contains("pub struct SettingsPanel"));
+    assert!(code.contains("impl Default for SettingsPanel"));
+    assert!(code.contains("pub fn ui(&mut self, ui: &mut egui::Ui)"));
+    assert!(!code.contains("eframe"));
+    assert!(!code.contains("CentralPanel"));
+
+    project.export.mode = ExportMode::ComponentWidget;
+    let code = Compiler::generate_component_rs(&project);
+    assert!(code.contains("impl egui::Widget for &mut SettingsPanel"));
+    assert!(code.contains("fn ui(self, ui: &mut egui::Ui) -> egui::Response"));
+
+    // Invalid names fall back to the defaults instead of producing broken code
+    project.export.struct_name = "Settings Panel".to_string();
+    project.export.module_path = "panels::".to_string();
+    let files = Compiler::generate_project_files(&project);
+    assert_eq!(files[0].path, "src/my_component.rs");
+    assert!(files[0].contents.contains("pub struct MyComponent"));
+}
+
+#[test]
+fn test_multiple_screens_codegen() {
+    let mut main = VerticalLayout::default();
+    let mut go = ButtonWidget::default();
+    go.events.insert(WidgetEvent::Clicked, vec![Action::NavigateTo("game over".to_string())]);
+    main.children.push(Box::new(go));
+
+    let mut project = ProjectState::new(Box::new(main));
+    project.screens.push(Screen::new("game over", Box::new(VerticalLayout::default())));
+
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains("pub enum Screen"));
+    assert!(code.contains("Main,"));
+    assert!(code.contains("GameOver,"));
+    assert!(code.contains("pub current_screen: Screen"));
+    assert!(code.contains("current_screen: Screen::Main"));
+    assert!(code.contains("match self.current_screen"));
+    assert!(code.contains("Screen::GameOver => {"));
+    assert!(code.contains("self.current_screen = Screen::GameOver;"));
+
+    // A single screen keeps the plain layout
+    project.screens.pop();
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(!code.contains("enum Screen"));
+    assert!(!code.contains("current_screen"));
+}
+
+#[test]
+fn test_screen_variant_names() {
+    assert_eq!(Compiler::screen_variant("Main").to_string(), "Main");
+    assert_eq!(Compiler::screen_variant("main menu").to_string(), "MainMenu");
+    assert_eq!(Compiler::screen_variant("level-2 boss").to_string(), "Level2Boss");
+    assert_eq!(Compiler::screen_variant("2nd").to_string(), "Screen2nd");
+    assert_eq!(Compiler::screen_variant("").to_string(), "Screen");
+}
+
+#[test]
+fn test_screen_management() {
+    let mut project = ProjectState::empty();
+    assert_eq!(project.screens.len(), 1);
+
+    let index = project.add_screen();
+    assert_eq!(index, 1);
+    assert_eq!(project.active_screen, 1);
+    assert_eq!(project.screens[1].name, "Screen 2");
+
+    // Edits go to the active screen
+    project
+        .root_mut()
+        .children_mut()
+        .unwrap()
+        .push(Box::new(LabelWidget::default()));
+    assert_eq!(project.screens[1].root.children().unwrap().len(), 1);
+    assert_ne!(project.root().id(), project.screens[0].root.id());
+
+    assert!(project.remove_screen(1));
+    assert_eq!(project.active_screen, 0);
+    assert!(!project.remove_screen(0), "the last screen cannot be removed");
+}
+
+#[test]
+fn test_rename_screen_updates_navigation() {
+    let mut main = VerticalLayout::default();
+    let mut go = ButtonWidget::default();
+    go.events.insert(
+        WidgetEvent::Clicked,
+        vec![Action::If {
+            condition: Condition { variable: "ready".to_string(), op: CompareOp::IsTrue, value: String::new() },
+            then: vec![Action::NavigateTo("Settings".to_string())],
+            otherwise: Vec::new(),
+        }],
+    );
+    let go_id = go.id;
+    main.children.push(Box::new(go));
+
+    let mut project = ProjectState::new(Box::new(main));
+    project.screens.push(Screen::new("Settings", Box::new(VerticalLayout::default())));
+    project.rename_screen(1, "Options");
+    assert_eq!(project.screens[1].name, "Options");
+
+    let mut targets = Vec::new();
+    for action in &project.screens[0].root.children().unwrap()[0].events().unwrap()[&WidgetEvent::Clicked] {
+        action.visit(&mut |action| {
+            if let Action::NavigateTo(target) = action {
+                targets.push(target.clone());
+            }
+        });
+    }
+    assert_eq!(targets, vec!["Options".to_string()]);
+    assert_eq!(project.screens[0].root.children().unwrap()[0].id(), go_id);
+
+    // While another screen still has the old name, references keep pointing at it
+    project.screens.push(Screen::new("Options", Box::new(VerticalLayout::default())));
+    project.rename_screen(2, "Extra");
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains("self.current_screen = Screen::Options;"));
+}
+
+#[test]
+fn test_legacy_project_loads_as_single_screen() {
+    let mut legacy: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&ProjectState::template_counter_app()).unwrap()).unwrap();
+    let object = legacy.as_object_mut().unwrap();
+    let root = object.remove("screens").unwrap()[0]["root"].clone();
+    object.remove("active_screen");
+    object.insert("root_node".to_string(), root);
+
+    let loaded: ProjectState = serde_json::from_value(legacy).unwrap();
+    assert_eq!(loaded.screens.len(), 1);
+    assert_eq!(loaded.screens[0].name, "Main");
+    assert_eq!(loaded.root().children().unwrap().len(), 3);
+}
+
+#[test]
+fn test_cli_argument_parsing() {
+    use aether_rad::cli::CliCommand;
+    use std::path::PathBuf;
+
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(CliCommand::parse(&[]), Ok(None));
+    assert_eq!(
+        CliCommand::parse(&args(&["export", "ui.json", "--out", "gen"])),
+        Ok(Some(CliCommand::Export {
+            project: PathBuf::from("ui.json"),
+            out: PathBuf::from("gen"),
+        }))
+    );
+    assert_eq!(
+        CliCommand::parse(&args(&["check", "ui.json"])),
+        Ok(Some(CliCommand::Check { project: PathBuf::from("ui.json") }))
+    );
+    assert!(CliCommand::parse(&args(&["export", "ui.json"])).is_err());
+    assert!(CliCommand::parse(&args(&["print"])).is_err());
+    assert!(CliCommand::parse(&args(&["exprot", "ui.json"])).is_err());
+}
+
+#[test]
+fn test_cli_export_and_check() {
+    use aether_rad::cli::{self, EXIT_FAILURE, EXIT_USAGE};
+
+    let dir = std::env::temp_dir().join(format!("aether_cli_test_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let project_path = dir.join("project.json");
+    let project = ProjectState::template_counter_app();
+    std::fs::write(&project_path, serde_json::to_string(&project).unwrap()).unwrap();
+    let path = project_path.to_string_lossy().to_string();
+    let out = dir.join("out");
+
+    let code = cli::run(&[
+        "export".to_string(),
+        path.clone(),
+        "--out".to_string(),
+        out.to_string_lossy().to_string(),
+    ]);
+    assert_eq!(code, Some(0));
+    assert!(out.join("Cargo.toml").exists());
+    assert!(out.join("src/app_generated.rs").exists());
+    assert!(out.join("src/handlers.rs").exists());
+
+    assert_eq!(cli::run(&["print".to_string(), path]), Some(0));
+
+    // Logical errors fail the check before cargo is ever run
+    let mut broken = project.clone();
+    broken.variables.clear();
+    std::fs::write(&project_path, serde_json::to_string(&broken).unwrap()).unwrap();
+    let path = project_path.to_string_lossy().to_string();
+    assert_eq!(cli::run(&["check".to_string(), path]), Some(EXIT_FAILURE));
+
+    let missing = dir.join("missing.json").to_string_lossy().to_string();
+    assert_eq!(cli::run(&["print".to_string(), missing]), Some(EXIT_FAILURE));
+    assert_eq!(cli::run(&["check".to_string()]), Some(EXIT_USAGE));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_source_map_traces_lines_to_widgets() {
+    let label = LabelWidget {
+        text: "Mapped label".to_string(),
+        ..Default::default()
+    };
+    let label_id = label.id;
+    let mut row = HorizontalLayout::default();
+    let row_id = row.id;
+    row.children.push(Box::new(label));
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(row));
+    let project = ProjectState::new(Box::new(root));
+
+    let (code, map) = Compiler::generate_app_generated_rs_mapped(&project);
+    assert_eq!(map.file, "src/app_generated.rs");
+    assert_eq!(code, Compiler::generate_app_generated_rs(&project));
+    assert!(!code.contains("aether_widget_begin"), "markers must be stripped:\n{}", code);
+
+    let label_line = code.lines().position(|l| l.contains("Mapped label")).unwrap() + 1;
+    assert_eq!(map.widget_at(label_line), Some(label_id));
+
+    let row_line = code.lines().position(|l| l.contains("ui.horizontal")).unwrap() + 1;
+    assert_eq!(map.widget_at(row_line), Some(row_id));
+    assert_eq!(map.widget_at(1), None);
+}
+
+#[test]
+fn test_cargo_diagnostics_map_to_widgets() {
+    use aether_rad::validator::parse_cargo_diagnostics;
+    use aether_rad::widgets::CustomCodeWidget;
+
+    let custom = CustomCodeWidget {
+        code: "let broken: u32 = \"text\";".to_string(),
+        ..Default::default()
+    };
+    let custom_id = custom.id;
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(custom));
+    let project = ProjectState::new(Box::new(root));
+
+    let (code, map) = Compiler::generate_app_generated_rs_mapped(&project);
+    let line = code.lines().position(|l| l.contains("let broken")).unwrap() + 1;
+
+    let error = serde_json::json!({
+        "reason": "compiler-message",
+        "message": {
+            "level": "error",
+            "message": "mismatched types",
+            "rendered": "error[E0308]: mismatched types\n",
+            "spans": [{ "file_name": "src/app_generated.rs", "line_start": line, "is_primary": true }],
+        },
+    });
+    let summary = serde_json::json!({
+        "reason": "compiler-message",
+        "message": { "level": "error", "message": "aborting due to 1 previous error", "spans": [] },
+    });
+    let output = format!(
+        "{}\n{}\n{}\n",
+        error,
+        summary,
+        serde_json::json!({ "reason": "build-finished", "success": false })
+    );
+
+    let diagnostics = parse_cargo_diagnostics(&output, &map);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "mismatched types");
+    assert_eq!(diagnostics[0].location, Some(("src/app_generated.rs".to_string(), line)));
+    assert_eq!(diagnostics[0].widget, Some(custom_id));
+}
+
+#[test]
+fn test_logical_validation_errors_name_the_widget() {
+    use aether_rad::validator::CodeValidator;
+    use std::sync::atomic::AtomicBool;
+
+    let mut project = ProjectState::template_counter_app();
+    project.variables.clear();
+    let diagnostics = CodeValidator::validate(&project, &mut |_| {}, &AtomicBool::new(false), None).unwrap_err();
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|d| d.widget.is_some()));
+
+    let id = diagnostics[0].widget.unwrap();
+    project.add_screen();
+    assert!(project.reveal_widget(id));
+    assert_eq!(project.active_screen, 0);
+    assert!(project.selection.contains(&id));
+}
+
+#[test]
+fn test_background_validation() {
+    use aether_rad::validator::{BackgroundValidation, ValidationStatus};
+    use std::time::{Duration, Instant};
+
+    let wait = |job: &BackgroundValidation, status: &mut ValidationStatus| {
+        let started = Instant::now();
+        while !job.poll(status) {
+            assert!(started.elapsed() < Duration::from_secs(60), "validation never finished");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    };
+
+    // Logical errors are reported without waiting for cargo
+    let mut broken = ProjectState::template_counter_app();
+    broken.variables.clear();
+    let mut status = ValidationStatus::NotRun;
+    let job = BackgroundValidation::start(&broken, None, || {});
+    wait(&job, &mut status);
+    assert!(matches!(status, ValidationStatus::Failed(ref d) if d.iter().all(|d| d.widget.is_some())));
+
+    // Cancelling stops cargo and reports the cancellation
+    let project = ProjectState::template_counter_app();
+    let job = BackgroundValidation::start(&project, None, || {});
+    job.cancel();
+    wait(&job, &mut status);
+    assert!(matches!(status, ValidationStatus::Cancelled), "{:?}", status);
+
+    // The scratch crate is kept for the next check
+    assert!(aether_rad::validator::CodeValidator::scratch_dir().join("Cargo.toml").exists());
+
+    // A check waits while another one, possibly in another process, holds the scratch crate
+    let lock = std::fs::File::create(aether_rad::validator::CodeValidator::scratch_dir().join(".lock")).unwrap();
+    lock.lock().unwrap();
+    let job = BackgroundValidation::start(&project, None, || {});
+    let started = Instant::now();
+    while !matches!(status, ValidationStatus::Checking(ref line) if line.contains("Waiting for another check")) {
+        assert!(!job.poll(&mut status), "finished while the crate was locked: {:?}", status);
+        assert!(started.elapsed() < Duration::from_secs(60), "validation never waited");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    job.cancel();
+    wait(&job, &mut status);
+    assert!(matches!(status, ValidationStatus::Cancelled), "{:?}", status);
+    drop(lock);
+
+    // A check that outlives its timeout fails
+    let job = BackgroundValidation::start(&project, Some(Duration::ZERO), || {});
+    wait(&job, &mut status);
+    match status {
+        ValidationStatus::Failed(diagnostics) => assert!(diagnostics[0].message.contains("timed out")),
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_play_session_stop_and_staleness() {
+    use aether_rad::runner::{PlaySession, PlayState};
+    use std::time::{Duration, Instant};
+
+    let mut project = ProjectState::template_counter_app();
+    project.export.mode = ExportMode::ComponentWidget;
+    project.export.struct_name = "CounterPanel".to_string();
+    let image = std::env::temp_dir().join(format!("aether_play_asset_{}.png", uuid::Uuid::new_v4()));
+    std::fs::write(&image, b"not really a png").unwrap();
+    project.assets.add_asset("logo".to_string(), aether_rad::model::AssetType::Image, image.clone());
+
+    let mut session = PlaySession::start(&project, || {});
+    session.stop();
+    let started = Instant::now();
+    while session.state().is_active() {
+        assert!(started.elapsed() < Duration::from_secs(60), "play session never stopped");
+        std::thread::sleep(Duration::from_millis(10));
+        session.poll();
+    }
+    assert_eq!(session.state(), &PlayState::Stopped);
+
+    // Components are played through a generated host binary
+    let workspace = PlaySession::workspace_dir();
+    let main_rs = std::fs::read_to_string(workspace.join("src/main.rs")).unwrap();
+    assert!(main_rs.contains("mod component;"));
+    assert!(main_rs.contains("ui.add(&mut self.0);"));
+    assert!(workspace.join("src/component.rs").exists());
+    let cargo_toml = std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("name = \"aether_play\""));
+    // Images load from the workspace's assets, as they would next to an export
+    assert!(workspace.join("assets").join(image.file_name().unwrap()).exists());
+
+    assert!(!session.is_stale(&project));
+    project
+        .root_mut()
+        .children_mut()
+        .unwrap()
+        .push(Box::new(LabelWidget::default()));
+    assert!(session.is_stale(&project));
+
+    // The workspace goes with the session
+    drop(session);
+    assert!(!workspace.exists());
+    let _ = std::fs::remove_file(&image);
+}
+
+#[test]
+fn test_preview_simulates_actions() {
+    use aether_rad::preview::PreviewState;
+
+    let mut project = ProjectState::template_counter_app();
+    project.variables.insert(
+        "message".to_string(),
+        Variable { name: "message".to_string(), v_type: VariableType::String, value: "".to_string() },
+    );
+    project.variables.insert(
+        "done".to_string(),
+        Variable { name: "done".to_string(), v_type: VariableType::Boolean, value: "false".to_string() },
+    );
+    project.screens.push(Screen::new("Settings", Box::new(VerticalLayout::default())));
+
+    let mut preview = PreviewState::new(&project);
+    let button = &project.root().children().unwrap()[2];
+    let events = button.events().unwrap().clone();
+
+    // Clicking the counter button twice increments the live copy only
+    preview.fire("Button", &events, WidgetEvent::Clicked);
+    preview.fire("Button", &events, WidgetEvent::Clicked);
+    assert_eq!(preview.display("counter").as_deref(), Some("2"));
+    assert_eq!(project.variables["counter"].value, "0");
+    assert_eq!(preview.log().len(), 2);
+    assert_eq!(preview.log()[1].message, "Button On Click: counter = 2");
+
+    // Events without an action do nothing
+    preview.fire("Button", &events, WidgetEvent::Hovered);
+    assert_eq!(preview.log().len(), 2);
+
+    // Literal assignments are interpreted
+    Action::SetVariable("message".to_string(), "\"hi\".to_string()".to_string()).simulate(&mut preview);
+    Action::SetVariable("done".to_string(), "true".to_string()).simulate(&mut preview);
+    Action::SetVariable("counter".to_string(), "-7".to_string()).simulate(&mut preview);
+    assert_eq!(preview.display("message").as_deref(), Some("hi"));
+    assert_eq!(preview.display("done").as_deref(), Some("true"));
+    assert_eq!(preview.display("counter").as_deref(), Some("-7"));
+
+    // Expressions, type mismatches and custom code are not simulated
+    let outcome = Action::SetVariable("counter".to_string(), "self.counter * 2".to_string()).simulate(&mut preview);
+    assert!(outcome.starts_with("not simulated"));
+    let outcome = Action::SetVariable("counter".to_string(), "\"text\"".to_string()).simulate(&mut preview);
+    assert!(outcome.starts_with("not simulated"));
+    assert_eq!(preview.display("counter").as_deref(), Some("-7"));
+    let outcome = Action::Custom("println!(\"hi\");".to_string()).simulate(&mut preview);
+    assert!(outcome.starts_with("not simulated"));
+    let outcome = Action::IncrementVariable("message".to_string()).simulate(&mut preview);
+    assert!(outcome.starts_with("cannot increment"));
+
+    // Navigation switches the previewed screen
+    assert_eq!(preview.current_screen, 0);
+    Action::NavigateTo("Settings".to_string()).simulate(&mut preview);
+    assert_eq!(preview.current_screen, 1);
+    let outcome = Action::NavigateTo("Missing".to_string()).simulate(&mut preview);
+    assert_eq!(outcome, "no screen 'Missing'");
+    assert_eq!(preview.current_screen, 1);
+}
+
+#[test]
+fn test_preview_state_sync_and_parsing() {
+    use aether_rad::preview::PreviewState;
+
+    let mut project = ProjectState::template_counter_app();
+    let mut preview = PreviewState::new(&project);
+
+    assert!(preview.set_parsed("counter", " 12 "));
+    assert!(!preview.set_parsed("counter", "twelve"));
+    assert_eq!(preview.display("counter").as_deref(), Some("12"));
+
+    // Live values survive a sync; added variables appear and removed ones disappear
+    project.variables.insert(
+        "ratio".to_string(),
+        Variable { name: "ratio".to_string(), v_type: VariableType::Float, value: "0.5".to_string() },
+    );
+    preview.sync(&project);
+    assert_eq!(preview.display("counter").as_deref(), Some("12"));
+    assert_eq!(preview.number("ratio"), Some(0.5));
+
+    project.variables.remove("ratio");
+    project.variables.get_mut("counter").unwrap().v_type = VariableType::Float;
+    preview.sync(&project);
+    assert!(preview.display("ratio").is_none());
+    // A retyped variable restarts from its initial value
+    assert_eq!(preview.display("counter").as_deref(), Some("0"));
+
+    // Repeated log lines are folded
+    preview.log_message("hovered");
+    preview.log_message("hovered");
+    assert_eq!(preview.log().len(), 1);
+    assert_eq!(preview.log()[0].to_string(), "hovered (×2)");
+}
+
+#[test]
+fn test_render_preview_all_widgets() {
+    use aether_rad::preview::PreviewState;
+    use aether_rad::widgets::create_widget_by_name;
+
+    let names = [
+        "Button", "Label", "Text Edit", "Checkbox", "Slider", "Progress Bar", "ComboBox", "Image",
+        "Vertical Layout", "Horizontal Layout", "Grid Layout", "Freeform Layout", "Separator",
+        "Spinner", "Hyperlink", "Custom Code", "Color Picker", "Table", "Plot", "Scroll Area",
+        "Tab Container", "Window",
+    ];
+    let mut root = VerticalLayout::default();
+    for name in names {
+        root.children.push(create_widget_by_name(name).unwrap());
+    }
+    let project = ProjectState::new(Box::new(root));
+    let mut preview = PreviewState::new(&project);
+
+    // Render a couple of frames headlessly; every widget must draw without panicking
+    let ctx = egui::Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                project.root().render_preview(ui, &mut preview);
+            });
+        });
+    }
+    assert!(preview.log().is_empty());
+}
+
+#[test]
+fn test_custom_variable_types() {
+    use aether_rad::validator::CodeValidator;
+
+    let mut project = ProjectState::template_counter_app();
+    project.types.push(CustomType::new_enum("Priority", &["Low", "High"]));
+    project.types.push(CustomType::new_struct(
+        "Task",
+        vec![
+            StructField { name: "title".to_string(), v_type: VariableType::String },
+            StructField { name: "priority".to_string(), v_type: VariableType::Enum("Priority".to_string()) },
+        ],
+    ));
+    let tasks = VariableType::List(Box::new(VariableType::Struct("Task".to_string())));
+    let types = project.types.clone();
+
+    // Values parse against the project's types, and missing struct fields take defaults
+    assert_eq!(
+        VariableType::Struct("Task".to_string()).default_value(&types),
+        r#"{"priority":"Low","title":""}"#
+    );
+    assert!(tasks.parse_value(r#"[{"title": "a", "priority": "High"}, {}]"#, &types).is_ok());
+    assert!(tasks.parse_value(r#"[{"title": 1}]"#, &types).is_err());
+    assert!(tasks.parse_value(r#"[{"owner": "me"}]"#, &types).is_err());
+    assert!(VariableType::Enum("Priority".to_string()).parse_value("Urgent", &types).is_err());
+    assert!(VariableType::Color.parse_value("#ff000080", &types).is_ok());
+    assert!(VariableType::Color.parse_value("red", &types).is_err());
+
+    project.variables.insert(
+        "tasks".to_string(),
+        Variable { name: "tasks".to_string(), v_type: tasks, value: r#"[{"title": "Ship it", "priority": "High"}]"#.to_string() },
+    );
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains("pub enum Priority"), "{}", code);
+    assert!(code.contains("pub struct Task"), "{}", code);
+    assert!(code.contains("pub tasks: Vec<Task>"), "{}", code);
+    assert!(code.contains("Priority::High"), "{}", code);
+
+    // Renaming a type follows through to fields and variables
+    project.rename_type("Priority", "Urgency");
+    assert_eq!(
+        project.variables["tasks"].v_type,
+        VariableType::List(Box::new(VariableType::Struct("Task".to_string())))
+    );
+    let aether_rad::model::CustomTypeKind::Struct { fields } = &project.types[1].kind else {
+        panic!("Task should still be a struct");
+    };
+    assert_eq!(fields[1].v_type, VariableType::Enum("Urgency".to_string()));
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+
+    // Invalid definitions are reported before cargo runs
+    let mut broken = project.clone();
+    broken.types.push(CustomType::new_enum("Empty", &[]));
+    broken.types.push(CustomType::new_struct(
+        "Node",
+        vec![StructField { name: "next".to_string(), v_type: VariableType::Struct("Node".to_string()) }],
+    ));
+    broken.types.remove(0);
+    let messages: Vec<String> = CodeValidator::validate_logic(&broken).into_iter().map(|d| d.message).collect();
+    assert!(messages.iter().any(|m| m.contains("has no variants")), "{:?}", messages);
+    assert!(messages.iter().any(|m| m.contains("contains itself")), "{:?}", messages);
+    assert!(messages.iter().any(|m| m.contains("unknown enum 'Urgency'")), "{:?}", messages);
+
+    // Projects saved before custom types still load
+    let mut json: serde_json::Value = serde_json::from_str(&ProjectState::template_counter_app().to_json()).unwrap();
+    json.as_object_mut().unwrap().remove("types");
+    let loaded: ProjectState = serde_json::from_value(json).unwrap();
+    assert!(loaded.types.is_empty());
+}
+
+#[test]
+fn test_concrete_numeric_types() {
+    use aether_rad::validator::CodeValidator;
+
+    // Initial values are checked against the type's range
+    assert!(VariableType::U8.parse_value("255", &[]).is_ok());
+    assert!(VariableType::U8.parse_value("256", &[]).is_err());
+    assert!(VariableType::U32.parse_value("-1", &[]).is_err());
+    assert!(VariableType::Integer.parse_value("3000000000", &[]).is_err());
+    assert!(VariableType::I64.parse_value("-3000000000", &[]).is_ok());
+    assert!(VariableType::Usize.parse_value("18446744073709551615", &[]).is_ok());
+    assert!(VariableType::F32.parse_value("1e39", &[]).is_err());
+    assert!(VariableType::F32.parse_value("0.5", &[]).is_ok());
+
+    let mut project = ProjectState::template_counter_app();
+    project.variables.get_mut("counter").unwrap().v_type = VariableType::U8;
+    project.variables.get_mut("counter").unwrap().value = "300".to_string();
+    let errors = CodeValidator::validate_logic(&project);
+    assert!(errors.iter().any(|e| e.message.contains("out of range for u8")), "{:?}", errors);
+
+    project.variables.get_mut("counter").unwrap().value = "7".to_string();
+    let mut slider = SliderWidget { min: 0.0, max: 1000.0, ..Default::default() };
+    slider.bindings.insert("value".to_string(), "counter".to_string());
+    project.screens[0].root.children_mut().unwrap().push(Box::new(slider));
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains("pub counter: u8"), "{}", code);
+    assert!(code.contains("counter: 7"), "{}", code);
+    // The slider names its numeric type and its range is clamped to it
+    assert!(code.contains("Slider::new::<u8>(&mut self.counter, 0..=255)"), "{}", code);
+
+    // Serialized names of the existing types don't change
+    assert_eq!(serde_json::to_string(&VariableType::Integer).unwrap(), "\"Integer\"");
+    assert_eq!(serde_json::to_string(&VariableType::U8).unwrap(), "\"U8\"");
+    assert_eq!(VariableType::Usize.to_string(), "usize");
+}
+
+#[test]
+fn test_interpolated_text() {
+    use aether_rad::interpolation::{self, FormatSpec, Segment};
+    use aether_rad::preview::PreviewState;
+    use aether_rad::validator::CodeValidator;
+
+    assert_eq!(
+        interpolation::parse("{first} {last} ({age:.1}) {{x}} {not a placeholder}"),
+        vec![
+            Segment::Placeholder { var: "first".to_string(), spec: String::new() },
+            Segment::Text(" ".to_string()),
+            Segment::Placeholder { var: "last".to_string(), spec: String::new() },
+            Segment::Text(" (".to_string()),
+            Segment::Placeholder { var: "age".to_string(), spec: ".1".to_string() },
+            Segment::Text(") {x} {not a placeholder}".to_string()),
+        ]
+    );
+    let spec = |s: &str| FormatSpec::parse(s).unwrap();
+    assert_eq!(spec(">6.2").apply(&VariableType::Float, "12.345"), format!("{:>6.2}", 12.345));
+    assert_eq!(spec("+05").apply(&VariableType::Integer, "-42"), format!("{:+05}", -42));
+    assert_eq!(spec("#x").apply(&VariableType::U32, "255"), format!("{:#x}", 255));
+    assert_eq!(spec("*^7").apply(&VariableType::String, "ab"), format!("{:*^7}", "ab"));
+    assert_eq!(spec("?").apply(&VariableType::String, "ab"), format!("{:?}", "ab"));
+    assert!(FormatSpec::parse("q").is_err());
+
+    let mut project = ProjectState::template_counter_app();
+    let mut label = LabelWidget { text: "Count: {counter:>3} of {total}".to_string(), ..Default::default() };
+    project.screens[0].root.children_mut().unwrap().push(Box::new(label.clone()));
+
+    // Unknown variables are reported and written literally
+    let errors = CodeValidator::validate_logic(&project);
+    assert!(errors.iter().any(|e| e.message.contains("unknown variable 'total'")), "{:?}", errors);
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains(r#"format!("Count: {:>3} of {{total}}", self.counter)"#), "{}", code);
+
+    project.variables.insert(
+        "total".to_string(),
+        Variable { name: "total".to_string(), v_type: VariableType::U8, value: "9".to_string() },
+    );
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains(r#"format!("Count: {:>3} of {}", self.counter, self.total)"#), "{}", code);
+
+    // Text without placeholders stays a plain literal
+    label.text = "Plain {{text}}".to_string();
+    let mut ctx = aether_rad::compiler::CodegenContext::new(&project.variables);
+    assert_eq!(ctx.text_expr(&label.text).to_string(), r#""Plain {text}""#);
+    assert!(ctx.widget_code(&label).to_string().contains(r#"ui . label ("Plain {text}")"#));
+
+    // The canvas preview fills in the live values
+    let mut preview = PreviewState::new(&project);
+    assert_eq!(preview.interpolate("Count: {counter:>3} of {total}"), "Count:   0 of 9");
+    preview.set_number("counter", 12.0);
+    assert_eq!(preview.interpolate("{counter}/{total} {missing}"), "12/9 {missing}");
+}
+
+#[test]
+fn test_event_action_sequences_and_conditions() {
+    use aether_rad::importer::Importer;
+    use aether_rad::preview::PreviewState;
+    use aether_rad::validator::CodeValidator;
+
+    let mut project = ProjectState::template_counter_app();
+    project.variables.insert(
+        "limited".to_string(),
+        Variable { name: "limited".to_string(), v_type: VariableType::Boolean, value: "true".to_string() },
+    );
+    let actions = vec![
+        Action::IncrementVariable("counter".to_string()),
+        Action::If {
+            condition: Condition { variable: "counter".to_string(), op: CompareOp::Ge, value: "2".to_string() },
+            then: vec![
+                Action::SetVariable("counter".to_string(), "0".to_string()),
+                Action::SetVariable("limited".to_string(), "false".to_string()),
+            ],
+            otherwise: vec![Action::Custom("println!(\"below\");".to_string())],
+        },
+    ];
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, actions.clone());
+    // An event can be handled without any actions yet
+    button.events.insert(WidgetEvent::Hovered, Vec::new());
+    project.screens[0].root.children_mut().unwrap().push(Box::new(button.clone()));
+
+    let json = serde_json::to_string(&button).unwrap();
+    assert_eq!(serde_json::from_str::<ButtonWidget>(&json).unwrap().events, button.events);
+    // Projects saved with one action per event load it as a one-item list
+    let mut saved = serde_json::to_value(&button).unwrap();
+    saved["events"]["Clicked"] = serde_json::to_value(Action::IncrementVariable("counter".to_string())).unwrap();
+    let loaded = serde_json::from_value::<ButtonWidget>(saved).unwrap();
+    assert_eq!(loaded.events[&WidgetEvent::Clicked], vec![Action::IncrementVariable("counter".to_string())]);
+
+    // Actions run in order, with the branch chosen at run time
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(
+        code.contains("self.counter += 1;\n") && code.contains("if self.counter >= 2 {"),
+        "{}",
+        code
+    );
+    assert!(code.contains("} else {\n") && code.contains("println!(\"below\");"), "{}", code);
+
+    // The preview follows the same branches
+    let mut preview = PreviewState::new(&project);
+    let events = button.events.clone();
+    preview.fire("Button", &events, WidgetEvent::Clicked);
+    assert_eq!(preview.display("counter").as_deref(), Some("1"));
+    assert!(preview.log()[0].message.contains("if counter >= 2: else not simulated"), "{:?}", preview.log());
+    preview.fire("Button", &events, WidgetEvent::Clicked);
+    assert_eq!(preview.display("counter").as_deref(), Some("0"));
+    assert_eq!(preview.display("limited").as_deref(), Some("false"));
+
+    // Generated statements import back as the same actions
+    let imported = Importer::import_source(&code).expect("Generated code should import");
+    let imported_button = imported.root().children().unwrap().iter().find(|c| c.id() == button.id).unwrap();
+    assert_eq!(imported_button.events().unwrap()[&WidgetEvent::Clicked], actions);
+    assert_eq!(imported_button.events().unwrap()[&WidgetEvent::Hovered], Vec::new());
+
+    // Nested actions are validated too
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+    let mut bad = ButtonWidget::default();
+    bad.events.insert(
+        WidgetEvent::Clicked,
+        vec![Action::If {
+            condition: Condition { variable: "counter".to_string(), op: CompareOp::IsTrue, value: String::new() },
+            then: vec![Action::NavigateTo("Nowhere".to_string())],
+            otherwise: Vec::new(),
+        }],
+    );
+    project.screens[0].root.children_mut().unwrap().push(Box::new(bad));
+    let errors = CodeValidator::validate_logic(&project);
+    assert!(errors.iter().any(|e| e.message.contains("missing screen 'Nowhere'")), "{:?}", errors);
+    assert!(errors.iter().any(|e| e.message.contains("needs a boolean variable")), "{:?}", errors);
+
+    // Compared values are written with the variable's type and checked against it
+    project.variables.insert(
+        "ratio".to_string(),
+        Variable { name: "ratio".to_string(), v_type: VariableType::Float, value: "0.5".to_string() },
+    );
+    let compare = |variable: &str, op: CompareOp, value: &str| Action::If {
+        condition: Condition { variable: variable.to_string(), op, value: value.to_string() },
+        then: vec![Action::ToggleVariable("limited".to_string())],
+        otherwise: Vec::new(),
+    };
+    let mut typed = ButtonWidget::default();
+    typed.events.insert(
+        WidgetEvent::Clicked,
+        vec![
+            compare("ratio", CompareOp::Ge, "10"),
+            compare("counter", CompareOp::Eq, "abc"),
+            compare("counter", CompareOp::Lt, "2.5"),
+        ],
+    );
+    project.screens[0].root.children_mut().unwrap().push(Box::new(typed));
+    let code = Compiler::generate_app_generated_rs(&project);
+    assert!(code.contains("if self.ratio >= 10.0 {"), "{}", code);
+    let errors = CodeValidator::validate_logic(&project);
+    assert!(errors.iter().any(|e| e.message.contains("'counter == abc' compares with something that isn't")), "{:?}", errors);
+    assert!(errors.iter().any(|e| e.message.contains("'counter < 2.5': '2.5' is not an integer")), "{:?}", errors);
+}
+
+#[test]
+fn test_builtin_actions() {
+    use aether_rad::importer::Importer;
+    use aether_rad::model::ActionTarget;
+    use aether_rad::preview::{PreviewRequest, PreviewState};
+    use aether_rad::validator::CodeValidator;
+
+    let mut project = ProjectState::template_counter_app();
+    for (name, v_type, value) in [
+        ("volume", VariableType::F32, "0.5"),
+        ("muted", VariableType::Boolean, "false"),
+        ("best", VariableType::Integer, "0"),
+        ("names", VariableType::List(Box::new(VariableType::String)), r#"["a"]"#),
+    ] {
+        project.variables.insert(name.to_string(), Variable { name: name.to_string(), v_type, value: value.to_string() });
+    }
+    let mut window = WindowWidget::default();
+    let mut close = ButtonWidget::default();
+    close.events.insert(WidgetEvent::Clicked, vec![Action::SetWindowOpen { window: window.id, open: false }]);
+    window.children.push(Box::new(close.clone()));
+    let tabs = TabContainerWidget::default();
+    let actions = vec![
+        Action::DecrementVariable("counter".to_string()),
+        Action::AddToVariable("volume".to_string(), "0.25".to_string()),
+        Action::ToggleVariable("muted".to_string()),
+        Action::CopyVariable { from: "counter".to_string(), to: "best".to_string() },
+        Action::PushToList("names".to_string(), "\"b\".to_string()".to_string()),
+        Action::ClearList("names".to_string()),
+        Action::SelectTab { container: tabs.id, tab: 1 },
+        Action::OpenUrl("https://example.com/{counter}".to_string()),
+        Action::CopyToClipboard("Best: {best}".to_string()),
+    ];
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, actions.clone());
+    let root = project.screens[0].root.children_mut().unwrap();
+    root.push(Box::new(button.clone()));
+    root.push(window.clone_box());
+    root.push(tabs.clone_box());
+
+    assert_eq!(
+        project.action_targets(),
+        [
+            ActionTarget::Window { id: window.id, title: "Window".to_string() },
+            ActionTarget::Tabs { id: tabs.id, tabs: vec!["Tab 1".to_string(), "Tab 2".to_string()] },
+        ]
+    );
+    assert!(CodeValidator::validate_logic(&project).is_empty(), "{:?}", CodeValidator::validate_logic(&project));
+
+    let code = Compiler::generate_app_generated_rs(&project);
+    for expected in [
+        "self.counter -= 1;",
+        "self.volume += 0.25;",
+        "self.muted = !self.muted;",
+        "self.best = self.counter;",
+        "self.names.push(\"b\".to_string());",
+        "self.names.clear();",
+        &format!("self.tab_{}_selected = 1;", tabs.id.simple()),
+        "open_url(egui::OpenUrl::new_tab(format!(\"https://example.com/{}\", self.counter)",
+        "ui.ctx().copy_text(format!(\"Best: {}\", self.best));",
+        &format!("self.window_{}_open = false;", window.id.simple()),
+        &format!("self.window_{}_open &= window_open;", window.id.simple()),
+    ] {
+        // Ignore how long lines were wrapped
+        let flat = |text: &str| text.split_whitespace().collect::<String>();
+        assert!(flat(&code).contains(&flat(expected)), "missing `{}` in\n{}", expected, code);
+    }
+
+    // The preview runs them against its live state, and hands URLs and clipboard text to the editor
+    let mut preview = PreviewState::new(&project);
+    let outcome = Action::Sequence(actions.clone()).simulate(&mut preview);
+    assert_eq!(preview.display("counter").as_deref(), Some("-1"), "{}", outcome);
+    assert_eq!(preview.display("volume").as_deref(), Some("0.75"));
+    assert_eq!(preview.display("muted").as_deref(), Some("true"));
+    assert_eq!(preview.display("best").as_deref(), Some("-1"));
+    assert_eq!(preview.display("names").as_deref(), Some("[]"));
+    assert_eq!(preview.widget_value(tabs.id, 0usize), 1);
+    assert_eq!(
+        preview.take_requests(),
+        [
+            PreviewRequest::OpenUrl("https://example.com/-1".to_string()),
+            PreviewRequest::CopyText("Best: -1".to_string()),
+        ]
+    );
+    Action::PushToList("names".to_string(), "\"b\".to_string()".to_string()).simulate(&mut preview);
+    assert_eq!(preview.display("names").as_deref(), Some(r#"["b"]"#));
+    let events = close.events.clone();
+    preview.fire("Button", &events, WidgetEvent::Clicked);
+    assert!(!preview.widget_value(window.id, true));
+
+    // Generated statements import back as the same actions
+    let imported = Importer::import_source(&code).expect("Generated code should import");
+    let children = imported.root().children().unwrap();
+    let imported_button = children.iter().find(|c| c.id() == button.id).unwrap();
+    let imported_actions = imported_button.events().unwrap()[&WidgetEvent::Clicked].clone();
+    // Tab containers aren't imported, so switching tabs stays an assignment
+    assert_eq!(imported_actions[..6], actions[..6]);
+    assert_eq!(imported_actions[7..], actions[7..]);
+    let imported_window = children.iter().find(|c| c.id() == window.id).expect("window keeps its id");
+    let imported_close = &imported_window.children().unwrap()[0];
+    assert_eq!(imported_close.events(), Some(&close.events));
+    assert!(!imported.variables.contains_key(&format!("window_{}_open", window.id.simple())));
+
+    // Actions are checked against the variables and widgets they use
+    let mut bad = ButtonWidget::default();
+    bad.events.insert(
+        WidgetEvent::Clicked,
+        vec![
+            Action::ToggleVariable("counter".to_string()),
+            Action::ClearList("missing".to_string()),
+            Action::CopyVariable { from: "volume".to_string(), to: "counter".to_string() },
+            Action::SelectTab { container: tabs.id, tab: 5 },
+            Action::SetWindowOpen { window: tabs.id, open: true },
+        ],
+    );
+    project.screens[0].root.children_mut().unwrap().push(Box::new(bad));
+    let errors: Vec<String> = CodeValidator::validate_logic(&project).into_iter().map(|e| e.message).collect();
+    for expected in [
+        "cannot toggle Integer variable 'counter'",
+        "uses missing variable 'missing'",
+        "cannot copy f32 'volume' into Integer 'counter'",
+        "selects missing tab 6",
+        "opens or closes a missing window",
+    ] {
+        assert!(errors.iter().any(|e| e.contains(expected)), "missing '{}' in {:?}", expected, errors);
+    }
+}
+
+#[test]
+fn test_common_widget_properties() {
+    use aether_rad::importer::Importer;
+    use aether_rad::preview::PreviewState;
+    use aether_rad::validator::CodeValidator;
+
+    let mut project = ProjectState::template_counter_app();
+    project.variables.insert(
+        "advanced".to_string(),
+        Variable { name: "advanced".to_string(), v_type: VariableType::Boolean, value: "false".to_string() },
+    );
+    let mut label = LabelWidget { text: "Details".to_string(), ..Default::default() };
+    label.common.visible = Flag::Bound("advanced".to_string());
+    let mut button = ButtonWidget {
+        common: CommonProperties {
+            tooltip: "Clicked {counter} times".to_string(),
+            enabled: Flag::Static(false),
+            visible: Flag::Static(true),
+        },
+        ..Default::default()
+    };
+    button.events.insert(WidgetEvent::Clicked, vec![Action::IncrementVariable("counter".to_string())]);
+    let root = project.screens[0].root.children_mut().unwrap();
+    root.push(Box::new(label.clone()));
+    root.push(Box::new(button.clone()));
+    assert!(CodeValidator::validate_logic(&project).is_empty());
+
+    // Widgets with default settings are not wrapped
+    let code = Compiler::generate_app_generated_rs(&project);
+    let flat: String = code.split_whitespace().collect();
+    assert_eq!(code.matches("add_enabled_ui").count(), 1, "{}", code);
+    assert!(flat.contains("ifself.advanced{ui.label(\"Details\");}"), "{}", code);
+    assert!(flat.contains("ui.scope(|ui|{ui.add_enabled_ui(false,|ui|{"), "{}", code);
+    assert!(flat.contains(".response.on_hover_text(format!(\"Clicked{}times\",self.counter));"), "{}", code);
+
+    // Older projects without the block load with the defaults
+    let json = serde_json::json!({ "type": "LabelWidget", "id": label.id, "text": "Old", "bindings": {} });
+    let old: Box<dyn WidgetNode> = serde_json::from_value(json).unwrap();
+    assert!(old.common().is_default());
+    let saved: Box<dyn WidgetNode> = serde_json::from_str(&serde_json::to_string(&(Box::new(button.clone()) as Box<dyn WidgetNode>)).unwrap()).unwrap();
+    assert_eq!(saved.common(), &button.common);
+
+    // The wrappers import back onto the widget
+    let imported = Importer::import_source(&code).expect("Generated code should import");
+    let children = imported.root().children().unwrap();
+    let imported_label = children.iter().find(|c| c.name() == "Label" && c.common().visible != Flag::Static(true)).unwrap();
+    assert_eq!(imported_label.common(), &label.common);
+    let imported_button = children.iter().find(|c| c.id() == button.id).unwrap();
+    assert_eq!(imported_button.common(), &button.common);
+
+    // The preview applies them without panicking
+    let mut preview = PreviewState::new(&project);
+    let ctx = egui::Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                project.root().render_preview(ui, &mut preview);
+            });
+        });
+    }
+
+    // Flags must follow boolean variables, and tooltips may only show existing ones
+    let broken = LabelWidget {
+        common: CommonProperties {
+            tooltip: "{missing}".to_string(),
+            enabled: Flag::Bound("counter".to_string()),
+            visible: Flag::Bound("nowhere".to_string()),
+        },
+        ..Default::default()
+    };
+    project.screens[0].root.children_mut().unwrap().push(Box::new(broken));
+    let errors: Vec<String> = CodeValidator::validate_logic(&project).into_iter().map(|e| e.message).collect();
+    for expected in [
+        "enabled state needs a boolean variable, not Integer 'counter'",
+        "visible state follows missing variable 'nowhere'",
+        "tooltip: {missing} refers to unknown variable 'missing'",
+    ] {
+        assert!(errors.iter().any(|e| e.contains(expected)), "missing '{}' in {:?}", expected, errors);
+    }
+}
+
+#[test]
+fn test_property_descriptors() {
+    use aether_rad::model::{PropertyKind, PropertyValue};
+    use aether_rad::widgets::create_widget_by_name;
+
+    let names = [
+        "Button", "Label", "Text Edit", "Checkbox", "Slider", "Progress Bar", "ComboBox", "Image",
+        "Vertical Layout", "Horizontal Layout", "Grid Layout", "Freeform Layout", "Separator",
+        "Spinner", "Hyperlink", "Custom Code", "Color Picker", "Table", "Plot", "Scroll Area",
+        "Tab Container", "Window",
+    ];
+    for name in names {
+        let mut widget = create_widget_by_name(name).unwrap();
+        let descriptors = widget.properties();
+        for descriptor in &descriptors {
+            // Defaults describe a new widget, and every value reads back as its kind
+            let value = descriptor.get(widget.as_ref());
+            assert_eq!(value.as_ref(), Some(&descriptor.default), "{}.{}", name, descriptor.name);
+            assert_eq!(descriptor.kind.normalize(descriptor.default.clone()).as_ref(), Some(&descriptor.default));
+            assert!(descriptor.set(widget.as_mut(), descriptor.default.clone()), "{}.{}", name, descriptor.name);
+            assert_eq!(descriptors.iter().filter(|d| d.name == descriptor.name).count(), 1);
+            if descriptor.bindable {
+                assert!(widget.bindings().is_some(), "{}.{} is bindable", name, descriptor.name);
+            }
+        }
+        assert_eq!(widget.property("no_such_property"), None);
+    }
+
+    // Setters clamp to the property's range and reject values of the wrong type
+    let mut grid: Box<dyn WidgetNode> = Box::new(GridLayout::default());
+    let columns = grid.properties().into_iter().find(|d| d.name == "columns").unwrap();
+    assert_eq!(columns.kind, PropertyKind::Integer { min: 1, max: 10 });
+    assert!(columns.set(grid.as_mut(), PropertyValue::Integer(50)));
+    assert_eq!(grid.property("columns"), Some(PropertyValue::Integer(10)));
+    assert!(!columns.set(grid.as_mut(), PropertyValue::Text("3".to_string())));
+    assert!(!grid.set_property("spacing", PropertyValue::Bool(true)));
+    assert!(grid.set_property("spacing", PropertyValue::Float(12.0)));
+    assert_eq!(grid.property("spacing"), Some(PropertyValue::Float(12.0)));
+
+    // Ranges can depend on the widget itself
+    let combo = ComboBoxWidget::default();
+    let selected = combo.properties().into_iter().find(|d| d.name == "selected").unwrap();
+    assert_eq!(selected.kind, PropertyKind::Integer { min: 0, max: 2 });
+    assert!(selected.bindable);
+
+    let mut layout = VerticalLayout::default();
+    assert!(layout.set_property("alignment", PropertyValue::Choice(1)));
+    assert_eq!(layout.alignment, aether_rad::widgets::LayoutAlignment::Center);
+    assert!(!layout.set_property("alignment", PropertyValue::Choice(3)));
+    assert!(layout.set_property("max_width", PropertyValue::OptionalFloat(Some(320.0))));
+    assert_eq!(layout.max_width, Some(320.0));
+    let groups: Vec<_> = layout.properties().iter().map(|d| d.group).collect();
+    assert_eq!(groups[0], Some("Layout Options"));
+    assert_eq!(groups[4], Some("Size Constraints"));
+
+    // Row spacing is not limited, as before descriptors existed
+    let row = HorizontalLayout::default();
+    let spacing = row.properties().into_iter().find(|d| d.name == "spacing").unwrap();
+    assert_eq!(spacing.kind, PropertyKind::Float { min: f64::NEG_INFINITY, max: f64::INFINITY, speed: 0.1 });
+    assert!(grid.set_property("spacing", PropertyValue::Float(80.0)));
+    assert_eq!(grid.property("spacing"), Some(PropertyValue::Float(80.0)));
+
+    // Text Edit's "value" property is stored in its text field
+    let mut edit = TextEditWidget::default();
+    assert!(edit.set_property("value", PropertyValue::Text("typed".to_string())));
+    assert_eq!(edit.text, "typed");
+
+    // The generic Inspector editor draws every widget
+    let vars = vec!["counter".to_string()];
+    let mut widgets: Vec<_> = names.iter().map(|name| create_widget_by_name(name).unwrap()).collect();
+    let mut label = LabelWidget::default();
+    label.bindings.insert("text".to_string(), "counter".to_string());
+    widgets.push(Box::new(label));
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            for widget in &mut widgets {
+                ui.push_id(widget.id(), |ui| widget.inspect(ui, &vars, &[]));
+            }
+        });
+    });
+}
+
+#[test]
+fn test_bulk_property_editing() {
+    use aether_rad::model::PropertyValue;
+
+    let layouts: Vec<Box<dyn WidgetNode>> = vec![
+        Box::new(VerticalLayout::default()),
+        Box::new(VerticalLayout { spacing: 8.0, ..Default::default() }),
+        Box::new(HorizontalLayout::default()),
+        Box::new(GridLayout::default()),
+        Box::new(HorizontalLayout::default()),
+    ];
+    let layout_ids: Vec<_> = layouts.iter().map(|l| l.id()).collect();
+    let label = LabelWidget::default();
+    let button = ButtonWidget { text: "Label".to_string(), ..Default::default() };
+    let checkbox = CheckboxWidget::default();
+    let ids = (label.id, button.id, checkbox.id);
+    let mut root = VerticalLayout::default();
+    root.children.extend(layouts);
+    root.children.push(Box::new(label));
+    root.children.push(Box::new(button));
+    root.children.push(Box::new(checkbox));
+    let mut project = ProjectState::new(Box::new(root));
+
+    // Layouts of different types share spacing, whose values differ
+    let shared = project.shared_properties(&layout_ids);
+    let names: Vec<_> = shared.iter().map(|p| p.descriptor.name).collect();
+    assert_eq!(names, ["spacing"]);
+    assert!(shared[0].mixed);
+    assert_eq!(shared[0].value, PropertyValue::Float(5.0));
+
+    // One edit reaches every widget, each clamped to its own range
+    assert_eq!(project.set_property(&layout_ids, "spacing", &PropertyValue::Float(12.0)), 5);
+    let shared = project.shared_properties(&layout_ids);
+    assert!(!shared[0].mixed);
+    assert_eq!(shared[0].value, PropertyValue::Float(12.0));
+    assert_eq!(project.set_property(&layout_ids, "spacing", &PropertyValue::Float(12.0)), 0);
+    assert_eq!(project.set_property(&layout_ids, "spacing", &PropertyValue::Float(500.0)), 5);
+    assert_eq!(project.find_node(layout_ids[0]).unwrap().property("spacing"), Some(PropertyValue::Float(50.0)));
+    assert_eq!(project.find_node(layout_ids[3]).unwrap().property("spacing"), Some(PropertyValue::Float(500.0)));
+
+    // Same name and type across widget types; equal values aren't mixed
+    let shared = project.shared_properties(&[ids.0, ids.1]);
+    assert_eq!(shared.len(), 1);
+    assert_eq!(shared[0].descriptor.name, "text");
+    assert!(!shared[0].mixed);
+
+    // Properties with the same name but another type are not shared
+    assert!(project.shared_properties(&[ids.0, ids.2]).is_empty());
+    assert!(project.shared_properties(&[ids.0, layout_ids[0]]).is_empty());
+    assert_eq!(project.set_property(&[ids.0, ids.2], "text", &PropertyValue::Text("Hi".to_string())), 1);
+
+    // A bound widget shows as mixed and keeps its static value
+    project
+        .find_node_mut(ids.1)
+        .unwrap()
+        .bindings_mut()
+        .unwrap()
+        .insert("text".to_string(), "counter".to_string());
+    let shared = project.shared_properties(&[ids.0, ids.1]);
+    assert!(shared[0].mixed);
+    assert!(project.shared_properties(&[ids.1])[0].mixed);
+    assert_eq!(project.set_property(&[ids.0, ids.1], "text", &PropertyValue::Text("Bye".to_string())), 1);
+    assert_eq!(project.find_node(ids.1).unwrap().property("text"), Some(PropertyValue::Text("Label".to_string())));
+
+    // The Inspector queues edits for the app to apply as one undo step, and clones drop them
+    project.pending_property_edit = Some(aether_rad::model::PropertyEdit {
+        widgets: layout_ids.clone(),
+        property: "spacing".to_string(),
+        value: PropertyValue::Float(3.0),
+    });
+    assert!(project.clone().pending_property_edit.is_none());
+}
+
+#[test]
+fn test_diff_undo_history() {
+    use aether_rad::history::{History, Patch};
+
+    // A large tree: 20 layouts of 25 labels
+    let mut root = VerticalLayout::default();
+    for _ in 0..20 {
+        let mut layout = HorizontalLayout::default();
+        for i in 0..25 {
+            layout.children.push(Box::new(LabelWidget { text: format!("Label {}", i), ..Default::default() }));
+        }
+        root.children.push(Box::new(layout));
+    }
+    let mut project = ProjectState::new(Box::new(root));
+    project.variables.insert(
+        "count".to_string(),
+        Variable { name: "count".to_string(), v_type: VariableType::Integer, value: "0".to_string() },
+    );
+    let mut history = History::new(&project);
+    let ids = project.get_all_widget_ids();
+    let (layout_id, label_id, other_label_id) = (ids[1], ids[2], ids[30]);
+    let project_size = serde_json::to_string(&project).unwrap().len();
+    assert!(!history.record(&project, 0.0));
+    assert!(!history.can_undo());
+
+    // A property edit stores only the changed value
+    project.find_node_mut(label_id).unwrap().set_property("text", "Hello".into());
+    assert!(history.record(&project, 1.0));
+    let size = |history: &History| {
+        history.undo_entries().iter().flat_map(|e| &e.patches).map(|p| format!("{:?}", p).len()).sum::<usize>()
+    };
+    assert_eq!(history.undo_entries().len(), 1);
+    assert_eq!(history.undo_entries()[0].patches.len(), 1);
+    assert!(size(&history) * 100 < project_size, "{} vs {}", size(&history), project_size);
+
+    // Typing into the same field shortly after is merged into one step
+    for (i, time) in [1.3, 1.6, 2.2].iter().enumerate() {
+        project.find_node_mut(label_id).unwrap().set_property("text", format!("Hello {}", i).into());
+        history.record(&project, *time);
+    }
+    assert_eq!(history.undo_entries().len(), 1);
+    // ...but not after a pause, nor edits of another value
+    project.find_node_mut(label_id).unwrap().set_property("text", "Later".into());
+    history.record(&project, 5.0);
+    project.find_node_mut(other_label_id).unwrap().set_property("text", "Other".into());
+    history.record(&project, 5.1);
+    assert_eq!(history.undo_entries().len(), 3);
+    // Editing a value back to where the step started leaves no step behind
+    project.find_node_mut(other_label_id).unwrap().set_property("text", "Label 2".into());
+    history.record(&project, 5.2);
+    assert_eq!(history.undo_entries().len(), 2);
+
+    // Deleting a widget is a splice of that one widget
+    project.selection.insert(layout_id);
+    assert!(project.delete_widget(label_id));
+    project.variables.get_mut("count").unwrap().value = "5".to_string();
+    history.record(&project, 6.0);
+    let patches = &history.undo_entries().last().unwrap().patches;
+    assert!(patches.iter().any(|p| matches!(p, Patch::Splice { removed, inserted, .. } if removed.len() == 1 && inserted.is_empty())));
+
+    // Reparenting is undoable too
+    let slider = SliderWidget::default();
+    let slider_id = slider.id;
+    project.find_node_mut(layout_id).unwrap().children_mut().unwrap().insert(3, Box::new(slider));
+    history.checkpoint();
+    history.record(&project, 7.0);
+    assert!(project.reparent_widget(slider_id, ids[0], 0));
+    history.record(&project, 7.1);
+    assert_eq!(project.get_parent_id(slider_id), Some(ids[0]));
+
+    assert!(history.undo(&mut project));
+    assert_eq!(project.get_parent_id(slider_id), Some(layout_id));
+    assert!(history.undo(&mut project));
+    assert!(project.find_node(slider_id).is_none());
+    assert!(history.undo(&mut project));
+    assert_eq!(project.find_node(label_id).unwrap().property("text"), Some("Later".into()));
+    assert_eq!(project.get_all_widget_ids()[2], label_id);
+    assert_eq!(project.variables["count"].value, "0");
+    // The selection is where the user is, not part of the design
+    assert!(project.selection.contains(&layout_id));
+    assert!(history.undo(&mut project));
+    assert!(history.undo(&mut project));
+    assert_eq!(project.find_node(label_id).unwrap().property("text"), Some("Label 0".into()));
+    assert!(!history.undo(&mut project));
+
+    // Redo walks forward again, and a new edit discards what is left to redo
+    assert!(history.redo(&mut project));
+    assert!(history.redo(&mut project));
+    assert_eq!(project.find_node(label_id).unwrap().property("text"), Some("Later".into()));
+    assert!(history.can_redo());
+    project.project_name = "renamed".to_string();
+    history.record(&project, 20.0);
+    assert!(!history.can_redo());
+
+    // Unrecorded changes are undone first
+    project.find_node_mut(other_label_id).unwrap().set_property("text", "Unrecorded".into());
+    assert!(history.undo(&mut project));
+    assert_eq!(project.find_node(other_label_id).unwrap().property("text"), Some("Label 2".into()));
+    assert_eq!(project.project_name, "renamed");
+}
+
+#[test]
+fn test_history_labels_and_jump() {
+    use aether_rad::history::History;
+
+    let mut root = VerticalLayout::default();
+    let button = ButtonWidget::default();
+    let label = LabelWidget::default();
+    let row = HorizontalLayout::default();
+    let (button_id, label_id, row_id) = (button.id, label.id, row.id);
+    root.children.push(Box::new(button));
+    root.children.push(Box::new(label));
+    root.children.push(Box::new(row));
+    let root_id = root.id;
+    let mut project = ProjectState::new(Box::new(root));
+    let mut history = History::new(&project);
+    let step = |project: &ProjectState, history: &mut History, time: f64| {
+        history.checkpoint();
+        assert!(history.record(project, time));
+        history.undo_entries().last().unwrap().label.clone()
+    };
+
+    project.find_node_mut(button_id).unwrap().set_property("text", "Go".into());
+    assert_eq!(step(&project, &mut history, 1.0), "Set Button.text");
+    project.find_node_mut(button_id).unwrap().common_mut().tooltip = "Start".to_string();
+    assert_eq!(step(&project, &mut history, 2.0), "Set Button.tooltip");
+    project.set_property(&[button_id, label_id], "text", &"Same".into());
+    assert_eq!(step(&project, &mut history, 3.0), "Set text on 2 widgets");
+
+    let slider = SliderWidget::default();
+    let slider_id = slider.id;
+    project.find_node_mut(root_id).unwrap().children_mut().unwrap().push(Box::new(slider));
+    assert_eq!(step(&project, &mut history, 4.0), "Add Slider");
+    assert!(project.reparent_widget(slider_id, row_id, 0));
+    assert_eq!(step(&project, &mut history, 5.0), "Reparent Slider into Horizontal Layout");
+    assert!(project.delete_widget(label_id));
+    assert_eq!(step(&project, &mut history, 6.0), "Delete Label");
+    project.project_name = "renamed".to_string();
+    assert_eq!(step(&project, &mut history, 7.0), "Rename project");
+
+    // Jumping back and forth restores each point, keeping later steps to redo
+    assert_eq!(history.position(), 7);
+    assert!(history.go_to(&mut project, 1));
+    assert_eq!(project.find_node(button_id).unwrap().property("text"), Some("Go".into()));
+    assert_eq!(project.find_node(button_id).unwrap().common().tooltip, "");
+    assert!(project.find_node(slider_id).is_none());
+    assert!(project.find_node(label_id).is_some());
+    assert_eq!(history.entries().count(), 7);
+    assert_eq!(history.redo_entries().last().unwrap().label, "Set Button.tooltip");
+
+    assert!(history.go_to(&mut project, 5));
+    assert_eq!(project.get_parent_id(slider_id), Some(row_id));
+    assert!(project.find_node(label_id).is_some());
+    assert!(history.go_to(&mut project, 0));
+    assert_eq!(project.find_node(button_id).unwrap().property("text"), Some("Click Me".into()));
+    assert!(!history.go_to(&mut project, 8));
+    assert!(history.go_to(&mut project, 7));
+    assert_eq!(project.project_name, "renamed");
+    assert!(project.find_node(label_id).is_none());
+
+    // Frames that were not marked as possibly changed don't compare the project
+    project.project_name = "quiet".to_string();
+    assert!(!history.record_if_changed(&project, 8.0));
+    history.mark_changed();
+    assert!(history.record_if_changed(&project, 8.1));
+    assert_eq!(history.position(), 8);
+}
+
+#[test]
+fn test_clipboard_copy_paste() {
+    use aether_rad::model::parse_clipboard;
+
+    let mut root = VerticalLayout::default();
+    let mut row = HorizontalLayout::default();
+    let label = LabelWidget { text: "Inner".to_string(), ..Default::default() };
+    let label_id = label.id;
+    row.children.push(Box::new(label));
+    let window = WindowWidget::default();
+    let window_id = window.id;
+    let mut button = ButtonWidget::default();
+    button.events.insert(WidgetEvent::Clicked, vec![Action::SetWindowOpen { window: window_id, open: true }]);
+    let button_id = button.id;
+    let (row_id, root_id) = (row.id, root.id);
+    root.children.push(Box::new(row));
+    root.children.push(Box::new(button));
+    root.children.push(Box::new(window));
+    let mut project = ProjectState::new(Box::new(root));
+
+    // Copying a container and something inside it copies the container once
+    let json = project.copy_widgets(&[label_id, row_id]).unwrap();
+    assert_eq!(parse_clipboard(&json).unwrap().len(), 1);
+    assert!(project.copy_widgets(&[]).is_none());
+    assert!(parse_clipboard("just some text").is_none());
+
+    // Pasted subtrees get fresh ids all the way down, and go after the selected widget
+    project.selection = [button_id].into();
+    let pasted = project.paste_widgets(&json);
+    assert_eq!(pasted.len(), 1);
+    let ids = project.get_all_widget_ids();
+    let unique: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(unique.len(), ids.len());
+    assert_eq!(project.root().children().unwrap()[2].id(), pasted[0]);
+    let copy = project.find_node(pasted[0]).unwrap();
+    assert_ne!(copy.children().unwrap()[0].id(), label_id);
+    assert_eq!(copy.children().unwrap()[0].property("text"), Some("Inner".into()));
+    assert!(project.selection.contains(&pasted[0]) && project.selection.len() == 1);
+
+    // Several widgets paste in hierarchy order, after the last selected one
+    let json = project.copy_widgets(&[window_id, button_id]).unwrap();
+    project.selection = [button_id, label_id].into();
+    let pasted = project.paste_widgets(&json);
+    assert_eq!(pasted.len(), 2);
+    assert_eq!(project.get_parent_id(pasted[0]), Some(root_id));
+    let order: Vec<_> = project.root().children().unwrap().iter().map(|c| c.id()).collect();
+    assert_eq!(order[2..4], pasted[..]);
+    let (new_button, new_window) = (project.find_node(pasted[0]).unwrap(), project.find_node(pasted[1]).unwrap());
+    assert_eq!(new_button.name(), "Button");
+    assert_eq!(new_window.name(), "Window");
+    // The copied button opens the copied window, not the original
+    assert!(matches!(
+        new_button.events().unwrap().get(&WidgetEvent::Clicked).map(Vec::as_slice),
+        Some([Action::SetWindowOpen { window, .. }]) if *window == pasted[1]
+    ));
+
+    // A single selected container receives the paste
+    project.selection = [row_id].into();
+    let pasted = project.paste_widgets(&json);
+    assert_eq!(project.get_parent_id(pasted[0]), Some(row_id));
+    assert_eq!(project.find_node(row_id).unwrap().children().unwrap().len(), 3);
+
+    // Text that happens to equal a copied id is the user's, and is pasted as it was
+    project.find_node_mut(label_id).unwrap().set_property("text", label_id.to_string().into());
+    let json = project.copy_widgets(&[row_id]).unwrap();
+    let pasted = project.paste_widgets(&json);
+    let copy = project.find_node(pasted[0]).unwrap();
+    assert_ne!(copy.children().unwrap()[0].id(), label_id);
+    assert_eq!(copy.children().unwrap()[0].property("text"), Some(label_id.to_string().into()));
+}
+
+#[test]
+fn test_container_wrap_unwrap_convert() {
+    use aether_rad::widgets::ScrollAreaWidget;
+
+    let mut root = VerticalLayout::default();
+    let button = ButtonWidget::default();
+    let label = LabelWidget::default();
+    let (button_id, label_id, root_id) = (button.id, label.id, root.id);
+    root.children.push(Box::new(button));
+    root.children.push(Box::new(label));
+    let mut project = ProjectState::new(Box::new(root));
+    let mut history = aether_rad::history::History::new(&project);
+
+    // Wrapping keeps the widget's place
+    let window_id = project.wrap_widget(label_id, "Window").unwrap();
+    assert_eq!(project.find_node(window_id).unwrap().name(), "Window");
+    assert_eq!(project.get_parent_id(label_id), Some(window_id));
+    assert_eq!(project.root().children().unwrap()[1].id(), window_id);
+    assert!(project.wrap_widget(root_id, "Grid Layout").is_none());
+    assert!(project.wrap_widget(button_id, "Button").is_none());
+
+    // Converting keeps the id, children and common properties
+    project.find_node_mut(window_id).unwrap().common_mut().tooltip = "Kept".to_string();
+    history.record(&project, 0.0);
+    assert!(project.convert_container(window_id, "Scroll Area"));
+    history.checkpoint();
+    history.record(&project, 1.0);
+    assert_eq!(history.undo_entries().last().unwrap().label, "Convert Window to Scroll Area");
+    let converted = project.find_node(window_id).unwrap();
+    assert_eq!(converted.name(), "Scroll Area");
+    assert_eq!(converted.common(), &CommonProperties { tooltip: "Kept".to_string(), ..Default::default() });
+    assert_eq!(project.get_parent_id(label_id), Some(window_id));
+    assert!(!project.convert_container(button_id, "Grid Layout"));
+    assert!(project.convert_container(root_id, "Grid Layout"));
+    assert_eq!(project.root().name(), "Grid Layout");
+    assert!(!project.convert_container(root_id, "Window"));
+
+    // Unwrapping puts the children back where the container was
+    let scroll = ScrollAreaWidget::default();
+    let scroll_id = scroll.id;
+    project.find_node_mut(window_id).unwrap().children_mut().unwrap().push(Box::new(scroll));
+    assert_eq!(project.unwrap_container(window_id), Some(vec![label_id, scroll_id]));
+    let order: Vec<_> = project.root().children().unwrap().iter().map(|c| c.id()).collect();
+    assert_eq!(order, vec![button_id, label_id, scroll_id]);
+    assert!(project.unwrap_container(root_id).is_none());
+    assert!(project.unwrap_container(button_id).is_none());
+
+    // Duplicates get fresh ids and go right after the original
+    let copy_id = project.duplicate_widget(button_id).unwrap();
+    assert_ne!(copy_id, button_id);
+    assert_eq!(project.root().children().unwrap()[1].id(), copy_id);
+    assert!(project.duplicate_widget(root_id).is_none());
+}
+
+#[test]
+fn test_convert_unwrap_tabs_and_freeform() {
+    use aether_rad::model::{ChildLayout, ChildPosition};
+
+    let mut tabs = TabContainerWidget::default();
+    let (first, second) = (LabelWidget::default(), LabelWidget::default());
+    let (first_id, second_id, tabs_id) = (first.id, second.id, tabs.id);
+    tabs.tabs[0].children.push(Box::new(first));
+    tabs.tabs[1].children.push(Box::new(second));
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(tabs));
+    let root_id = root.id;
+    let mut project = ProjectState::new(Box::new(root));
+    let layout_of = |project: &ProjectState, id| project.child_layout(project.child_position(id).unwrap()).unwrap();
+    let placed = |x, y| ChildLayout::Placed { x, y, width: None, height: None };
+
+    // Every tab's children move across; flow children get stacked positions in a freeform
+    assert!(project.convert_container(tabs_id, "Freeform Layout"));
+    assert_eq!(project.find_node(tabs_id).unwrap().name(), "Freeform Layout");
+    assert_eq!(project.child_position(second_id), Some(ChildPosition { parent: tabs_id, slot: 0, index: 1 }));
+    assert_eq!(layout_of(&project, first_id), placed(0.0, 0.0));
+    assert_eq!(layout_of(&project, second_id), placed(0.0, 30.0));
+
+    // Unwrapping inside a positioned parent keeps the children where they were on screen
+    let outer_id = project.wrap_widget(tabs_id, "Freeform Layout").unwrap();
+    let mut outer = project.find_node_mut(outer_id).unwrap().slots_mut();
+    outer[0].children.set_layout(0, placed(100.0, 50.0));
+    drop(outer);
+    assert_eq!(project.unwrap_container(tabs_id), Some(vec![first_id, second_id]));
+    assert_eq!(project.get_parent_id(first_id), Some(outer_id));
+    assert_eq!(layout_of(&project, first_id), placed(100.0, 50.0));
+    assert_eq!(layout_of(&project, second_id), placed(100.0, 80.0));
+
+    // A tab container receives everything in its selected tab
+    assert!(project.convert_container(outer_id, "Tab Container"));
+    let converted = project.find_node(outer_id).unwrap();
+    assert_eq!(converted.slots()[0].children.len(), 2);
+    assert_eq!(converted.slots()[1].children.len(), 0);
+    assert_eq!(layout_of(&project, second_id), ChildLayout::Flow);
+
+    // Flow children of an unwrapped container stack below its position
+    assert!(project.convert_container(root_id, "Freeform Layout"));
+    assert_eq!(project.root().name(), "Freeform Layout");
+    assert_eq!(project.unwrap_container(outer_id), Some(vec![first_id, second_id]));
+    assert_eq!(layout_of(&project, first_id), placed(0.0, 0.0));
+    assert_eq!(layout_of(&project, second_id), placed(0.0, 30.0));
+}
+
+#[test]
+fn test_child_slots() {
+    use aether_rad::model::{ChildLayout, ChildPosition};
+    use aether_rad::validator::CodeValidator;
+    use aether_rad::widgets::{FreeformChild, FreeformLayout};
+
+    let mut freeform = FreeformLayout::default();
+    let placed = ButtonWidget::default();
+    let placed_id = placed.id;
+    freeform.children.push(FreeformChild { widget: Box::new(placed), x: 40.0, y: 20.0, width: None, height: None });
+    let mut tabs = TabContainerWidget::default();
+    let first_tab = LabelWidget::default();
+    // A problem in a tab that isn't selected
+    let second_tab = LabelWidget {
+        common: CommonProperties { tooltip: "{missing}".to_string(), ..Default::default() },
+        ..Default::default()
+    };
+    let (first_tab_id, second_tab_id) = (first_tab.id, second_tab.id);
+    tabs.tabs[0].children.push(Box::new(first_tab));
+    tabs.tabs[1].children.push(Box::new(second_tab));
+    let (freeform_id, tabs_id) = (freeform.id, tabs.id);
+    let mut root = VerticalLayout::default();
+    root.children.push(Box::new(freeform));
+    root.children.push(Box::new(tabs));
+    let mut project = ProjectState::new(Box::new(root));
+
+    // Every tab and every positioned child is part of the tree
+    let tab_node = project.find_node(tabs_id).unwrap();
+    let names: Vec<_> = tab_node.slots().iter().map(|slot| slot.name.clone()).collect();
+    assert_eq!(names, ["Tab 1", "Tab 2"]);
+    let ids = project.get_all_widget_ids();
+    for id in [placed_id, first_tab_id, second_tab_id] {
+        assert!(ids.contains(&id));
+    }
+    assert_eq!(project.get_parent_id(placed_id), Some(freeform_id));
+    let position = project.child_position(second_tab_id).unwrap();
+    assert_eq!(position, ChildPosition { parent: tabs_id, slot: 1, index: 0 });
+    assert_eq!(
+        project.child_layout(project.child_position(placed_id).unwrap()),
+        Some(ChildLayout::Placed { x: 40.0, y: 20.0, width: None, height: None })
+    );
+    assert!(project.find_node_mut(placed_id).unwrap().set_property("text", "Placed".into()));
+    assert!(project.is_container(freeform_id));
+
+    let errors = CodeValidator::validate_logic(&project);
+    assert!(errors.iter().any(|e| e.widget == Some(second_tab_id)), "{:?}", errors);
+
+    // Moving between slots; a widget coming from a flow slot starts at the freeform's top left
+    assert!(project.move_widget_to(placed_id, ChildPosition { parent: tabs_id, slot: 1, index: 0 }));
+    assert_eq!(project.child_position(placed_id), Some(ChildPosition { parent: tabs_id, slot: 1, index: 0 }));
+    assert_eq!(project.child_position(second_tab_id).unwrap().index, 1);
+    assert_eq!(project.child_layout(project.child_position(placed_id).unwrap()), Some(ChildLayout::Flow));
+    assert!(project.move_widget_to(placed_id, ChildPosition { parent: freeform_id, slot: 0, index: 0 }));
+    assert_eq!(
+        project.child_layout(project.child_position(placed_id).unwrap()),
+        Some(ChildLayout::Placed { x: 0.0, y: 0.0, width: None, height: None })
+    );
+    assert!(!project.move_widget_to(placed_id, ChildPosition { parent: tabs_id, slot: 5, index: 0 }));
+    assert!(project.find_node(placed_id).is_some());
+
+    // Reordering, deleting and duplicating work inside tabs too
+    assert!(project.move_widget_after(first_tab_id, second_tab_id));
+    assert_eq!(project.child_position(first_tab_id).unwrap(), ChildPosition { parent: tabs_id, slot: 1, index: 1 });
+    let copy_id = project.duplicate_widget(placed_id).unwrap();
+    assert!(matches!(
+        project.child_layout(project.child_position(copy_id).unwrap()),
+        Some(ChildLayout::Placed { x, .. }) if x == 10.0
+    ));
+    assert!(project.delete_widget(second_tab_id));
+    assert!(project.find_node(second_tab_id).is_none());
+
+    // Pasting into a tab container goes to the selected tab
+    let json = project.copy_widgets(&[copy_id]).unwrap();
+    project.selection = [tabs_id].into();
+    let pasted = project.paste_widgets(&json);
+    assert_eq!(project.child_position(pasted[0]).unwrap().slot, 0);
+}